@@ -22,6 +22,10 @@ pub enum FinanceError {
     DivisionByZero,
     /// Valor inválido
     InvalidInput,
+    /// Precio fuera de las cotas de no arbitraje
+    ArbitrageViolation,
+    /// El método numérico no convergió
+    NoConvergence,
 }
 
 impl std::fmt::Display for FinanceError {
@@ -32,6 +36,8 @@ impl std::fmt::Display for FinanceError {
             FinanceError::InvalidMonetaryValue => write!(f, "Valor monetario inválido"),
             FinanceError::DivisionByZero => write!(f, "División por cero en cálculo financiero"),
             FinanceError::InvalidInput => write!(f, "Valor inválido"),
+            FinanceError::ArbitrageViolation => write!(f, "Precio fuera de las cotas de no arbitraje"),
+            FinanceError::NoConvergence => write!(f, "El método numérico no convergió"),
         }
     }
 }
@@ -58,6 +64,9 @@ pub type Interest = f64;
 /// Precio del activo subyacente (S0)
 pub type SpotPrice = f64;
 
+/// Precio forward o de futuro del subyacente (F)
+pub type ForwardPrice = f64;
+
 /// Precio de ejercicio (K)
 pub type StrikePrice = f64;
 
@@ -67,6 +76,24 @@ pub type TimeToMaturity = f64;
 /// Volatilidad anualizada (σ)
 pub type Volatility = f64;
 
+/// Rendimiento por dividendo continuo anualizado (q)
+pub type DividendYield = f64;
+
+/// Coste de mantenimiento anualizado (b): b = r para acciones sin dividendos,
+/// b = r - q con rendimiento continuo, b = 0 para futuros, b = r - r_f para divisas
+pub type CostOfCarry = f64;
+
+/// Cotización de volatilidad implícita de una opción europea
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VolQuote {
+    /// Precio de ejercicio (K)
+    pub strike: StrikePrice,
+    /// Vencimiento en años (T)
+    pub maturity: TimeToMaturity,
+    /// Volatilidad implícita de Black-Scholes cotizada
+    pub volatility: Volatility,
+}
+
 /// Tipo de opción europea
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum OptionType {
@@ -74,6 +101,54 @@ pub enum OptionType {
     Put,
 }
 
+impl OptionType {
+    /// Valor intrínseco: max(S - K, 0) para call y max(K - S, 0) para put.
+    #[inline]
+    pub fn payoff(self, s: SpotPrice, k: StrikePrice) -> f64 {
+        match self {
+            OptionType::Call => (s - k).max(0.0),
+            OptionType::Put  => (k - s).max(0.0),
+        }
+    }
+}
+
+/// Estilo de ejercicio de una opción
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExerciseStyle {
+    /// Solo al vencimiento
+    European,
+    /// En cualquier momento hasta el vencimiento
+    American,
+    /// En un conjunto discreto de fechas (en años desde hoy)
+    Bermudan(Vec<TimeInYears>),
+}
+
+/// Tipo de barrera de una opción barrera
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BarrierType {
+    DownAndOut,
+    DownAndIn,
+    UpAndOut,
+    UpAndIn,
+}
+
+impl BarrierType {
+    /// `true` si la barrera está por debajo del subyacente
+    pub fn is_down(self) -> bool {
+        matches!(self, BarrierType::DownAndOut | BarrierType::DownAndIn)
+    }
+
+    /// `true` si la opción se activa al tocar la barrera
+    pub fn is_knock_in(self) -> bool {
+        matches!(self, BarrierType::DownAndIn | BarrierType::UpAndIn)
+    }
+
+    /// `true` si el nivel `s` está en la barrera o más allá
+    pub fn is_breached(self, s: SpotPrice, barrier: SpotPrice) -> bool {
+        if self.is_down() { s <= barrier } else { s >= barrier }
+    }
+}
+
 // === TIPOS PARA TEORÍA DE PORTFOLIOS ===
 
 /// Rentabilidad esperada de una inversión
@@ -4,9 +4,10 @@ pub mod common;
 pub mod time_value;
 pub mod options;
 pub mod portfolio;
+pub mod num;
 
 // Re-exportar los tipos más comunes
-pub use common::{FinanceError, FinanceResult, OptionType};
+pub use common::{FinanceError, FinanceResult, OptionType, ExerciseStyle, BarrierType, VolQuote};
 
 // Re-exportar funciones principales de time_value
 pub use time_value::{
@@ -18,7 +19,8 @@ pub use time_value::{
 
 // Re-exportar funciones principales de options
 pub use options::{
-    call_price, put_price, option_price, d1_d2
+    call_price, put_price, option_price, option_greeks, implied_volatility, d1_d2, Greeks,
+    call_price_dividend, put_price_dividend, option_price_dividend, option_greeks_dividend
 };
 
 // Re-exportar funciones principales de portfolio
@@ -77,6 +79,14 @@ mod tests {
         let parity_rhs = s0 - k * df;
         
         assert!((parity_lhs - parity_rhs).abs() < 1e-10);
+
+        // Con dividendo continuo q: C - P = S0*e^(-qT) - K*e^(-rT)
+        let q = 0.02;
+        let call_q = call_price_dividend(s0, k, t, r, q, sigma).unwrap();
+        let put_q = put_price_dividend(s0, k, t, r, q, sigma).unwrap();
+        let parity_rhs_q = s0 * (-q * t).exp() - k * df;
+
+        assert!((call_q - put_q - parity_rhs_q).abs() < 1e-10);
     }
 
     #[test]
@@ -0,0 +1,206 @@
+//! Números complejos.
+//!
+//! Lo justo para evaluar funciones características: aritmética, exponencial,
+//! logaritmo y raíz cuadrada (ramas principales).
+
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+/// Número complejo re + i·im
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Complex {
+    pub re: f64,
+    pub im: f64,
+}
+
+impl Complex {
+    /// Unidad imaginaria
+    pub const I: Complex = Complex { re: 0.0, im: 1.0 };
+
+    /// Crea el complejo re + i·im
+    pub const fn new(re: f64, im: f64) -> Self {
+        Complex { re, im }
+    }
+
+    /// Módulo |z|
+    pub fn abs(self) -> f64 {
+        self.re.hypot(self.im)
+    }
+
+    /// Argumento en (-π, π]
+    pub fn arg(self) -> f64 {
+        self.im.atan2(self.re)
+    }
+
+    /// Conjugado
+    pub fn conj(self) -> Self {
+        Complex::new(self.re, -self.im)
+    }
+
+    /// e^z
+    pub fn exp(self) -> Self {
+        let modulus = self.re.exp();
+        Complex::new(modulus * self.im.cos(), modulus * self.im.sin())
+    }
+
+    /// Logaritmo principal: ln|z| + i arg z
+    pub fn ln(self) -> Self {
+        Complex::new(self.abs().ln(), self.arg())
+    }
+
+    /// Raíz cuadrada principal (parte real no negativa).
+    ///
+    /// La componente pequeña se obtiene dividiendo por la grande para no
+    /// perder precisión cuando z es casi real.
+    pub fn sqrt(self) -> Self {
+        if self.re == 0.0 && self.im == 0.0 {
+            return Complex::default();
+        }
+        let modulus = self.abs();
+        if self.re >= 0.0 {
+            let re = (0.5 * (modulus + self.re)).sqrt();
+            Complex::new(re, self.im / (2.0 * re))
+        } else {
+            let im = (0.5 * (modulus - self.re)).sqrt();
+            Complex::new(self.im.abs() / (2.0 * im), if self.im < 0.0 { -im } else { im })
+        }
+    }
+}
+
+impl From<f64> for Complex {
+    fn from(re: f64) -> Self {
+        Complex::new(re, 0.0)
+    }
+}
+
+impl Add for Complex {
+    type Output = Complex;
+    fn add(self, rhs: Complex) -> Complex {
+        Complex::new(self.re + rhs.re, self.im + rhs.im)
+    }
+}
+
+impl Sub for Complex {
+    type Output = Complex;
+    fn sub(self, rhs: Complex) -> Complex {
+        Complex::new(self.re - rhs.re, self.im - rhs.im)
+    }
+}
+
+impl Mul for Complex {
+    type Output = Complex;
+    fn mul(self, rhs: Complex) -> Complex {
+        Complex::new(self.re * rhs.re - self.im * rhs.im, self.re * rhs.im + self.im * rhs.re)
+    }
+}
+
+impl Div for Complex {
+    type Output = Complex;
+    fn div(self, rhs: Complex) -> Complex {
+        let denom = rhs.re * rhs.re + rhs.im * rhs.im;
+        Complex::new(
+            (self.re * rhs.re + self.im * rhs.im) / denom,
+            (self.im * rhs.re - self.re * rhs.im) / denom,
+        )
+    }
+}
+
+impl Neg for Complex {
+    type Output = Complex;
+    fn neg(self) -> Complex {
+        Complex::new(-self.re, -self.im)
+    }
+}
+
+impl Add<f64> for Complex {
+    type Output = Complex;
+    fn add(self, rhs: f64) -> Complex {
+        Complex::new(self.re + rhs, self.im)
+    }
+}
+
+impl Sub<f64> for Complex {
+    type Output = Complex;
+    fn sub(self, rhs: f64) -> Complex {
+        Complex::new(self.re - rhs, self.im)
+    }
+}
+
+impl Mul<f64> for Complex {
+    type Output = Complex;
+    fn mul(self, rhs: f64) -> Complex {
+        Complex::new(self.re * rhs, self.im * rhs)
+    }
+}
+
+impl Div<f64> for Complex {
+    type Output = Complex;
+    fn div(self, rhs: f64) -> Complex {
+        Complex::new(self.re / rhs, self.im / rhs)
+    }
+}
+
+impl Add<Complex> for f64 {
+    type Output = Complex;
+    fn add(self, rhs: Complex) -> Complex {
+        rhs + self
+    }
+}
+
+impl Sub<Complex> for f64 {
+    type Output = Complex;
+    fn sub(self, rhs: Complex) -> Complex {
+        Complex::new(self - rhs.re, -rhs.im)
+    }
+}
+
+impl Mul<Complex> for f64 {
+    type Output = Complex;
+    fn mul(self, rhs: Complex) -> Complex {
+        rhs * self
+    }
+}
+
+impl Div<Complex> for f64 {
+    type Output = Complex;
+    fn div(self, rhs: Complex) -> Complex {
+        Complex::from(self) / rhs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(a: Complex, b: Complex) -> bool {
+        (a - b).abs() < 1e-12
+    }
+
+    #[test]
+    fn arithmetic() {
+        let a = Complex::new(1.0, 2.0);
+        let b = Complex::new(-3.0, 0.5);
+        assert!(close(a * b, Complex::new(-4.0, -5.5)));
+        assert!(close(a / b * b, a));
+        assert!(close(Complex::I * Complex::I, Complex::from(-1.0)));
+        assert!(close(2.0 - a, Complex::new(1.0, -2.0)));
+        assert!(close(1.0 / Complex::I, -Complex::I));
+        assert!(close(a * a.conj(), Complex::from(5.0)));
+    }
+
+    #[test]
+    fn elementary_functions() {
+        // e^{iπ} = -1
+        assert!(close((Complex::I * std::f64::consts::PI).exp(), Complex::from(-1.0)));
+        let z = Complex::new(0.3, -1.7);
+        assert!(close(z.ln().exp(), z));
+        assert!(close(z.sqrt() * z.sqrt(), z));
+        assert!(z.sqrt().re >= 0.0);
+        // Rama principal sobre el eje real negativo
+        assert!(close(Complex::from(-4.0).sqrt(), Complex::new(0.0, 2.0)));
+        assert!((Complex::from(-1.0).ln().im - std::f64::consts::PI).abs() < 1e-15);
+        // Casi real: la parte imaginaria no se pierde por cancelación
+        let root = Complex::new(4.0, 1e-12).sqrt();
+        assert!((root.im - 2.5e-13).abs() < 1e-27);
+        assert_eq!(Complex::default().sqrt(), Complex::default());
+    }
+}
@@ -0,0 +1,105 @@
+//! Transformada rápida de Fourier.
+//!
+//! Cooley-Tukey radix 2 iterativa, in situ: reordenación por inversión de
+//! bits seguida de log₂ n etapas de mariposas. Calcula
+//! X_k = Σ_j x_j e^{-2πi jk/n}
+//! (la inversa usa el signo contrario y divide por n).
+
+use crate::common::{FinanceError, FinanceResult};
+use super::complex::Complex;
+
+/// Transformada directa in situ.
+///
+/// # Ejemplos
+/// ```
+/// use quant_finance::num::{fft, Complex};
+///
+/// let mut data = vec![Complex::from(1.0); 4];
+/// fft(&mut data).unwrap();
+/// assert_eq!(data[0], Complex::from(4.0));
+/// assert!(data[1..].iter().all(|z| z.abs() < 1e-15));
+/// ```
+///
+/// # Errores
+/// * `InvalidPeriods` - Si la longitud no es una potencia de dos
+pub fn fft(data: &mut [Complex]) -> FinanceResult<()> {
+    transform(data, -1.0)
+}
+
+/// Transformada inversa in situ (incluye el factor 1/n).
+///
+/// # Errores
+/// * `InvalidPeriods` - Si la longitud no es una potencia de dos
+pub fn inverse_fft(data: &mut [Complex]) -> FinanceResult<()> {
+    transform(data, 1.0)?;
+    let n = data.len() as f64;
+    for z in data.iter_mut() {
+        *z = *z / n;
+    }
+    Ok(())
+}
+
+fn transform(data: &mut [Complex], sign: f64) -> FinanceResult<()> {
+    let n = data.len();
+    if !n.is_power_of_two() {
+        return Err(FinanceError::InvalidPeriods);
+    }
+    if n == 1 {
+        return Ok(());
+    }
+    let shift = usize::BITS - n.trailing_zeros();
+    for i in 0..n {
+        let j = i.reverse_bits() >> shift;
+        if j > i {
+            data.swap(i, j);
+        }
+    }
+    let mut len = 2;
+    while len <= n {
+        let angle = sign * 2.0 * std::f64::consts::PI / len as f64;
+        let root = Complex::new(angle.cos(), angle.sin());
+        for chunk in data.chunks_mut(len) {
+            let (low, high) = chunk.split_at_mut(len / 2);
+            let mut twiddle = Complex::from(1.0);
+            for (a, b) in low.iter_mut().zip(high.iter_mut()) {
+                let t = twiddle * *b;
+                *b = *a - t;
+                *a = *a + t;
+                twiddle = twiddle * root;
+            }
+        }
+        len *= 2;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_direct_transform() {
+        let n = 16;
+        let x: Vec<Complex> = (0..n).map(|j| Complex::new((j as f64 * 0.7).sin(), (j * j) as f64 / 50.0)).collect();
+        let mut fast = x.clone();
+        fft(&mut fast).unwrap();
+        for (k, value) in fast.iter().enumerate() {
+            let direct = x.iter().enumerate().fold(Complex::default(), |acc, (j, xj)| {
+                let angle = -2.0 * std::f64::consts::PI * (j * k) as f64 / n as f64;
+                acc + *xj * Complex::new(angle.cos(), angle.sin())
+            });
+            assert!((*value - direct).abs() < 1e-12);
+        }
+        inverse_fft(&mut fast).unwrap();
+        assert!(fast.iter().zip(&x).all(|(a, b)| (*a - *b).abs() < 1e-14));
+    }
+
+    #[test]
+    fn length_must_be_power_of_two() {
+        assert_eq!(fft(&mut [Complex::default(); 12]).unwrap_err(), FinanceError::InvalidPeriods);
+        assert_eq!(fft(&mut []).unwrap_err(), FinanceError::InvalidPeriods);
+        let mut single = vec![Complex::new(2.0, 1.0)];
+        fft(&mut single).unwrap();
+        assert_eq!(single[0], Complex::new(2.0, 1.0));
+    }
+}
@@ -0,0 +1,126 @@
+//! Cuadratura numérica.
+//!
+//! Gauss-Legendre de n puntos sobre [a, b]: exacta para polinomios de grado
+//! 2n - 1. Los nodos son las raíces de P_n, obtenidas por Newton a partir de
+//! la aproximación x_i ≈ cos(π(i - 1/4)/(n + 1/2)), y los pesos
+//! w_i = 2 / [(1 - x_i²) P_n'(x_i)²].
+
+use crate::common::{FinanceError, FinanceResult};
+
+/// Regla de Gauss-Legendre precalculada
+#[derive(Debug, Clone, PartialEq)]
+pub struct GaussLegendre {
+    /// Nodos en [-1, 1]
+    pub nodes: Vec<f64>,
+    /// Pesos asociados
+    pub weights: Vec<f64>,
+}
+
+impl GaussLegendre {
+    /// Regla de `n` puntos.
+    ///
+    /// # Ejemplos
+    /// ```
+    /// use quant_finance::num::GaussLegendre;
+    ///
+    /// let rule = GaussLegendre::new(8).unwrap();
+    /// let integral = rule.integrate(|x| x.exp(), 0.0, 1.0);
+    /// assert!((integral - (1f64.exp() - 1.0)).abs() < 1e-14);
+    /// ```
+    ///
+    /// # Errores
+    /// * `InvalidPeriods` - Si `n` es cero
+    pub fn new(n: usize) -> FinanceResult<Self> {
+        if n == 0 {
+            return Err(FinanceError::InvalidPeriods);
+        }
+        let mut nodes = vec![0.0; n];
+        let mut weights = vec![0.0; n];
+        let nf = n as f64;
+        for i in 0..n.div_ceil(2) {
+            let mut x = (std::f64::consts::PI * (i as f64 + 0.75) / (nf + 0.5)).cos();
+            let mut derivative = 1.0;
+            for _ in 0..100 {
+                let (p, dp) = legendre(n, x);
+                derivative = dp;
+                let dx = p / dp;
+                x -= dx;
+                if dx.abs() < 1e-15 {
+                    break;
+                }
+            }
+            let w = 2.0 / ((1.0 - x * x) * derivative * derivative);
+            nodes[i] = -x;
+            nodes[n - 1 - i] = x;
+            weights[i] = w;
+            weights[n - 1 - i] = w;
+        }
+        Ok(GaussLegendre { nodes, weights })
+    }
+
+    /// ∫_a^b f(x) dx
+    pub fn integrate<F: Fn(f64) -> f64>(&self, f: F, a: f64, b: f64) -> f64 {
+        let half = 0.5 * (b - a);
+        let mid = 0.5 * (a + b);
+        half * self.nodes.iter().zip(&self.weights).map(|(x, w)| w * f(mid + half * x)).sum::<f64>()
+    }
+
+    /// Integral sobre [a, ∞) por paneles consecutivos de anchura `panel`,
+    /// hasta que dos paneles seguidos aportan menos de `tol` (en valor
+    /// absoluto) o se alcanzan `max_panels`. Pensada para integrandos con
+    /// decaimiento rápido, como los de las fórmulas de inversión de Fourier.
+    pub fn integrate_to_infinity<F: Fn(f64) -> f64>(&self, f: F, a: f64, panel: f64, tol: f64, max_panels: usize) -> f64 {
+        let mut total = 0.0;
+        let mut small = 0;
+        for i in 0..max_panels {
+            let lo = a + i as f64 * panel;
+            let contribution = self.integrate(&f, lo, lo + panel);
+            total += contribution;
+            small = if contribution.abs() < tol { small + 1 } else { 0 };
+            if small == 2 {
+                break;
+            }
+        }
+        total
+    }
+}
+
+/// (P_n(x), P_n'(x)) por la recurrencia de Bonnet.
+fn legendre(n: usize, x: f64) -> (f64, f64) {
+    let (mut p0, mut p1) = (1.0, x);
+    for k in 2..=n {
+        let kf = k as f64;
+        let p2 = ((2.0 * kf - 1.0) * x * p1 - (kf - 1.0) * p0) / kf;
+        p0 = p1;
+        p1 = p2;
+    }
+    let p = if n == 0 { 1.0 } else { p1 };
+    let dp = n as f64 * (x * p - p0) / (x * x - 1.0);
+    (p, dp)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exact_for_polynomials() {
+        let rule = GaussLegendre::new(5).unwrap();
+        // Grado 9 = 2·5 - 1
+        let integral = rule.integrate(|x| x.powi(9) + 3.0 * x.powi(4), -1.0, 2.0);
+        let exact = (2f64.powi(10) - 1.0) / 10.0 + 3.0 * (2f64.powi(5) + 1.0) / 5.0;
+        assert!((integral - exact).abs() < 1e-11);
+        let weights: f64 = rule.weights.iter().sum();
+        assert!((weights - 2.0).abs() < 1e-14);
+        assert!(GaussLegendre::new(1).unwrap().nodes == vec![0.0]);
+    }
+
+    #[test]
+    fn semi_infinite_integral() {
+        let rule = GaussLegendre::new(16).unwrap();
+        // ∫_0^∞ e^{-x²/2} dx = √(π/2)
+        let integral = rule.integrate_to_infinity(|x| (-0.5 * x * x).exp(), 0.0, 2.0, 1e-16, 100);
+        assert!((integral - (std::f64::consts::PI / 2.0).sqrt()).abs() < 1e-13);
+        assert_eq!(GaussLegendre::new(0).unwrap_err(), FinanceError::InvalidPeriods);
+    }
+}
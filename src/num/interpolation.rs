@@ -0,0 +1,134 @@
+//! Interpolación unidimensional.
+//!
+//! Lineal a trozos y spline cúbico natural (segunda derivada nula en los
+//! extremos). Las segundas derivadas M_i del spline resuelven el sistema
+//! tridiagonal
+//! h_{i-1} M_{i-1} + 2(h_{i-1} + h_i) M_i + h_i M_{i+1}
+//!     = 6[(y_{i+1} - y_i)/h_i - (y_i - y_{i-1})/h_{i-1}]
+//! con h_i = x_{i+1} - x_i. Fuera de los nodos ambos prolongan linealmente
+//! el tramo extremo.
+
+use crate::common::{FinanceError, FinanceResult};
+use super::linalg::solve_tridiagonal;
+
+/// Spline cúbico natural
+#[derive(Debug, Clone, PartialEq)]
+pub struct CubicSpline {
+    x: Vec<f64>,
+    y: Vec<f64>,
+    second_derivatives: Vec<f64>,
+}
+
+impl CubicSpline {
+    /// Construye el spline que pasa por los nodos (x_i, y_i).
+    ///
+    /// # Ejemplos
+    /// ```
+    /// use quant_finance::num::CubicSpline;
+    ///
+    /// let spline = CubicSpline::new(&[0.0, 1.0, 2.0, 3.0], &[0.0, 1.0, 8.0, 27.0]).unwrap();
+    /// assert_eq!(spline.evaluate(2.0), 8.0);
+    /// // Las condiciones naturales separan el spline de x³ entre los nodos
+    /// assert!((spline.evaluate(1.5) - 3.15).abs() < 1e-12);
+    /// ```
+    ///
+    /// # Errores
+    /// * `InvalidInput` - Si hay menos de dos nodos, las longitudes no
+    ///   coinciden o las abscisas no son finitas y estrictamente crecientes
+    pub fn new(x: &[f64], y: &[f64]) -> FinanceResult<Self> {
+        validate_nodes(x, y)?;
+        let n = x.len();
+        let mut second_derivatives = vec![0.0; n];
+        if n > 2 {
+            let h: Vec<f64> = x.windows(2).map(|w| w[1] - w[0]).collect();
+            let slopes: Vec<f64> = y.windows(2).zip(&h).map(|(w, hi)| (w[1] - w[0]) / hi).collect();
+            let lower: Vec<f64> = (1..n - 1).map(|i| h[i - 1]).collect();
+            let diag: Vec<f64> = (1..n - 1).map(|i| 2.0 * (h[i - 1] + h[i])).collect();
+            let upper: Vec<f64> = (1..n - 1).map(|i| h[i]).collect();
+            let rhs: Vec<f64> = slopes.windows(2).map(|s| 6.0 * (s[1] - s[0])).collect();
+            let interior = solve_tridiagonal(&lower, &diag, &upper, &rhs)?;
+            second_derivatives[1..n - 1].copy_from_slice(&interior);
+        }
+        Ok(CubicSpline { x: x.to_vec(), y: y.to_vec(), second_derivatives })
+    }
+
+    /// Valor del spline en `x`.
+    pub fn evaluate(&self, x: f64) -> f64 {
+        let (xs, ys, m) = (&self.x, &self.y, &self.second_derivatives);
+        let n = xs.len();
+        if x <= xs[0] || x >= xs[n - 1] {
+            // Prolongación lineal con la pendiente del spline en el extremo
+            let (i, edge) = if x <= xs[0] { (0, 0) } else { (n - 2, n - 1) };
+            let h = xs[i + 1] - xs[i];
+            let slope = (ys[i + 1] - ys[i]) / h
+                + if edge == 0 { -h * (2.0 * m[0] + m[1]) / 6.0 } else { h * (m[i] + 2.0 * m[i + 1]) / 6.0 };
+            return ys[edge] + slope * (x - xs[edge]);
+        }
+        let i = segment(xs, x);
+        let h = xs[i + 1] - xs[i];
+        let a = (xs[i + 1] - x) / h;
+        let b = (x - xs[i]) / h;
+        a * ys[i] + b * ys[i + 1] + ((a * a * a - a) * m[i] + (b * b * b - b) * m[i + 1]) * h * h / 6.0
+    }
+}
+
+/// Interpolación lineal a trozos en `x` de los nodos (xs_i, ys_i).
+///
+/// # Errores
+/// * `InvalidInput` - En los mismos casos que [`CubicSpline::new`]
+pub fn linear_interpolation(xs: &[f64], ys: &[f64], x: f64) -> FinanceResult<f64> {
+    validate_nodes(xs, ys)?;
+    let i = segment(xs, x);
+    let weight = (x - xs[i]) / (xs[i + 1] - xs[i]);
+    Ok(ys[i] + weight * (ys[i + 1] - ys[i]))
+}
+
+fn validate_nodes(x: &[f64], y: &[f64]) -> FinanceResult<()> {
+    if x.len() < 2 || x.len() != y.len() || x.iter().chain(y).any(|v| !v.is_finite()) || x.windows(2).any(|w| w[1] <= w[0]) {
+        return Err(FinanceError::InvalidInput);
+    }
+    Ok(())
+}
+
+/// Índice i del tramo [x_i, x_{i+1}] que contiene a `x` (el extremo si está fuera).
+fn segment(xs: &[f64], x: f64) -> usize {
+    xs.partition_point(|&xi| xi <= x).clamp(1, xs.len() - 1) - 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn linear_interpolation_and_extension() {
+        let (xs, ys) = ([1.0, 2.0, 4.0], [10.0, 20.0, 0.0]);
+        assert_eq!(linear_interpolation(&xs, &ys, 1.5).unwrap(), 15.0);
+        assert_eq!(linear_interpolation(&xs, &ys, 3.0).unwrap(), 10.0);
+        assert_eq!(linear_interpolation(&xs, &ys, 4.0).unwrap(), 0.0);
+        assert_eq!(linear_interpolation(&xs, &ys, 0.0).unwrap(), 0.0);
+        assert_eq!(linear_interpolation(&xs, &ys, 5.0).unwrap(), -10.0);
+        assert_eq!(linear_interpolation(&[1.0, 1.0], &[0.0, 1.0], 1.0).unwrap_err(), FinanceError::InvalidInput);
+        assert_eq!(linear_interpolation(&[1.0], &[0.0], 1.0).unwrap_err(), FinanceError::InvalidInput);
+    }
+
+    #[test]
+    fn natural_spline() {
+        // Reproduce rectas exactamente y es C² en los nodos interiores
+        let xs = [0.0, 0.5, 1.5, 2.0, 3.5];
+        let line = CubicSpline::new(&xs, &xs.map(|x| 2.0 * x - 1.0)).unwrap();
+        for x in [-1.0, 0.2, 1.0, 3.0, 5.0] {
+            assert!((line.evaluate(x) - (2.0 * x - 1.0)).abs() < 1e-12);
+        }
+        let spline = CubicSpline::new(&xs, &xs.map(f64::sin)).unwrap();
+        for (x, y) in xs.iter().zip(xs.map(f64::sin)) {
+            assert!((spline.evaluate(*x) - y).abs() < 1e-14);
+        }
+        let h = 1e-5;
+        let d2 = |x: f64| (spline.evaluate(x + h) - 2.0 * spline.evaluate(x) + spline.evaluate(x - h)) / (h * h);
+        assert!((d2(1.5 - 2.0 * h) - d2(1.5 + 2.0 * h)).abs() < 1e-3);
+        // Extensión lineal continua en el extremo derecho
+        assert!((spline.evaluate(3.5 + 1e-9) - spline.evaluate(3.5)).abs() < 1e-8);
+        assert!((spline.evaluate(1.0) - 1f64.sin()).abs() < 2e-2);
+        assert_eq!(CubicSpline::new(&[0.0, 1.0], &[0.0]).unwrap_err(), FinanceError::InvalidInput);
+    }
+}
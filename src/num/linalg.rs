@@ -0,0 +1,208 @@
+//! Álgebra lineal densa de pequeña dimensión.
+//!
+//! Pensada para sistemas de unas pocas incógnitas (regresiones con bases de
+//! grado bajo, calibraciones), por lo que prima la sencillez sobre el
+//! rendimiento: eliminación gaussiana con pivoteo parcial, algoritmo de
+//! Thomas para sistemas tridiagonales y mínimos cuadrados por ecuaciones
+//! normales.
+
+use crate::common::{FinanceError, FinanceResult};
+
+/// Pivote por debajo del cual la matriz se considera singular (relativo a la
+/// mayor entrada en valor absoluto).
+const SINGULAR_TOL: f64 = 1e-13;
+
+/// Resuelve A x = b por eliminación gaussiana con pivoteo parcial.
+///
+/// # Argumentos
+/// * `a` - Matriz cuadrada n×n, por filas
+/// * `b` - Término independiente de longitud n
+///
+/// # Ejemplos
+/// ```
+/// use quant_finance::num::solve_linear;
+///
+/// let x = solve_linear(vec![vec![2.0, 1.0], vec![1.0, 3.0]], vec![3.0, 5.0]).unwrap();
+/// assert!((x[0] - 0.8).abs() < 1e-12 && (x[1] - 1.4).abs() < 1e-12);
+/// ```
+///
+/// # Errores
+/// * `InvalidInput` - Si las dimensiones no son coherentes
+/// * `DivisionByZero` - Si la matriz es singular
+pub fn solve_linear(mut a: Vec<Vec<f64>>, mut b: Vec<f64>) -> FinanceResult<Vec<f64>> {
+    let n = b.len();
+    if n == 0 || a.len() != n || a.iter().any(|row| row.len() != n) {
+        return Err(FinanceError::InvalidInput);
+    }
+    let scale = a.iter().flatten().fold(0.0f64, |m, x| m.max(x.abs()));
+    if !scale.is_finite() || scale == 0.0 {
+        return Err(FinanceError::DivisionByZero);
+    }
+
+    for col in 0..n {
+        let pivot = (col..n)
+            .max_by(|&i, &j| a[i][col].abs().total_cmp(&a[j][col].abs()))
+            .unwrap_or(col);
+        if a[pivot][col].abs() <= SINGULAR_TOL * scale {
+            return Err(FinanceError::DivisionByZero);
+        }
+        a.swap(col, pivot);
+        b.swap(col, pivot);
+        let (done, rest) = a.split_at_mut(col + 1);
+        let pivot_row = &done[col];
+        for (offset, row) in rest.iter_mut().enumerate() {
+            let factor = row[col] / pivot_row[col];
+            if factor != 0.0 {
+                for (x, p) in row[col..].iter_mut().zip(&pivot_row[col..]) {
+                    *x -= factor * p;
+                }
+                b[col + 1 + offset] -= factor * b[col];
+            }
+        }
+    }
+
+    let mut x = vec![0.0; n];
+    for row in (0..n).rev() {
+        let tail: f64 = (row + 1..n).map(|j| a[row][j] * x[j]).sum();
+        x[row] = (b[row] - tail) / a[row][row];
+    }
+    Ok(x)
+}
+
+/// Resuelve un sistema tridiagonal por el algoritmo de Thomas:
+/// lower_i x_{i-1} + diag_i x_i + upper_i x_{i+1} = rhs_i.
+///
+/// `lower[0]` y `upper[n-1]` no se usan. No pivota, así que está pensado
+/// para matrices diagonalmente dominantes (como las de los esquemas
+/// implícitos de diferencias finitas).
+///
+/// # Errores
+/// * `InvalidInput` - Si las longitudes no coinciden o el sistema está vacío
+/// * `DivisionByZero` - Si se anula algún pivote
+pub fn solve_tridiagonal(lower: &[f64], diag: &[f64], upper: &[f64], rhs: &[f64]) -> FinanceResult<Vec<f64>> {
+    let n = diag.len();
+    if n == 0 || lower.len() != n || upper.len() != n || rhs.len() != n {
+        return Err(FinanceError::InvalidInput);
+    }
+    let mut c_star = vec![0.0; n];
+    let mut x = vec![0.0; n];
+    let mut pivot = diag[0];
+    for i in 0..n {
+        if i > 0 {
+            pivot = diag[i] - lower[i] * c_star[i - 1];
+        }
+        if pivot == 0.0 || !pivot.is_finite() {
+            return Err(FinanceError::DivisionByZero);
+        }
+        c_star[i] = upper[i] / pivot;
+        x[i] = (rhs[i] - if i > 0 { lower[i] * x[i - 1] } else { 0.0 }) / pivot;
+    }
+    for i in (0..n - 1).rev() {
+        x[i] -= c_star[i] * x[i + 1];
+    }
+    Ok(x)
+}
+
+/// Mínimos cuadrados ordinarios: β = argmin ‖Xβ - y‖², resuelto mediante
+/// las ecuaciones normales (XᵀX) β = Xᵀy.
+///
+/// # Argumentos
+/// * `x` - Filas de la matriz de diseño (todas de la misma longitud p)
+/// * `y` - Observaciones, una por fila
+///
+/// # Ejemplos
+/// ```
+/// use quant_finance::num::least_squares;
+///
+/// // Recta y = 1 + 2x
+/// let rows: Vec<Vec<f64>> = (0..5).map(|i| vec![1.0, i as f64]).collect();
+/// let y: Vec<f64> = (0..5).map(|i| 1.0 + 2.0 * i as f64).collect();
+/// let beta = least_squares(&rows, &y).unwrap();
+/// assert!((beta[0] - 1.0).abs() < 1e-10 && (beta[1] - 2.0).abs() < 1e-10);
+/// ```
+///
+/// # Errores
+/// * `InvalidInput` - Si hay menos observaciones que coeficientes o las
+///   dimensiones no son coherentes
+/// * `DivisionByZero` - Si las columnas de X son linealmente dependientes
+pub fn least_squares(x: &[Vec<f64>], y: &[f64]) -> FinanceResult<Vec<f64>> {
+    let p = x.first().map_or(0, Vec::len);
+    if p == 0 || x.len() != y.len() || x.len() < p || x.iter().any(|row| row.len() != p) {
+        return Err(FinanceError::InvalidInput);
+    }
+    let mut xtx = vec![vec![0.0; p]; p];
+    let mut xty = vec![0.0; p];
+    for (row, yi) in x.iter().zip(y) {
+        for ((xtx_i, xty_i), xi) in xtx.iter_mut().zip(xty.iter_mut()).zip(row) {
+            *xty_i += xi * yi;
+            for (xtx_ij, xj) in xtx_i.iter_mut().zip(row) {
+                *xtx_ij += xi * xj;
+            }
+        }
+    }
+    solve_linear(xtx, xty)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn solves_system_requiring_pivoting() {
+        // El primer pivote es nulo: sin intercambio de filas fallaría
+        let a = vec![
+            vec![0.0, 2.0, 1.0],
+            vec![1.0, 1.0, 1.0],
+            vec![2.0, 1.0, 3.0],
+        ];
+        let x = solve_linear(a.clone(), vec![5.0, 6.0, 13.0]).unwrap();
+        for (row, bi) in a.iter().zip([5.0, 6.0, 13.0]) {
+            let lhs: f64 = row.iter().zip(&x).map(|(aij, xj)| aij * xj).sum();
+            assert!((lhs - bi).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn tridiagonal_matches_dense_solver() {
+        let (lower, diag, upper) = ([0.0, -1.0, -1.0, -1.0], [4.0, 4.0, 4.0, 4.0], [-1.0, -1.0, -1.0, 0.0]);
+        let rhs = [1.0, 2.0, 3.0, 4.0];
+        let dense: Vec<Vec<f64>> = (0..4).map(|i| (0..4).map(|j| match j as i64 - i as i64 {
+            -1 => lower[i],
+            0 => diag[i],
+            1 => upper[i],
+            _ => 0.0,
+        }).collect()).collect();
+        let expected = solve_linear(dense, rhs.to_vec()).unwrap();
+        let x = solve_tridiagonal(&lower, &diag, &upper, &rhs).unwrap();
+        for (xi, ei) in x.iter().zip(&expected) {
+            assert!((xi - ei).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn quadratic_fit_recovers_coefficients() {
+        let rows: Vec<Vec<f64>> = (0..20).map(|i| {
+            let t = i as f64 / 10.0;
+            vec![1.0, t, t * t]
+        }).collect();
+        let y: Vec<f64> = rows.iter().map(|r| 0.5 - 1.5 * r[1] + 0.25 * r[2]).collect();
+        let beta = least_squares(&rows, &y).unwrap();
+        assert!((beta[0] - 0.5).abs() < 1e-10);
+        assert!((beta[1] + 1.5).abs() < 1e-10);
+        assert!((beta[2] - 0.25).abs() < 1e-10);
+    }
+
+    #[test]
+    fn degenerate_inputs() {
+        let singular = vec![vec![1.0, 2.0], vec![2.0, 4.0]];
+        assert_eq!(solve_linear(singular, vec![1.0, 2.0]).unwrap_err(), FinanceError::DivisionByZero);
+        assert_eq!(solve_linear(vec![vec![1.0]], vec![1.0, 2.0]).unwrap_err(), FinanceError::InvalidInput);
+        // Columnas repetidas
+        let rows = vec![vec![1.0, 1.0], vec![2.0, 2.0], vec![3.0, 3.0]];
+        assert_eq!(least_squares(&rows, &[1.0, 2.0, 3.0]).unwrap_err(), FinanceError::DivisionByZero);
+        // Menos observaciones que coeficientes
+        assert_eq!(least_squares(&[vec![1.0, 2.0]], &[1.0]).unwrap_err(), FinanceError::InvalidInput);
+        assert_eq!(solve_tridiagonal(&[0.0], &[0.0], &[0.0], &[1.0]).unwrap_err(), FinanceError::DivisionByZero);
+        assert_eq!(solve_tridiagonal(&[], &[], &[], &[]).unwrap_err(), FinanceError::InvalidInput);
+    }
+}
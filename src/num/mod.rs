@@ -0,0 +1,23 @@
+//! Herramientas numéricas
+//!
+//! Rutinas genéricas (búsqueda de raíces, números aleatorios, álgebra lineal,
+//! números complejos, cuadratura, optimización, FFT, interpolación, etc.) que
+//! reutilizan los distintos módulos de valoración.
+
+pub mod roots;
+pub mod random;
+pub mod linalg;
+pub mod complex;
+pub mod integration;
+pub mod optimize;
+pub mod fft;
+pub mod interpolation;
+
+pub use roots::{brent, newton_safeguarded};
+pub use random::Rng;
+pub use linalg::{solve_linear, solve_tridiagonal, least_squares};
+pub use complex::Complex;
+pub use integration::GaussLegendre;
+pub use optimize::{levenberg_marquardt, LeastSquaresFit};
+pub use fft::{fft, inverse_fft};
+pub use interpolation::{CubicSpline, linear_interpolation};
@@ -0,0 +1,174 @@
+//! Optimización: mínimos cuadrados no lineales con cotas.
+//!
+//! Levenberg-Marquardt con escalado de Marquardt: en cada iteración se
+//! resuelve (JᵀJ + λ diag(JᵀJ)) δ = -Jᵀr con el jacobiano J por diferencias
+//! finitas hacia delante, y el nuevo punto se proyecta sobre la caja
+//! [lower, upper]. λ se reduce tras un paso aceptado y se aumenta tras uno
+//! rechazado.
+
+use crate::common::{FinanceError, FinanceResult};
+use super::linalg::solve_linear;
+
+/// Resultado de un ajuste por mínimos cuadrados
+#[derive(Debug, Clone, PartialEq)]
+pub struct LeastSquaresFit {
+    /// Parámetros óptimos encontrados
+    pub x: Vec<f64>,
+    /// Residuos en el óptimo
+    pub residuals: Vec<f64>,
+    /// Suma de cuadrados de los residuos
+    pub cost: f64,
+    /// Iteraciones realizadas
+    pub iterations: usize,
+    /// Si se alcanzó la tolerancia antes de agotar las iteraciones
+    pub converged: bool,
+}
+
+/// Minimiza ‖r(x)‖² sujeto a lower ≤ x ≤ upper.
+///
+/// Los puntos en los que `residuals` devuelve error o valores no finitos se
+/// tratan como de coste infinito (el paso se rechaza). Se detiene cuando la
+/// mejora relativa del coste o el tamaño relativo del paso caen por debajo
+/// de `tol`.
+///
+/// # Ejemplos
+/// ```
+/// use quant_finance::num::levenberg_marquardt;
+///
+/// // Ajuste de y = a e^{b t}
+/// let data: Vec<(f64, f64)> = (0..10).map(|i| {
+///     let t = i as f64 / 5.0;
+///     (t, 2.0 * (-1.5 * t).exp())
+/// }).collect();
+/// let fit = levenberg_marquardt(
+///     |p| Ok(data.iter().map(|(t, y)| p[0] * (p[1] * t).exp() - y).collect()),
+///     &[1.0, 0.0], &[0.0, -10.0], &[10.0, 10.0], 200, 1e-12,
+/// ).unwrap();
+/// assert!((fit.x[0] - 2.0).abs() < 1e-6 && (fit.x[1] + 1.5).abs() < 1e-6);
+/// ```
+///
+/// # Errores
+/// * `InvalidInput` - Si las dimensiones no son coherentes, `lower > upper`
+///   o los residuos no pueden evaluarse en el punto inicial
+pub fn levenberg_marquardt<F>(residuals: F, x0: &[f64], lower: &[f64], upper: &[f64], max_iter: usize, tol: f64) -> FinanceResult<LeastSquaresFit>
+where
+    F: Fn(&[f64]) -> FinanceResult<Vec<f64>>,
+{
+    let n = x0.len();
+    if n == 0 || lower.len() != n || upper.len() != n || lower.iter().zip(upper).any(|(lo, hi)| lo.is_nan() || hi.is_nan() || lo > hi) {
+        return Err(FinanceError::InvalidInput);
+    }
+    let project = |x: &mut [f64]| {
+        for ((xi, lo), hi) in x.iter_mut().zip(lower).zip(upper) {
+            *xi = xi.clamp(*lo, *hi);
+        }
+    };
+    let evaluate = |x: &[f64]| -> Option<(Vec<f64>, f64)> {
+        let r = residuals(x).ok()?;
+        let cost: f64 = r.iter().map(|ri| ri * ri).sum();
+        cost.is_finite().then_some((r, cost))
+    };
+
+    let mut x = x0.to_vec();
+    project(&mut x);
+    let (mut r, mut cost) = evaluate(&x).ok_or(FinanceError::InvalidInput)?;
+    if r.is_empty() {
+        return Err(FinanceError::InvalidInput);
+    }
+    let mut lambda = 1e-3;
+
+    for iteration in 1..=max_iter {
+        // Jacobiano por diferencias hacia delante (hacia atrás si se sale de la caja)
+        let mut jacobian = vec![vec![0.0; n]; r.len()];
+        for j in 0..n {
+            let mut h = 1e-7 * x[j].abs().max(1.0);
+            if x[j] + h > upper[j] {
+                h = -h;
+            }
+            let mut shifted = x.clone();
+            shifted[j] += h;
+            let (r_shifted, _) = evaluate(&shifted).ok_or(FinanceError::InvalidInput)?;
+            for (row, (rs, r0)) in jacobian.iter_mut().zip(r_shifted.iter().zip(&r)) {
+                row[j] = (rs - r0) / h;
+            }
+        }
+        let mut jtj = vec![vec![0.0; n]; n];
+        let mut jtr = vec![0.0; n];
+        for (row, ri) in jacobian.iter().zip(&r) {
+            for i in 0..n {
+                jtr[i] += row[i] * ri;
+                for j in 0..n {
+                    jtj[i][j] += row[i] * row[j];
+                }
+            }
+        }
+
+        // Se aumenta λ hasta encontrar un paso que reduzca el coste
+        let mut accepted = None;
+        while lambda < 1e12 {
+            let mut damped = jtj.clone();
+            for (i, row) in damped.iter_mut().enumerate() {
+                row[i] += lambda * jtj[i][i].max(1e-12);
+            }
+            let rhs: Vec<f64> = jtr.iter().map(|g| -g).collect();
+            if let Ok(step) = solve_linear(damped, rhs) {
+                let mut candidate: Vec<f64> = x.iter().zip(&step).map(|(xi, di)| xi + di).collect();
+                project(&mut candidate);
+                if let Some((r_new, cost_new)) = evaluate(&candidate).filter(|(_, c)| *c < cost) {
+                    accepted = Some((candidate, r_new, cost_new));
+                    break;
+                }
+            }
+            lambda *= 4.0;
+        }
+
+        let Some((x_new, r_new, cost_new)) = accepted else {
+            // Ningún paso mejora: estamos en un mínimo (local) a precisión de máquina
+            return Ok(LeastSquaresFit { x, residuals: r, cost, iterations: iteration, converged: true });
+        };
+        let step_size = x_new.iter().zip(&x).map(|(a, b)| (a - b).abs() / b.abs().max(1.0)).fold(0.0, f64::max);
+        let improvement = (cost - cost_new) / cost.max(f64::MIN_POSITIVE);
+        x = x_new;
+        r = r_new;
+        cost = cost_new;
+        lambda = (lambda / 3.0).max(1e-12);
+        if improvement < tol || step_size < tol || cost < tol * tol {
+            return Ok(LeastSquaresFit { x, residuals: r, cost, iterations: iteration, converged: true });
+        }
+    }
+    Ok(LeastSquaresFit { x, residuals: r, cost, iterations: max_iter, converged: false })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rosenbrock_as_least_squares() {
+        // r = (10(y - x²), 1 - x): mínimo en (1, 1)
+        let fit = levenberg_marquardt(
+            |p| Ok(vec![10.0 * (p[1] - p[0] * p[0]), 1.0 - p[0]]),
+            &[-1.2, 1.0], &[-5.0, -5.0], &[5.0, 5.0], 500, 1e-14,
+        ).unwrap();
+        assert!(fit.converged);
+        assert!((fit.x[0] - 1.0).abs() < 1e-6 && (fit.x[1] - 1.0).abs() < 1e-6);
+        assert!(fit.cost < 1e-12);
+    }
+
+    #[test]
+    fn bounds_are_respected() {
+        // El mínimo libre está en x = 3, fuera de la caja [0, 2]
+        let fit = levenberg_marquardt(|p| Ok(vec![p[0] - 3.0]), &[0.5], &[0.0], &[2.0], 100, 1e-12).unwrap();
+        assert!((fit.x[0] - 2.0).abs() < 1e-12);
+        assert!((fit.cost - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn invalid_inputs() {
+        let ok = |p: &[f64]| Ok(vec![p[0]]);
+        assert_eq!(levenberg_marquardt(ok, &[1.0], &[2.0], &[0.0], 10, 1e-10).unwrap_err(), FinanceError::InvalidInput);
+        assert_eq!(levenberg_marquardt(ok, &[1.0, 2.0], &[0.0], &[2.0], 10, 1e-10).unwrap_err(), FinanceError::InvalidInput);
+        let failing = |_: &[f64]| Err(FinanceError::NoConvergence);
+        assert_eq!(levenberg_marquardt(failing, &[1.0], &[0.0], &[2.0], 10, 1e-10).unwrap_err(), FinanceError::InvalidInput);
+    }
+}
@@ -0,0 +1,207 @@
+//! Generador de números aleatorios reproducible.
+//!
+//! xoshiro256** (Blackman y Vigna, 2018) con el estado inicial obtenido de
+//! una semilla de 64 bits mediante SplitMix64. Las normales se generan con el
+//! método polar de Marsaglia, guardando la segunda variable de cada par; las
+//! de Poisson por inversión secuencial de la función de distribución, las
+//! gamma por el método de Marsaglia y Tsang (2000) y las inversas gaussianas
+//! por el de Michael, Schucany y Haas (1976).
+
+/// Generador pseudoaleatorio xoshiro256** con semilla
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rng {
+    state: [u64; 4],
+    spare_normal: Option<f64>,
+}
+
+impl Rng {
+    /// Crea un generador a partir de una semilla; la misma semilla produce
+    /// siempre la misma secuencia.
+    pub fn new(seed: u64) -> Self {
+        let mut sm = seed;
+        let mut next = || {
+            sm = sm.wrapping_add(0x9E37_79B9_7F4A_7C15);
+            let mut z = sm;
+            z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+            z ^ (z >> 31)
+        };
+        Rng { state: [next(), next(), next(), next()], spare_normal: None }
+    }
+
+    /// Siguiente entero de 64 bits
+    pub fn next_u64(&mut self) -> u64 {
+        let s = &mut self.state;
+        let result = s[1].wrapping_mul(5).rotate_left(7).wrapping_mul(9);
+        let t = s[1] << 17;
+        s[2] ^= s[0];
+        s[3] ^= s[1];
+        s[1] ^= s[2];
+        s[0] ^= s[3];
+        s[2] ^= t;
+        s[3] = s[3].rotate_left(45);
+        result
+    }
+
+    /// Uniforme en el intervalo abierto (0, 1)
+    pub fn uniform(&mut self) -> f64 {
+        ((self.next_u64() >> 11) as f64 + 0.5) / (1u64 << 53) as f64
+    }
+
+    /// Normal estándar N(0, 1)
+    pub fn normal(&mut self) -> f64 {
+        if let Some(z) = self.spare_normal.take() {
+            return z;
+        }
+        loop {
+            let u = 2.0 * self.uniform() - 1.0;
+            let v = 2.0 * self.uniform() - 1.0;
+            let s = u * u + v * v;
+            if s > 0.0 && s < 1.0 {
+                let factor = (-2.0 * s.ln() / s).sqrt();
+                self.spare_normal = Some(v * factor);
+                return u * factor;
+            }
+        }
+    }
+
+    /// Rellena `out` con normales estándar independientes
+    pub fn fill_normal(&mut self, out: &mut [f64]) {
+        for z in out.iter_mut() {
+            *z = self.normal();
+        }
+    }
+
+    /// Gamma de forma `shape` y escala 1 (Marsaglia-Tsang). Para formas
+    /// menores que 1 se usa Γ(a) = Γ(a + 1) U^{1/a}; devuelve 0 si `shape`
+    /// no es positiva.
+    pub fn gamma(&mut self, shape: f64) -> f64 {
+        if shape.is_nan() || shape <= 0.0 {
+            return 0.0;
+        }
+        if shape < 1.0 {
+            let boost = self.uniform().powf(1.0 / shape);
+            return self.gamma(shape + 1.0) * boost;
+        }
+        let d = shape - 1.0 / 3.0;
+        let c = 1.0 / (9.0 * d).sqrt();
+        loop {
+            let z = self.normal();
+            let v = (1.0 + c * z).powi(3);
+            if v <= 0.0 {
+                continue;
+            }
+            if self.uniform().ln() < 0.5 * z * z + d - d * v + d * v.ln() {
+                return d * v;
+            }
+        }
+    }
+
+    /// Inversa gaussiana de media `mean` y forma `shape`
+    /// (Michael-Schucany-Haas).
+    pub fn inverse_gaussian(&mut self, mean: f64, shape: f64) -> f64 {
+        let y = self.normal().powi(2);
+        let my = mean * y;
+        let x = mean + mean * my / (2.0 * shape) - mean / (2.0 * shape) * (4.0 * shape * my + my * my).sqrt();
+        if self.uniform() <= mean / (mean + x) { x } else { mean * mean / x }
+    }
+
+    /// Poisson de media `mean`, por inversión secuencial. Adecuado para
+    /// medias pequeñas, como el número de saltos λΔt en un paso de
+    /// simulación; devuelve 0 si `mean` no es positiva.
+    pub fn poisson(&mut self, mean: f64) -> u64 {
+        if mean.is_nan() || mean <= 0.0 {
+            return 0;
+        }
+        let u = self.uniform();
+        let mut probability = (-mean).exp();
+        let mut cumulative = probability;
+        let mut n = 0;
+        while u > cumulative && probability > 0.0 {
+            n += 1;
+            probability *= mean / n as f64;
+            cumulative += probability;
+        }
+        n
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_seed_same_sequence() {
+        let mut a = Rng::new(42);
+        let mut b = Rng::new(42);
+        let mut c = Rng::new(43);
+        let xs: Vec<u64> = (0..5).map(|_| a.next_u64()).collect();
+        let ys: Vec<u64> = (0..5).map(|_| b.next_u64()).collect();
+        let zs: Vec<u64> = (0..5).map(|_| c.next_u64()).collect();
+        assert_eq!(xs, ys);
+        assert_ne!(xs, zs);
+    }
+
+    #[test]
+    fn uniform_moments() {
+        let mut rng = Rng::new(7);
+        let n = 200_000;
+        let (mut sum, mut sum_sq) = (0.0, 0.0);
+        for _ in 0..n {
+            let u = rng.uniform();
+            assert!(u > 0.0 && u < 1.0);
+            sum += u;
+            sum_sq += u * u;
+        }
+        let mean = sum / n as f64;
+        assert!((mean - 0.5).abs() < 3e-3);
+        assert!((sum_sq / n as f64 - mean * mean - 1.0 / 12.0).abs() < 2e-3);
+    }
+
+    #[test]
+    fn normal_moments() {
+        let mut rng = Rng::new(2024);
+        let n = 200_000;
+        let mut z = vec![0.0; n];
+        rng.fill_normal(&mut z);
+        let mean = z.iter().sum::<f64>() / n as f64;
+        let var = z.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / n as f64;
+        let kurtosis = z.iter().map(|x| (x - mean).powi(4)).sum::<f64>() / n as f64 / (var * var);
+        assert!(mean.abs() < 1e-2);
+        assert!((var - 1.0).abs() < 1e-2);
+        assert!((kurtosis - 3.0).abs() < 0.05);
+    }
+
+    #[test]
+    fn poisson_moments() {
+        let mut rng = Rng::new(99);
+        let n = 200_000;
+        let draws: Vec<f64> = (0..n).map(|_| rng.poisson(0.7) as f64).collect();
+        let mean = draws.iter().sum::<f64>() / n as f64;
+        let var = draws.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / n as f64;
+        assert!((mean - 0.7).abs() < 1e-2);
+        assert!((var - 0.7).abs() < 1e-2);
+        assert_eq!(rng.poisson(0.0), 0);
+    }
+
+    #[test]
+    fn gamma_and_inverse_gaussian_moments() {
+        let mut rng = Rng::new(314);
+        let n = 200_000;
+        for shape in [0.3, 2.5] {
+            let draws: Vec<f64> = (0..n).map(|_| rng.gamma(shape)).collect();
+            let mean = draws.iter().sum::<f64>() / n as f64;
+            let var = draws.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / n as f64;
+            assert!(draws.iter().all(|&x| x >= 0.0));
+            assert!((mean / shape - 1.0).abs() < 1e-2, "{shape}: {mean}");
+            assert!((var / shape - 1.0).abs() < 3e-2, "{shape}: {var}");
+        }
+        // IG(μ, λ): media μ, varianza μ³/λ
+        let (mu, lambda) = (0.8, 2.0);
+        let draws: Vec<f64> = (0..n).map(|_| rng.inverse_gaussian(mu, lambda)).collect();
+        let mean = draws.iter().sum::<f64>() / n as f64;
+        let var = draws.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / n as f64;
+        assert!((mean - mu).abs() < 1e-2);
+        assert!((var - mu.powi(3) / lambda).abs() < 1e-2);
+    }
+}
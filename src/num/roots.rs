@@ -0,0 +1,203 @@
+//! Búsqueda de raíces en una variable.
+//!
+//! Ambos métodos exigen un intervalo [a, b] con cambio de signo, de modo que
+//! la convergencia está garantizada (a costa, como mucho, de bisección).
+
+use crate::common::{FinanceError, FinanceResult};
+
+/// Método de Brent (interpolación cuadrática inversa + secante + bisección).
+///
+/// # Argumentos
+/// * `f` - Función cuya raíz se busca
+/// * `a`, `b` - Extremos del intervalo, con f(a)·f(b) ≤ 0
+/// * `tol` - Tolerancia absoluta en x
+/// * `max_iter` - Número máximo de iteraciones
+///
+/// # Ejemplos
+/// ```
+/// use quant_finance::num::brent;
+///
+/// let root = brent(|x| x * x - 2.0, 0.0, 2.0, 1e-12, 100).unwrap();
+/// assert!((root - 2f64.sqrt()).abs() < 1e-10);
+/// ```
+///
+/// # Errores
+/// * `InvalidInput` - Si no hay cambio de signo en el intervalo
+/// * `NoConvergence` - Si se agotan las iteraciones
+pub fn brent<F: Fn(f64) -> f64>(f: F, a: f64, b: f64, tol: f64, max_iter: usize) -> FinanceResult<f64> {
+    let mut a = a;
+    let mut b = b;
+    let mut fa = f(a);
+    let mut fb = f(b);
+    if !fa.is_finite() || !fb.is_finite() || fa * fb > 0.0 {
+        return Err(FinanceError::InvalidInput);
+    }
+    if fa == 0.0 {
+        return Ok(a);
+    }
+
+    let mut c = b;
+    let mut fc = fb;
+    let mut d = b - a;
+    let mut e = d;
+
+    for _ in 0..max_iter {
+        if fb * fc > 0.0 {
+            c = a;
+            fc = fa;
+            d = b - a;
+            e = d;
+        }
+        if fc.abs() < fb.abs() {
+            a = b;
+            b = c;
+            c = a;
+            fa = fb;
+            fb = fc;
+            fc = fa;
+        }
+
+        let tol1 = 2.0 * f64::EPSILON * b.abs() + 0.5 * tol;
+        let xm = 0.5 * (c - b);
+        if xm.abs() <= tol1 || fb == 0.0 {
+            return Ok(b);
+        }
+
+        if e.abs() >= tol1 && fa.abs() > fb.abs() {
+            let s = fb / fa;
+            let (mut p, mut q) = if a == c {
+                (2.0 * xm * s, 1.0 - s)
+            } else {
+                let q = fa / fc;
+                let r = fb / fc;
+                (
+                    s * (2.0 * xm * q * (q - r) - (b - a) * (r - 1.0)),
+                    (q - 1.0) * (r - 1.0) * (s - 1.0),
+                )
+            };
+            if p > 0.0 {
+                q = -q;
+            }
+            p = p.abs();
+            let min1 = 3.0 * xm * q - (tol1 * q).abs();
+            let min2 = (e * q).abs();
+            if 2.0 * p < min1.min(min2) {
+                e = d;
+                d = p / q;
+            } else {
+                d = xm;
+                e = d;
+            }
+        } else {
+            d = xm;
+            e = d;
+        }
+
+        a = b;
+        fa = fb;
+        b += if d.abs() > tol1 { d } else { tol1.copysign(xm) };
+        fb = f(b);
+    }
+    Err(FinanceError::NoConvergence)
+}
+
+/// Newton-Raphson protegido por bisección.
+///
+/// `f` devuelve el par (f(x), f'(x)). Si el paso de Newton sale del intervalo
+/// que acota la raíz (o la derivada se anula) se toma el punto medio, por lo que
+/// el método nunca diverge.
+///
+/// # Argumentos
+/// * `f` - Función y derivada
+/// * `a`, `b` - Extremos del intervalo, con f(a)·f(b) ≤ 0
+/// * `x0` - Estimación inicial (si cae fuera de [a, b] se usa el punto medio)
+/// * `tol` - Tolerancia absoluta en x
+/// * `max_iter` - Número máximo de iteraciones
+///
+/// # Errores
+/// * `InvalidInput` - Si no hay cambio de signo en el intervalo
+/// * `NoConvergence` - Si se agotan las iteraciones
+pub fn newton_safeguarded<F: Fn(f64) -> (f64, f64)>(f: F, a: f64, b: f64, x0: f64, tol: f64, max_iter: usize) -> FinanceResult<f64> {
+    let (fa, _) = f(a);
+    let (fb, _) = f(b);
+    if !fa.is_finite() || !fb.is_finite() || fa * fb > 0.0 {
+        return Err(FinanceError::InvalidInput);
+    }
+    if fa == 0.0 {
+        return Ok(a);
+    }
+    if fb == 0.0 {
+        return Ok(b);
+    }
+
+    // Orientar el intervalo de forma que f(lo) < 0 < f(hi)
+    let (mut lo, mut hi) = if fa < 0.0 { (a, b) } else { (b, a) };
+    let mut x = if (x0 - a) * (x0 - b) < 0.0 { x0 } else { 0.5 * (a + b) };
+
+    for _ in 0..max_iter {
+        let (fx, dfx) = f(x);
+        if fx == 0.0 {
+            return Ok(x);
+        }
+        if fx < 0.0 {
+            lo = x;
+        } else {
+            hi = x;
+        }
+
+        let newton = x - fx / dfx;
+        let next = if dfx != 0.0 && newton.is_finite() && (newton - lo) * (newton - hi) < 0.0 {
+            newton
+        } else {
+            0.5 * (lo + hi)
+        };
+
+        if (next - x).abs() <= tol || (hi - lo).abs() <= tol {
+            return Ok(next);
+        }
+        x = next;
+    }
+    Err(FinanceError::NoConvergence)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn brent_finds_sqrt_two() {
+        let root = brent(|x| x * x - 2.0, 0.0, 2.0, 1e-14, 100).unwrap();
+        assert!((root - 2f64.sqrt()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn brent_transcendental() {
+        let root = brent(|x| x.cos() - x, 0.0, 1.0, 1e-14, 100).unwrap();
+        assert!((root - 0.739_085_133_215_160_6).abs() < 1e-12);
+    }
+
+    #[test]
+    fn brent_requires_sign_change() {
+        let err = brent(|x| x * x + 1.0, -1.0, 1.0, 1e-12, 100).unwrap_err();
+        assert_eq!(err, FinanceError::InvalidInput);
+    }
+
+    #[test]
+    fn newton_safeguarded_converges() {
+        let root = newton_safeguarded(|x| (x * x * x - x - 2.0, 3.0 * x * x - 1.0), 1.0, 2.0, 1.5, 1e-14, 100).unwrap();
+        assert!((root * root * root - root - 2.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn newton_safeguarded_survives_flat_derivative() {
+        // Arrancando en x0 = 0 la derivada es nula: debe recurrir a bisección
+        let root = newton_safeguarded(|x| (x * x - 0.25, 2.0 * x), 1.0, -0.1, 0.0, 1e-14, 200).unwrap();
+        assert!((root - 0.5).abs() < 1e-12);
+    }
+
+    #[test]
+    fn newton_safeguarded_requires_sign_change() {
+        let err = newton_safeguarded(|x| (x * x + 1.0, 2.0 * x), -1.0, 1.0, 0.0, 1e-12, 100).unwrap_err();
+        assert_eq!(err, FinanceError::InvalidInput);
+    }
+}
@@ -0,0 +1,351 @@
+//! Aproximaciones analíticas para opciones americanas con coste de
+//! mantenimiento b (q = r - b en Black-Scholes-Merton generalizado).
+//!
+//! Barone-Adesi y Whaley (1987): aproximación cuadrática de la prima de
+//! ejercicio anticipado. Para la call, con M = 2r/σ², N = 2b/σ², K(T) = 1 - e^{-rT}:
+//! q2 = [-(N - 1) + √((N - 1)² + 4M/K(T))] / 2
+//! C = c(S) + A2 (S/S*)^{q2} si S < S*,  C = S - K en otro caso
+//! A2 = (S*/q2) [1 - e^{(b-r)T} N(d1(S*))]
+//! donde la frontera S* resuelve S* - K = c(S*) + A2. La put es análoga con q1
+//! (raíz negativa) y la frontera S** < K.
+//!
+//! Bjerksund y Stensland (2002): la frontera de ejercicio se aproxima por dos
+//! tramos planos (I2 en [0, t1] e I1 en [t1, T], con t1 = (√5 - 1) T / 2) y
+//! el precio se obtiene en forma cerrada con la normal bivariante. La put se
+//! valora con la transformación put-call P(S, K, T, r, b) = C(K, S, T, r - b, -b).
+//!
+//! Ejercicio anticipado nunca óptimo: call con b ≥ r y put con r ≤ 0; en esos
+//! casos se devuelve el precio europeo y una frontera infinita (call) o nula (put).
+
+use crate::common::{
+    FinanceError, FinanceResult,
+    SpotPrice, StrikePrice, TimeToMaturity, InterestRate, Volatility,
+    CostOfCarry, OptionType
+};
+use crate::num::brent;
+use super::black_scholes::{
+    normal_cdf, bivariate_normal_cdf, validate_inputs,
+    d1_d2_dividend, option_price_dividend, EPS_TIME, EPS_VOL
+};
+
+/// Tolerancia relativa (sobre K) al resolver la frontera de ejercicio.
+const BOUNDARY_TOL: f64 = 1e-10;
+const BOUNDARY_MAX_ITER: usize = 200;
+
+/// Precio aproximado de una opción americana y frontera de ejercicio anticipado hoy.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AmericanApproximation {
+    /// Precio de la opción americana, en unidades monetarias del subyacente
+    pub price: f64,
+    /// Frontera de ejercicio en t = 0: spot crítico S* a partir del cual
+    /// conviene ejercer ya (S ≥ S* en la call, S ≤ S** en la put). En
+    /// Bjerksund-Stensland es el tramo I2 de [0, t1]; vale ∞ (call) o 0 (put)
+    /// si el ejercicio anticipado nunca es óptimo
+    pub boundary: SpotPrice,
+}
+
+/// Aproximación de Barone-Adesi y Whaley (1987).
+///
+/// # Argumentos
+/// * `b` - Coste de mantenimiento (b = r - q)
+///
+/// # Errores
+/// * `InvalidInterestRate` - Si `sigma` es nula o negativa, o `b` no es finito
+/// * `NoConvergence` - Si no se localiza la frontera de ejercicio
+pub fn barone_adesi_whaley(s0: SpotPrice, k: StrikePrice, t: TimeToMaturity, r: InterestRate, b: CostOfCarry, sigma: Volatility, kind: OptionType) -> FinanceResult<AmericanApproximation> {
+    if let Some(trivial) = trivial_cases(s0, k, t, r, b, sigma, kind)? {
+        return Ok(trivial);
+    }
+
+    let q = r - b;
+    let carry = ((b - r) * t).exp();
+    let m = 2.0 * r / (sigma * sigma);
+    let n = 2.0 * b / (sigma * sigma);
+    let kt = 1.0 - (-r * t).exp();
+    let disc = ((n - 1.0).powi(2) + 4.0 * m / kt).sqrt();
+    let tol = BOUNDARY_TOL * k;
+
+    match kind {
+        OptionType::Call => {
+            let q2 = 0.5 * (-(n - 1.0) + disc);
+            let a2 = |s: f64| -> FinanceResult<f64> {
+                let (d1, _) = d1_d2_dividend(s, k, t, r, q, sigma)?;
+                Ok(s / q2 * (1.0 - carry * normal_cdf(d1)))
+            };
+            let g = |s: f64| match (option_price_dividend(s, k, t, r, q, sigma, kind), a2(s)) {
+                (Ok(c), Ok(a)) => c + a - (s - k),
+                _ => f64::NAN,
+            };
+            let hi = expand_bracket(&g, 2.0 * k, 2.0)?;
+            let boundary = brent(g, k, hi, tol, BOUNDARY_MAX_ITER)?;
+            if s0 >= boundary {
+                return Ok(AmericanApproximation { price: s0 - k, boundary });
+            }
+            let european = option_price_dividend(s0, k, t, r, q, sigma, kind)?;
+            let price = european + a2(boundary)? * (s0 / boundary).powf(q2);
+            Ok(AmericanApproximation { price, boundary })
+        }
+        OptionType::Put => {
+            let q1 = 0.5 * (-(n - 1.0) - disc);
+            let a1 = |s: f64| -> FinanceResult<f64> {
+                let (d1, _) = d1_d2_dividend(s, k, t, r, q, sigma)?;
+                Ok(-s / q1 * (1.0 - carry * normal_cdf(-d1)))
+            };
+            let g = |s: f64| match (option_price_dividend(s, k, t, r, q, sigma, kind), a1(s)) {
+                (Ok(p), Ok(a)) => p + a - (k - s),
+                _ => f64::NAN,
+            };
+            let lo = expand_bracket(&g, 0.5 * k, 0.5)?;
+            let boundary = brent(g, lo, k, tol, BOUNDARY_MAX_ITER)?;
+            if s0 <= boundary {
+                return Ok(AmericanApproximation { price: k - s0, boundary });
+            }
+            let european = option_price_dividend(s0, k, t, r, q, sigma, kind)?;
+            let price = european + a1(boundary)? * (s0 / boundary).powf(q1);
+            Ok(AmericanApproximation { price, boundary })
+        }
+    }
+}
+
+/// Aproximación de Bjerksund y Stensland (2002).
+///
+/// # Argumentos
+/// * `b` - Coste de mantenimiento (b = r - q)
+///
+/// # Errores
+/// * `InvalidInterestRate` - Si `sigma` es nula o negativa, o `b` no es finito
+/// * `InvalidInput` - Si los parámetros no admiten frontera finita
+///   (tipos negativos con b < r muy cercano a r)
+pub fn bjerksund_stensland(s0: SpotPrice, k: StrikePrice, t: TimeToMaturity, r: InterestRate, b: CostOfCarry, sigma: Volatility, kind: OptionType) -> FinanceResult<AmericanApproximation> {
+    if let Some(trivial) = trivial_cases(s0, k, t, r, b, sigma, kind)? {
+        return Ok(trivial);
+    }
+
+    match kind {
+        OptionType::Call => bjerksund_stensland_call(s0, k, t, r, b, sigma),
+        OptionType::Put => {
+            // La frontera I2 de la call transformada es proporcional a su
+            // strike S, así que K ≥ I2(S) equivale a S ≤ K S / I2(S)
+            let call = bjerksund_stensland_call(k, s0, t, r - b, -b, sigma)?;
+            Ok(AmericanApproximation { price: call.price, boundary: k * s0 / call.boundary })
+        }
+    }
+}
+
+/// Vencimiento, ejercicio anticipado nunca óptimo y validación comunes.
+fn trivial_cases(s0: SpotPrice, k: StrikePrice, t: TimeToMaturity, r: InterestRate, b: CostOfCarry, sigma: Volatility, kind: OptionType) -> FinanceResult<Option<AmericanApproximation>> {
+    validate_inputs(s0, k, t, r, sigma)?;
+    if !b.is_finite() {
+        return Err(FinanceError::InvalidInterestRate);
+    }
+    if t < EPS_TIME {
+        return Ok(Some(AmericanApproximation { price: kind.payoff(s0, k), boundary: k }));
+    }
+    if sigma < EPS_VOL {
+        return Err(FinanceError::InvalidInterestRate);
+    }
+
+    let boundary = match kind {
+        OptionType::Call if b >= r => f64::INFINITY,
+        OptionType::Put if r <= 0.0 => 0.0,
+        _ => return Ok(None),
+    };
+    let price = option_price_dividend(s0, k, t, r, r - b, sigma, kind)?;
+    Ok(Some(AmericanApproximation { price, boundary }))
+}
+
+/// Multiplica `x` por `factor` hasta que `g(x) < 0`.
+fn expand_bracket<F: Fn(f64) -> f64>(g: &F, x: f64, factor: f64) -> FinanceResult<f64> {
+    let mut x = x;
+    for _ in 0..BOUNDARY_MAX_ITER {
+        let gx = g(x);
+        if gx.is_nan() {
+            return Err(FinanceError::NoConvergence);
+        }
+        if gx < 0.0 {
+            return Ok(x);
+        }
+        x *= factor;
+    }
+    Err(FinanceError::NoConvergence)
+}
+
+/// Call de Bjerksund-Stensland (2002) con b < r, T > 0 y σ > 0.
+fn bjerksund_stensland_call(s0: SpotPrice, k: StrikePrice, t: TimeToMaturity, r: InterestRate, b: CostOfCarry, sigma: Volatility) -> FinanceResult<AmericanApproximation> {
+    let v2 = sigma * sigma;
+    let beta = (0.5 - b / v2) + ((b / v2 - 0.5).powi(2) + 2.0 * r / v2).sqrt();
+    if !beta.is_finite() || beta <= 1.0 {
+        return Err(FinanceError::InvalidInput);
+    }
+
+    let t1 = 0.5 * (5f64.sqrt() - 1.0) * t;
+    let b_inf = beta / (beta - 1.0) * k;
+    let b_0 = if r > b && r > 0.0 { k.max(r / (r - b) * k) } else { k };
+    let scale = k * k / ((b_inf - b_0) * b_0);
+    let h1 = -(b * t1 + 2.0 * sigma * t1.sqrt()) * scale;
+    let h2 = -(b * t + 2.0 * sigma * t.sqrt()) * scale;
+    let i1 = b_0 + (b_inf - b_0) * (1.0 - h1.exp());
+    let i2 = b_0 + (b_inf - b_0) * (1.0 - h2.exp());
+    if s0 >= i2 {
+        return Ok(AmericanApproximation { price: s0 - k, boundary: i2 });
+    }
+
+    let alpha1 = (i1 - k) * i1.powf(-beta);
+    let alpha2 = (i2 - k) * i2.powf(-beta);
+    let f = TwoStepBoundary { r, b, sigma, t, t1 };
+
+    let price = alpha2 * s0.powf(beta)
+        - alpha2 * f.phi(s0, t1, beta, i2, i2)
+        + f.phi(s0, t1, 1.0, i2, i2)
+        - f.phi(s0, t1, 1.0, i1, i2)
+        - k * f.phi(s0, t1, 0.0, i2, i2)
+        + k * f.phi(s0, t1, 0.0, i1, i2)
+        + alpha1 * f.phi(s0, t1, beta, i1, i2)
+        - alpha1 * f.psi(s0, beta, i1, i2, i1)
+        + f.psi(s0, 1.0, i1, i2, i1)
+        - f.psi(s0, 1.0, k, i2, i1)
+        - k * f.psi(s0, 0.0, i1, i2, i1)
+        + k * f.psi(s0, 0.0, k, i2, i1);
+    Ok(AmericanApproximation { price, boundary: i2 })
+}
+
+/// Parámetros comunes de las funciones φ y ψ de Bjerksund-Stensland.
+struct TwoStepBoundary {
+    r: InterestRate,
+    b: CostOfCarry,
+    sigma: Volatility,
+    t: TimeToMaturity,
+    t1: TimeToMaturity,
+}
+
+impl TwoStepBoundary {
+    /// λ T y κ de la medida asociada a S^γ.
+    fn lambda_kappa(&self, gamma: f64, t: TimeToMaturity) -> (f64, f64) {
+        let v2 = self.sigma * self.sigma;
+        let lambda = (-self.r + gamma * self.b + 0.5 * gamma * (gamma - 1.0) * v2) * t;
+        let kappa = 2.0 * self.b / v2 + 2.0 * gamma - 1.0;
+        (lambda, kappa)
+    }
+
+    /// φ(S, T, γ, H, I) = e^{-rT} E[S_T^γ 1{S_T ≤ H, max S < I}]
+    fn phi(&self, s: SpotPrice, t: TimeToMaturity, gamma: f64, h: f64, i: f64) -> f64 {
+        let (lambda, kappa) = self.lambda_kappa(gamma, t);
+        let sigma_sqrt_t = self.sigma * t.sqrt();
+        let drift = (self.b + (gamma - 0.5) * self.sigma * self.sigma) * t;
+        let d = -((s / h).ln() + drift) / sigma_sqrt_t;
+        let reflected = d - 2.0 * (i / s).ln() / sigma_sqrt_t;
+        lambda.exp() * s.powf(gamma) * (normal_cdf(d) - (i / s).powf(kappa) * normal_cdf(reflected))
+    }
+
+    /// ψ: como φ hasta T con barrera I2 en [0, t1] e I1 en [t1, T].
+    fn psi(&self, s: SpotPrice, gamma: f64, h: f64, i2: f64, i1: f64) -> f64 {
+        let (lambda, kappa) = self.lambda_kappa(gamma, self.t);
+        let mu = self.b + (gamma - 0.5) * self.sigma * self.sigma;
+        let (sd1, sd) = (self.sigma * self.t1.sqrt(), self.sigma * self.t.sqrt());
+        let rho = (self.t1 / self.t).sqrt();
+
+        let e1 = ((s / i1).ln() + mu * self.t1) / sd1;
+        let e2 = ((i2 * i2 / (s * i1)).ln() + mu * self.t1) / sd1;
+        let e3 = ((s / i1).ln() - mu * self.t1) / sd1;
+        let e4 = ((i2 * i2 / (s * i1)).ln() - mu * self.t1) / sd1;
+        let f1 = ((s / h).ln() + mu * self.t) / sd;
+        let f2 = ((i2 * i2 / (s * h)).ln() + mu * self.t) / sd;
+        let f3 = ((i1 * i1 / (s * h)).ln() + mu * self.t) / sd;
+        let f4 = ((s * i1 * i1 / (h * i2 * i2)).ln() + mu * self.t) / sd;
+
+        lambda.exp() * s.powf(gamma) * (
+            bivariate_normal_cdf(-e1, -f1, rho)
+            - (i2 / s).powf(kappa) * bivariate_normal_cdf(-e2, -f2, rho)
+            - (i1 / s).powf(kappa) * bivariate_normal_cdf(-e3, -f3, -rho)
+            + (i1 / i2).powf(kappa) * bivariate_normal_cdf(-e4, -f4, -rho)
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::ExerciseStyle;
+    use crate::options::BinomialTree;
+
+    type Approximation = fn(f64, f64, f64, f64, f64, f64, OptionType) -> FinanceResult<AmericanApproximation>;
+    const METHODS: [Approximation; 2] = [barone_adesi_whaley, bjerksund_stensland];
+
+    #[test]
+    fn american_put_reference_value() {
+        // S=K=50, T=5/12, r=10%, σ=40%; árbol convergido ≈ 4.2842
+        for method in METHODS {
+            let am = method(50.0, 50.0, 5.0 / 12.0, 0.10, 0.10, 0.40, OptionType::Put).unwrap();
+            assert!((am.price - 4.2842).abs() < 0.05, "{}", am.price);
+            assert!(am.boundary > 0.0 && am.boundary < 50.0);
+        }
+    }
+
+    #[test]
+    fn puts_match_binomial_tree() {
+        let tree = BinomialTree::new(1000).unwrap().with_exercise(ExerciseStyle::American).unwrap();
+        let (k, r, sigma) = (100.0, 0.06, 0.3);
+        for t in [0.25, 1.0] {
+            for s0 in [80.0, 100.0, 120.0] {
+                let reference = tree.price(s0, k, t, r, sigma, OptionType::Put).unwrap();
+                for method in METHODS {
+                    let am = method(s0, k, t, r, r, sigma, OptionType::Put).unwrap();
+                    assert!((am.price - reference).abs() < 0.02 * reference.max(1.0), "{s0} {t}: {} vs {reference}", am.price);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn calls_with_carry_below_rate() {
+        // Haug (2007): BAW sobre futuros (b = 0), K=100, T=0.1, r=10%, σ=15%
+        let baw = barone_adesi_whaley(100.0, 100.0, 0.1, 0.10, 0.0, 0.15, OptionType::Call).unwrap();
+        assert!((baw.price - 1.8771).abs() < 1e-3, "{}", baw.price);
+        // S=42, K=40, T=0.75, r=4%, b=-4%, σ=35%: árbol CRR de 4000 pasos ≈ 5.3090.
+        // Bjerksund-Stensland valora una estrategia de ejercicio concreta: es cota inferior
+        let bs = bjerksund_stensland(42.0, 40.0, 0.75, 0.04, -0.04, 0.35, OptionType::Call).unwrap();
+        assert!(bs.price < 5.3090 && bs.price > 5.3090 - 0.03, "{}", bs.price);
+        assert!(bs.boundary > 40.0);
+    }
+
+    #[test]
+    fn call_without_carry_advantage_is_european() {
+        for method in METHODS {
+            let am = method(100.0, 100.0, 1.0, 0.05, 0.05, 0.2, OptionType::Call).unwrap();
+            let eu = option_price_dividend(100.0, 100.0, 1.0, 0.05, 0.0, 0.2, OptionType::Call).unwrap();
+            assert!((am.price - eu).abs() < 1e-12);
+            assert_eq!(am.boundary, f64::INFINITY);
+            let put = method(100.0, 100.0, 1.0, 0.0, -0.02, 0.2, OptionType::Put).unwrap();
+            assert_eq!(put.boundary, 0.0);
+        }
+    }
+
+    #[test]
+    fn price_bounds_and_exercise_region() {
+        let (k, t, r, b, sigma) = (100.0, 0.5, 0.08, -0.04, 0.25);
+        for method in METHODS {
+            for kind in [OptionType::Call, OptionType::Put] {
+                for s0 in [70.0, 90.0, 100.0, 110.0, 130.0] {
+                    let am = method(s0, k, t, r, b, sigma, kind).unwrap();
+                    let eu = option_price_dividend(s0, k, t, r, r - b, sigma, kind).unwrap();
+                    assert!(am.price >= eu - 1e-10 && am.price >= kind.payoff(s0, k) - 1e-10);
+                }
+                // Más allá de la frontera el precio es el valor intrínseco
+                let boundary = method(100.0, k, t, r, b, sigma, kind).unwrap().boundary;
+                let deep = match kind { OptionType::Call => 1.05 * boundary, OptionType::Put => 0.95 * boundary };
+                let am = method(deep, k, t, r, b, sigma, kind).unwrap();
+                assert!((am.price - kind.payoff(deep, k)).abs() < 1e-10);
+            }
+        }
+    }
+
+    #[test]
+    fn degenerate_inputs() {
+        for method in METHODS {
+            let expired = method(90.0, 100.0, 0.0, 0.05, 0.0, 0.2, OptionType::Put).unwrap();
+            assert_eq!(expired, AmericanApproximation { price: 10.0, boundary: 100.0 });
+            assert_eq!(method(100.0, 100.0, 1.0, 0.05, 0.0, 0.0, OptionType::Put).unwrap_err(), FinanceError::InvalidInterestRate);
+            assert_eq!(method(100.0, 100.0, 1.0, 0.05, f64::NAN, 0.2, OptionType::Put).unwrap_err(), FinanceError::InvalidInterestRate);
+        }
+    }
+}
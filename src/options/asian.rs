@@ -0,0 +1,434 @@
+//! Opciones asiáticas (sobre precio medio) europeas.
+//!
+//! La media puede ser continua sobre una ventana [t0, T] o discreta sobre un
+//! calendario de fijaciones; en ambos casos se admite una parte ya observada
+//! (ventana empezada, t0 < 0, o fijaciones pasadas) que entra en la media con
+//! su peso y se trata como conocida.
+//!
+//! - Media geométrica: ln G es normal, de modo que el precio es exacto
+//!   (Kemna y Vorst, 1990, en el caso continuo):
+//!   V = e^{-rT} [E[G] N(d1) - K N(d2)], d1 = [ln(E[G]/K) + v/2]/√v, v = Var[ln G]
+//! - Media aritmética, Turnbull y Wakeman (1991): se ajusta una lognormal con
+//!   los dos primeros momentos exactos de la media futura F y se valora con
+//!   Black sobre el strike ajustado K' = K - (parte ya fijada de la media):
+//!   v = ln(E[F²]/E[F]²), V = e^{-rT} [E[F] N(d1) - K' N(d2)]
+//!   Si K' ≤ 0 la call está dentro del dinero con seguridad y vale
+//!   e^{-rT} (E[F] - K'), y la put vale cero.
+//! - Media aritmética continua, Levy (1992): la misma idea en forma cerrada,
+//!   con S_E = S e^{-rT} (e^{bT} - 1)/(b T₂), D = M/T₂² y V = ln D - 2(rT + ln S_E),
+//!   donde T₂ es la longitud total de la ventana y M = E[(∫₀ᵀ S_u du)²].
+//!
+//! Con b = r - q, E[S_u S_v] = S² e^{b(u + v) + σ² min(u, v)}.
+
+use crate::common::{
+    FinanceError, FinanceResult,
+    TimeInYears, DividendYield,
+    SpotPrice, StrikePrice, TimeToMaturity, InterestRate, Volatility,
+    OptionType
+};
+use super::black_scholes::{normal_cdf, validate_inputs, EPS_TIME, EPS_VOL};
+
+/// Calendario de promediado
+#[derive(Debug, Clone, PartialEq)]
+pub enum AverageSchedule {
+    /// Media continua sobre [start, T]; `start` < 0 si la ventana ya empezó
+    Continuous { start: TimeInYears },
+    /// Fechas de fijación futuras en años desde hoy, en (0, T]
+    Discrete(Vec<TimeInYears>),
+}
+
+/// Opción asiática sobre precio medio
+#[derive(Debug, Clone, PartialEq)]
+pub struct AsianOption {
+    /// Calendario de promediado pendiente
+    pub schedule: AverageSchedule,
+    /// Media ya observada: sobre [start, 0] en el caso continuo, o de las
+    /// fijaciones pasadas en el discreto (geométrica si se valora la
+    /// media geométrica)
+    pub running_average: SpotPrice,
+    /// Número de fijaciones ya realizadas (solo calendario discreto)
+    pub past_fixings: usize,
+    /// Rendimiento por dividendo continuo del subyacente
+    pub dividend_yield: DividendYield,
+}
+
+/// Media futura y parte ya fijada, ambas ponderadas por su peso en la media final
+struct AverageMoments {
+    /// Contribución conocida a la media final
+    fixed: f64,
+    /// E[F]
+    mean: f64,
+    /// E[F²]
+    second: f64,
+}
+
+impl AsianOption {
+    /// Media continua sobre [start, T].
+    ///
+    /// # Errores
+    /// * `InvalidPeriods` - Si `start` no es finito
+    pub fn continuous(start: TimeInYears) -> FinanceResult<Self> {
+        if !start.is_finite() {
+            return Err(FinanceError::InvalidPeriods);
+        }
+        Ok(AsianOption {
+            schedule: AverageSchedule::Continuous { start },
+            running_average: 0.0,
+            past_fixings: 0,
+            dividend_yield: 0.0,
+        })
+    }
+
+    /// Media discreta sobre las fechas de fijación indicadas.
+    ///
+    /// # Errores
+    /// * `InvalidPeriods` - Si alguna fecha no es positiva o finita
+    pub fn discrete(fixings: Vec<TimeInYears>) -> FinanceResult<Self> {
+        if fixings.iter().any(|t| !t.is_finite() || *t <= 0.0) {
+            return Err(FinanceError::InvalidPeriods);
+        }
+        Ok(AsianOption {
+            schedule: AverageSchedule::Discrete(fixings),
+            running_average: 0.0,
+            past_fixings: 0,
+            dividend_yield: 0.0,
+        })
+    }
+
+    /// Media de la parte de la ventana continua ya transcurrida.
+    ///
+    /// # Errores
+    /// * `InvalidMonetaryValue` - Si la media no es positiva
+    pub fn with_running_average(mut self, average: SpotPrice) -> FinanceResult<Self> {
+        if !average.is_finite() || average <= 0.0 {
+            return Err(FinanceError::InvalidMonetaryValue);
+        }
+        self.running_average = average;
+        Ok(self)
+    }
+
+    /// Fijaciones ya realizadas: número y media.
+    ///
+    /// # Errores
+    /// * `InvalidMonetaryValue` - Si la media no es positiva
+    pub fn with_past_fixings(mut self, count: usize, average: SpotPrice) -> FinanceResult<Self> {
+        if count > 0 && (!average.is_finite() || average <= 0.0) {
+            return Err(FinanceError::InvalidMonetaryValue);
+        }
+        self.past_fixings = count;
+        self.running_average = average;
+        Ok(self)
+    }
+
+    /// Cambia el rendimiento por dividendo continuo.
+    ///
+    /// # Errores
+    /// * `InvalidInterestRate` - Si no es finito
+    pub fn with_dividend_yield(mut self, q: DividendYield) -> FinanceResult<Self> {
+        if !q.is_finite() {
+            return Err(FinanceError::InvalidInterestRate);
+        }
+        self.dividend_yield = q;
+        Ok(self)
+    }
+
+    /// Precio exacto con media geométrica.
+    ///
+    /// # Errores
+    /// * `InvalidInterestRate` - Si `sigma` es nula o negativa
+    /// * `InvalidPeriods` - Si el calendario no encaja con `t`
+    pub fn geometric_price(&self, s0: SpotPrice, k: StrikePrice, t: TimeToMaturity, r: InterestRate, sigma: Volatility, kind: OptionType) -> FinanceResult<f64> {
+        self.validate(s0, k, t, r, sigma)?;
+        let drift = r - self.dividend_yield - 0.5 * sigma * sigma;
+
+        // ln G = w_p ln G_p + Σ w_i ln S_{t_i}: media y varianza de la parte futura
+        let (past_weight, mut mean, variance) = match &self.schedule {
+            AverageSchedule::Continuous { start } => {
+                let (t0, total, remaining) = self.continuous_window(*start, t)?;
+                let mean = remaining / total * s0.ln() + drift * (t * t - t0 * t0) / (2.0 * total);
+                let covariance = (t.powi(3) - t0.powi(3)) / 3.0 - t0 * t0 * remaining;
+                ((t0 - *start) / total, mean, sigma * sigma * covariance / (total * total))
+            }
+            AverageSchedule::Discrete(dates) => {
+                let n = self.discrete_count(dates, t)? as f64;
+                let mean = dates.iter().map(|ti| s0.ln() + drift * ti).sum::<f64>() / n;
+                let covariance: f64 = dates.iter()
+                    .flat_map(|ti| dates.iter().map(move |tj| ti.min(*tj)))
+                    .sum();
+                (self.past_fixings as f64 / n, mean, sigma * sigma * covariance / (n * n))
+            }
+        };
+        if past_weight > 0.0 {
+            mean += past_weight * self.running_average.ln();
+        }
+
+        let df = (-r * t).exp();
+        if variance < EPS_VOL {
+            return Ok(df * kind.payoff(mean.exp(), k));
+        }
+        let forward = (mean + 0.5 * variance).exp();
+        Ok(df * black(forward, k, variance, kind))
+    }
+
+    /// Media aritmética por ajuste de momentos de Turnbull-Wakeman.
+    ///
+    /// # Errores
+    /// * `InvalidInterestRate` - Si `sigma` es nula o negativa
+    /// * `InvalidPeriods` - Si el calendario no encaja con `t`
+    pub fn turnbull_wakeman_price(&self, s0: SpotPrice, k: StrikePrice, t: TimeToMaturity, r: InterestRate, sigma: Volatility, kind: OptionType) -> FinanceResult<f64> {
+        self.validate(s0, k, t, r, sigma)?;
+        let moments = self.arithmetic_moments(s0, t, r, sigma)?;
+        let df = (-r * t).exp();
+        let strike = k - moments.fixed;
+
+        if strike <= 0.0 {
+            return Ok(match kind {
+                OptionType::Call => df * (moments.mean - strike),
+                OptionType::Put => 0.0,
+            });
+        }
+        let variance = (moments.second / (moments.mean * moments.mean)).ln().max(0.0);
+        if variance < EPS_VOL {
+            return Ok(df * kind.payoff(moments.mean, strike));
+        }
+        Ok(df * black(moments.mean, strike, variance, kind))
+    }
+
+    /// Media aritmética continua con la fórmula cerrada de Levy.
+    ///
+    /// Solo para ventanas continuas ya empezadas o que empiezan hoy.
+    ///
+    /// # Errores
+    /// * `InvalidInput` - Si el calendario es discreto o la ventana empieza en el futuro
+    /// * `InvalidInterestRate` - Si `sigma` es nula o negativa
+    pub fn levy_price(&self, s0: SpotPrice, k: StrikePrice, t: TimeToMaturity, r: InterestRate, sigma: Volatility, kind: OptionType) -> FinanceResult<f64> {
+        self.validate(s0, k, t, r, sigma)?;
+        let start = match self.schedule {
+            AverageSchedule::Continuous { start } if start <= 0.0 => start,
+            _ => return Err(FinanceError::InvalidInput),
+        };
+        let (_, total, _) = self.continuous_window(start, t)?;
+        let b = r - self.dividend_yield;
+        let a = b + sigma * sigma;
+        let df = (-r * t).exp();
+
+        let strike = k - self.running_average * (-start) / total;
+        let s_e = s0 * df * growth(b, t) / total;
+        if strike <= 0.0 {
+            return Ok(match kind {
+                OptionType::Call => s_e - strike * df,
+                OptionType::Put => 0.0,
+            });
+        }
+        let m = 2.0 * s0 * s0 * integrated_growth(a, b, t);
+        let d = m / (total * total);
+        let v = d.ln() - 2.0 * (r * t + s_e.ln());
+        if v < EPS_VOL {
+            return Ok(df * kind.payoff(s_e / df, strike));
+        }
+        let d1 = (0.5 * d.ln() - strike.ln()) / v.sqrt();
+        let d2 = d1 - v.sqrt();
+        Ok(match kind {
+            OptionType::Call => s_e * normal_cdf(d1) - strike * df * normal_cdf(d2),
+            OptionType::Put => strike * df * normal_cdf(-d2) - s_e * normal_cdf(-d1),
+        })
+    }
+
+    fn validate(&self, s0: SpotPrice, k: StrikePrice, t: TimeToMaturity, r: InterestRate, sigma: Volatility) -> FinanceResult<()> {
+        validate_inputs(s0, k, t, r, sigma)?;
+        if t < EPS_TIME {
+            return Err(FinanceError::InvalidPeriods);
+        }
+        if sigma < EPS_VOL {
+            return Err(FinanceError::InvalidInterestRate);
+        }
+        let has_past = match self.schedule {
+            AverageSchedule::Continuous { start } => start < 0.0,
+            AverageSchedule::Discrete(_) => self.past_fixings > 0,
+        };
+        if has_past && self.running_average <= 0.0 {
+            return Err(FinanceError::InvalidMonetaryValue);
+        }
+        Ok(())
+    }
+
+    /// (t0, longitud total, longitud pendiente) de una ventana continua.
+    fn continuous_window(&self, start: TimeInYears, t: TimeToMaturity) -> FinanceResult<(f64, f64, f64)> {
+        if start >= t {
+            return Err(FinanceError::InvalidPeriods);
+        }
+        let t0 = start.max(0.0);
+        Ok((t0, t - start, t - t0))
+    }
+
+    /// Número total de fijaciones, pasadas y futuras.
+    fn discrete_count(&self, dates: &[TimeInYears], t: TimeToMaturity) -> FinanceResult<usize> {
+        if dates.is_empty() || dates.iter().any(|ti| *ti > t) {
+            return Err(FinanceError::InvalidPeriods);
+        }
+        Ok(dates.len() + self.past_fixings)
+    }
+
+    /// Momentos de la media aritmética futura.
+    fn arithmetic_moments(&self, s0: SpotPrice, t: TimeToMaturity, r: InterestRate, sigma: Volatility) -> FinanceResult<AverageMoments> {
+        let b = r - self.dividend_yield;
+        match &self.schedule {
+            AverageSchedule::Continuous { start } => {
+                let (t0, total, remaining) = self.continuous_window(*start, t)?;
+                let a = b + sigma * sigma;
+                Ok(AverageMoments {
+                    fixed: self.running_average * (t0 - start) / total,
+                    mean: s0 * (b * t0).exp() * growth(b, remaining) / total,
+                    second: 2.0 * s0 * s0 * ((a + b) * t0).exp() * integrated_growth(a, b, remaining) / (total * total),
+                })
+            }
+            AverageSchedule::Discrete(dates) => {
+                let n = self.discrete_count(dates, t)? as f64;
+                let second: f64 = dates.iter()
+                    .flat_map(|ti| dates.iter().map(move |tj| (b * (ti + tj) + sigma * sigma * ti.min(*tj)).exp()))
+                    .sum();
+                Ok(AverageMoments {
+                    fixed: self.running_average * self.past_fixings as f64 / n,
+                    mean: s0 * dates.iter().map(|ti| (b * ti).exp()).sum::<f64>() / n,
+                    second: s0 * s0 * second / (n * n),
+                })
+            }
+        }
+    }
+}
+
+/// Black sin descontar con varianza total `variance`.
+fn black(forward: f64, k: StrikePrice, variance: f64, kind: OptionType) -> f64 {
+    let sd = variance.sqrt();
+    let d1 = ((forward / k).ln() + 0.5 * variance) / sd;
+    let d2 = d1 - sd;
+    match kind {
+        OptionType::Call => forward * normal_cdf(d1) - k * normal_cdf(d2),
+        OptionType::Put => k * normal_cdf(-d2) - forward * normal_cdf(-d1),
+    }
+}
+
+/// ∫₀ˡ e^{xs} ds = (e^{xl} - 1)/x, con límite l en x = 0.
+fn growth(x: f64, l: f64) -> f64 {
+    if x.abs() < 1e-12 { l } else { (x * l).exp_m1() / x }
+}
+
+/// ∫₀ˡ e^{bu} ∫₀ᵘ e^{av} dv du, con a = b + σ².
+fn integrated_growth(a: f64, b: f64, l: f64) -> f64 {
+    if a.abs() < 1e-12 {
+        // ∫₀ˡ u e^{bu} du
+        return if b.abs() < 1e-12 { 0.5 * l * l } else { ((b * l).exp() * (b * l - 1.0) + 1.0) / (b * b) };
+    }
+    (growth(a + b, l) - growth(b, l)) / a
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::options::option_price_dividend;
+
+    fn monthly() -> Vec<f64> {
+        (1..=12).map(|i| i as f64 / 12.0).collect()
+    }
+
+    #[test]
+    fn single_fixing_at_expiry_is_european() {
+        let option = AsianOption::discrete(vec![1.0]).unwrap().with_dividend_yield(0.02).unwrap();
+        for kind in [OptionType::Call, OptionType::Put] {
+            let european = option_price_dividend(100.0, 95.0, 1.0, 0.05, 0.02, 0.25, kind).unwrap();
+            let g = option.geometric_price(100.0, 95.0, 1.0, 0.05, 0.25, kind).unwrap();
+            let a = option.turnbull_wakeman_price(100.0, 95.0, 1.0, 0.05, 0.25, kind).unwrap();
+            assert!((g - european).abs() < 1e-10);
+            assert!((a - european).abs() < 1e-10);
+        }
+    }
+
+    #[test]
+    fn kemna_vorst_continuous_geometric() {
+        // Media continua desde hoy: Black-Scholes con b_A = (b - σ²/6)/2 y σ_A = σ/√3
+        let (s0, k, t, r, q, sigma) = (100.0, 100.0, 1.0, 0.06, 0.01, 0.3);
+        let option = AsianOption::continuous(0.0).unwrap().with_dividend_yield(q).unwrap();
+        let b_a = 0.5 * (r - q - sigma * sigma / 6.0);
+        let reference = option_price_dividend(s0, k, t, r, r - b_a, sigma / 3f64.sqrt(), OptionType::Call).unwrap();
+        let price = option.geometric_price(s0, k, t, r, sigma, OptionType::Call).unwrap();
+        assert!((price - reference).abs() < 1e-10);
+    }
+
+    #[test]
+    fn arithmetic_monte_carlo_reference() {
+        // 12 fijaciones mensuales, S=K=100, T=1, r=5%, σ=30%;
+        // Monte Carlo (10⁶ trayectorias): call 8.466 ± 0.013, put 5.869 ± 0.008
+        let option = AsianOption::discrete(monthly()).unwrap();
+        let call = option.turnbull_wakeman_price(100.0, 100.0, 1.0, 0.05, 0.3, OptionType::Call).unwrap();
+        let put = option.turnbull_wakeman_price(100.0, 100.0, 1.0, 0.05, 0.3, OptionType::Put).unwrap();
+        assert!((call - 8.466).abs() < 0.08, "{call}");
+        assert!((put - 5.869).abs() < 0.08, "{put}");
+        // Media geométrica ≤ media aritmética
+        let geometric = option.geometric_price(100.0, 100.0, 1.0, 0.05, 0.3, OptionType::Call).unwrap();
+        assert!(geometric < call);
+    }
+
+    #[test]
+    fn levy_matches_turnbull_wakeman_continuous() {
+        let (s0, k, t, r, sigma) = (95.0, 100.0, 0.75, 0.04, 0.25);
+        for start in [0.0, -0.25] {
+            let option = AsianOption::continuous(start).unwrap()
+                .with_running_average(98.0).unwrap()
+                .with_dividend_yield(0.04).unwrap();
+            for kind in [OptionType::Call, OptionType::Put] {
+                let levy = option.levy_price(s0, k, t, r, sigma, kind).unwrap();
+                let tw = option.turnbull_wakeman_price(s0, k, t, r, sigma, kind).unwrap();
+                assert!((levy - tw).abs() < 1e-10, "{start} {kind:?}");
+            }
+        }
+        let forward_start = AsianOption::continuous(0.25).unwrap();
+        assert_eq!(forward_start.levy_price(s0, k, t, r, sigma, OptionType::Call).unwrap_err(), FinanceError::InvalidInput);
+    }
+
+    #[test]
+    fn discrete_converges_to_continuous() {
+        let (s0, k, t, r, sigma) = (100.0, 105.0, 1.0, 0.05, 0.2);
+        let continuous = AsianOption::continuous(0.0).unwrap();
+        let dense = AsianOption::discrete((1..=2000).map(|i| i as f64 / 2000.0).collect()).unwrap();
+        for kind in [OptionType::Call, OptionType::Put] {
+            let c = continuous.turnbull_wakeman_price(s0, k, t, r, sigma, kind).unwrap();
+            let d = dense.turnbull_wakeman_price(s0, k, t, r, sigma, kind).unwrap();
+            assert!((c - d).abs() < 5e-3);
+            let cg = continuous.geometric_price(s0, k, t, r, sigma, kind).unwrap();
+            let dg = dense.geometric_price(s0, k, t, r, sigma, kind).unwrap();
+            assert!((cg - dg).abs() < 5e-3);
+        }
+    }
+
+    #[test]
+    fn past_fixings_shift_the_strike() {
+        let (s0, t, r, sigma) = (100.0, 0.5, 0.05, 0.25);
+        let remaining: Vec<f64> = (1..=6).map(|i| i as f64 / 12.0).collect();
+        // Seis fijaciones pasadas con media 100 y strike 100 equivalen a la
+        // mitad de una asiática de strike 100 sobre las pendientes
+        let partial = AsianOption::discrete(remaining.clone()).unwrap().with_past_fixings(6, 100.0).unwrap();
+        let fresh = AsianOption::discrete(remaining).unwrap();
+        let p = partial.turnbull_wakeman_price(s0, 100.0, t, r, sigma, OptionType::Call).unwrap();
+        let f = fresh.turnbull_wakeman_price(s0, 100.0, t, r, sigma, OptionType::Call).unwrap();
+        assert!((p - 0.5 * f).abs() < 1e-10);
+
+        // Media pasada muy alta: la call ya está dentro del dinero y la put vale cero
+        let deep = partial.clone().with_past_fixings(6, 300.0).unwrap();
+        assert_eq!(deep.turnbull_wakeman_price(s0, 100.0, t, r, sigma, OptionType::Put).unwrap(), 0.0);
+        let call = deep.turnbull_wakeman_price(s0, 100.0, t, r, sigma, OptionType::Call).unwrap();
+        let expected_average = 150.0 + 0.5 * (1..=6).map(|i| s0 * (r * i as f64 / 12.0).exp()).sum::<f64>() / 6.0;
+        assert!((call - (-r * t).exp() * (expected_average - 100.0)).abs() < 1e-10);
+    }
+
+    #[test]
+    fn invalid_configuration() {
+        assert_eq!(AsianOption::discrete(vec![0.5, -0.1]).unwrap_err(), FinanceError::InvalidPeriods);
+        let late = AsianOption::discrete(vec![0.5, 1.5]).unwrap();
+        assert_eq!(late.geometric_price(100.0, 100.0, 1.0, 0.05, 0.2, OptionType::Call).unwrap_err(), FinanceError::InvalidPeriods);
+        let after_expiry = AsianOption::continuous(1.0).unwrap();
+        assert_eq!(after_expiry.turnbull_wakeman_price(100.0, 100.0, 1.0, 0.05, 0.2, OptionType::Call).unwrap_err(), FinanceError::InvalidPeriods);
+        assert_eq!(AsianOption::continuous(0.0).unwrap().with_running_average(-1.0).unwrap_err(), FinanceError::InvalidMonetaryValue);
+        let option = AsianOption::continuous(0.0).unwrap();
+        assert_eq!(option.levy_price(100.0, 100.0, 1.0, 0.05, 0.0, OptionType::Call).unwrap_err(), FinanceError::InvalidInterestRate);
+    }
+}
@@ -0,0 +1,264 @@
+//! Modelo de Bachelier (normal) para subyacentes que pueden ser negativos.
+//!
+//! El forward sigue dF = σ_N dW, de modo que F y K pueden tomar cualquier
+//! signo. Fórmulas implementadas, con df = e^{-rT}:
+//! C = df [(F - K) N(d) + σ_N √T φ(d)]
+//! P = df [(K - F) N(-d) + σ_N √T φ(d)]
+//! d = (F - K) / (σ_N √T)
+//!
+//! Incluye la conversión entre volatilidad normal y lognormal (Black-76)
+//! por equivalencia de precios.
+
+use crate::common::{
+    FinanceError, FinanceResult,
+    ForwardPrice, StrikePrice, TimeToMaturity, InterestRate, Volatility,
+    OptionType
+};
+use crate::num::newton_safeguarded;
+use super::black_scholes::{Greeks, normal_cdf, normal_pdf, EPS_TIME, EPS_VOL};
+use super::black76::{black76_price, black76_implied_volatility};
+
+/// Tolerancia relativa del solver de volatilidad normal implícita.
+const IMPLIED_VOL_TOL: f64 = 1e-12;
+const IMPLIED_VOL_MAX_ITER: usize = 200;
+const MAX_BRACKET_DOUBLINGS: usize = 200;
+
+/// Validación de entradas: F y K pueden ser negativos pero deben ser finitos.
+#[inline]
+fn validate_inputs(f: ForwardPrice, k: StrikePrice, t: TimeToMaturity, r: InterestRate, sigma: Volatility) -> FinanceResult<()> {
+    if !f.is_finite() || !k.is_finite() || !t.is_finite() || !r.is_finite() || !sigma.is_finite() {
+        return Err(FinanceError::InvalidMonetaryValue);
+    }
+    if t < 0.0 {
+        return Err(FinanceError::InvalidPeriods);
+    }
+    if sigma < 0.0 {
+        return Err(FinanceError::InvalidInterestRate);
+    }
+    Ok(())
+}
+
+/// Calcula d = (F - K) / (σ_N √T), con los mismos límites que `d1_d2`.
+pub fn bachelier_d(f: ForwardPrice, k: StrikePrice, t: TimeToMaturity, sigma: Volatility) -> FinanceResult<f64> {
+    validate_inputs(f, k, t, 0.0, sigma)?;
+
+    if t < EPS_TIME || sigma < EPS_VOL {
+        let sign = (f - k).signum();
+        return Ok(if f == k { 0.0 } else { sign * f64::INFINITY });
+    }
+    Ok((f - k) / (sigma * t.sqrt()))
+}
+
+/// Precio de call europea en el modelo normal.
+pub fn bachelier_call_price(f: ForwardPrice, k: StrikePrice, t: TimeToMaturity, r: InterestRate, sigma: Volatility) -> FinanceResult<f64> {
+    bachelier_price(f, k, t, r, sigma, OptionType::Call)
+}
+
+/// Precio de put europea en el modelo normal.
+pub fn bachelier_put_price(f: ForwardPrice, k: StrikePrice, t: TimeToMaturity, r: InterestRate, sigma: Volatility) -> FinanceResult<f64> {
+    bachelier_price(f, k, t, r, sigma, OptionType::Put)
+}
+
+/// Precio genérico del modelo normal según OptionType.
+pub fn bachelier_price(f: ForwardPrice, k: StrikePrice, t: TimeToMaturity, r: InterestRate, sigma: Volatility, kind: OptionType) -> FinanceResult<f64> {
+    validate_inputs(f, k, t, r, sigma)?;
+    let df = (-r * t).exp();
+
+    if t < EPS_TIME || sigma < EPS_VOL {
+        return Ok(df * kind.payoff(f, k));
+    }
+    let d = bachelier_d(f, k, t, sigma)?;
+    let time_value = sigma * t.sqrt() * normal_pdf(d);
+    Ok(match kind {
+        OptionType::Call => df * ((f - k) * normal_cdf(d) + time_value),
+        OptionType::Put  => df * ((k - f) * normal_cdf(-d) + time_value),
+    })
+}
+
+/// Griegas del modelo normal (respecto al forward, con σ_N absoluta).
+///
+/// Fórmulas, con df = e^{-rT}:
+/// Δ_c = df N(d), Δ_p = -df N(-d)
+/// Γ = df φ(d) / (σ√T), Vega = df √T φ(d)
+/// Θ = rV - df σ φ(d) / (2√T), ρ = -T V
+/// Vanna = -df φ(d) d / σ, Volga = df √T φ(d) d² / σ
+/// Charm_c = r df N(d) + df φ(d) d / (2T), Charm_p = -r df N(-d) + df φ(d) d / (2T)
+/// Speed = -df φ(d) d / (σ² T)
+pub fn bachelier_greeks(f: ForwardPrice, k: StrikePrice, t: TimeToMaturity, r: InterestRate, sigma: Volatility, kind: OptionType) -> FinanceResult<Greeks> {
+    let price = bachelier_price(f, k, t, r, sigma, kind)?;
+    let d = bachelier_d(f, k, t, sigma)?;
+    let df = (-r * t).exp();
+
+    let (delta, carry_charm) = match kind {
+        OptionType::Call => (df * normal_cdf(d), r * df * normal_cdf(d)),
+        OptionType::Put  => (-df * normal_cdf(-d), -r * df * normal_cdf(-d)),
+    };
+    let theta_carry = r * price;
+    let rho = -t * price;
+
+    if t < EPS_TIME || sigma < EPS_VOL {
+        return Ok(Greeks { delta, theta: theta_carry, rho, charm: carry_charm, ..Greeks::default() });
+    }
+
+    let sqrt_t = t.sqrt();
+    let pdf_d = df * normal_pdf(d);
+
+    Ok(Greeks {
+        delta,
+        gamma: pdf_d / (sigma * sqrt_t),
+        vega: sqrt_t * pdf_d,
+        theta: theta_carry - sigma * pdf_d / (2.0 * sqrt_t),
+        rho,
+        vanna: -pdf_d * d / sigma,
+        volga: sqrt_t * pdf_d * d * d / sigma,
+        charm: carry_charm + pdf_d * d / (2.0 * t),
+        speed: -pdf_d * d / (sigma * sigma * t),
+    })
+}
+
+/// Volatilidad normal implícita a partir de un precio de mercado.
+///
+/// Newton-Raphson con vega protegido por bisección. En el modelo normal la
+/// prima no está acotada superiormente, así que solo se comprueba la cota
+/// inferior df·max(±(F - K), 0).
+///
+/// # Errores
+/// * `ArbitrageViolation` - Si el precio es inferior al valor intrínseco descontado
+/// * `InvalidPeriods` - Si `t < EPS_TIME`
+/// * `NoConvergence` - Si el solver no converge
+pub fn bachelier_implied_volatility(market_price: f64, f: ForwardPrice, k: StrikePrice, t: TimeToMaturity, r: InterestRate, kind: OptionType) -> FinanceResult<Volatility> {
+    validate_inputs(f, k, t, r, 0.0)?;
+    if !market_price.is_finite() || market_price < 0.0 {
+        return Err(FinanceError::InvalidMonetaryValue);
+    }
+    if t < EPS_TIME {
+        return Err(FinanceError::InvalidPeriods);
+    }
+
+    let df = (-r * t).exp();
+    let intrinsic = df * kind.payoff(f, k);
+    let scale = market_price.max(f.abs()).max(k.abs()).max(1.0);
+    if market_price < intrinsic - IMPLIED_VOL_TOL * scale {
+        return Err(FinanceError::ArbitrageViolation);
+    }
+    if market_price <= intrinsic {
+        return Ok(0.0);
+    }
+
+    let objective = |sigma: f64| -> (f64, f64) {
+        let price = bachelier_price(f, k, t, r, sigma, kind).unwrap_or(f64::NAN);
+        let vega = bachelier_greeks(f, k, t, r, sigma, kind).map(|g| g.vega).unwrap_or(0.0);
+        (price - market_price, vega)
+    };
+
+    // El valor temporal es máximo en el dinero: σ ≥ (V - intrínseco) / (df √T φ(0))
+    let mut hi = 2.0 * (market_price - intrinsic) / (df * t.sqrt() * normal_pdf(0.0));
+    let mut doublings = 0;
+    while objective(hi).0 < 0.0 {
+        hi *= 2.0;
+        doublings += 1;
+        if doublings > MAX_BRACKET_DOUBLINGS {
+            return Err(FinanceError::NoConvergence);
+        }
+    }
+
+    newton_safeguarded(objective, 0.0, hi, 0.5 * hi, IMPLIED_VOL_TOL * hi, IMPLIED_VOL_MAX_ITER)
+}
+
+/// Volatilidad normal equivalente a una volatilidad lognormal (Black-76).
+///
+/// Ambas vols producen el mismo precio (la conversión no depende de r).
+/// Requiere F > 0 y K > 0.
+pub fn lognormal_to_normal_vol(f: ForwardPrice, k: StrikePrice, t: TimeToMaturity, sigma_ln: Volatility) -> FinanceResult<Volatility> {
+    let kind = if k >= f { OptionType::Call } else { OptionType::Put };
+    let price = black76_price(f, k, t, 0.0, sigma_ln, kind)?;
+    bachelier_implied_volatility(price, f, k, t, 0.0, kind)
+}
+
+/// Volatilidad lognormal (Black-76) equivalente a una volatilidad normal.
+///
+/// Requiere F > 0 y K > 0.
+pub fn normal_to_lognormal_vol(f: ForwardPrice, k: StrikePrice, t: TimeToMaturity, sigma_n: Volatility) -> FinanceResult<Volatility> {
+    let kind = if k >= f { OptionType::Call } else { OptionType::Put };
+    let price = bachelier_price(f, k, t, 0.0, sigma_n, kind)?;
+    black76_implied_volatility(price, f, k, t, 0.0, kind)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn atm_closed_form() {
+        // En el dinero: C = P = df σ √T / √(2π)
+        let (f, t, r, sigma): (f64, f64, f64, f64) = (0.01, 2.0, 0.02, 0.0075);
+        let expected = (-r * t).exp() * sigma * t.sqrt() * normal_pdf(0.0);
+        let c = bachelier_call_price(f, f, t, r, sigma).unwrap();
+        let p = bachelier_put_price(f, f, t, r, sigma).unwrap();
+        assert!((c - expected).abs() < 1e-14);
+        assert!((p - expected).abs() < 1e-14);
+    }
+
+    #[test]
+    fn negative_forward_and_strike() {
+        let (f, k, t, r, sigma) = (-0.0025, -0.001, 1.5, -0.005, 0.006);
+        let c = bachelier_call_price(f, k, t, r, sigma).unwrap();
+        let p = bachelier_put_price(f, k, t, r, sigma).unwrap();
+        assert!(c > 0.0 && p > 0.0);
+        assert!((c - p - (-r * t).exp() * (f - k)).abs() < 1e-14);
+    }
+
+    #[test]
+    fn greeks_match_finite_differences() {
+        let (f, k, t, r, sigma) = (0.5, 0.8, 0.9, 0.03, 0.6);
+        for kind in [OptionType::Call, OptionType::Put] {
+            let g = bachelier_greeks(f, k, t, r, sigma, kind).unwrap();
+            let price = |f: f64, t: f64, s: f64| bachelier_price(f, k, t, r, s, kind).unwrap();
+            let greeks_at = |f: f64, t: f64, s: f64| bachelier_greeks(f, k, t, r, s, kind).unwrap();
+
+            let h = 1e-4;
+            assert!((g.delta - (price(f + h, t, sigma) - price(f - h, t, sigma)) / (2.0 * h)).abs() < 1e-5);
+            assert!((g.gamma - (greeks_at(f + h, t, sigma).delta - greeks_at(f - h, t, sigma).delta) / (2.0 * h)).abs() < 1e-4);
+            assert!((g.vega - (price(f, t, sigma + h) - price(f, t, sigma - h)) / (2.0 * h)).abs() < 1e-5);
+            assert!((g.theta + (price(f, t + h, sigma) - price(f, t - h, sigma)) / (2.0 * h)).abs() < 1e-5);
+            assert!((g.vanna - (greeks_at(f, t, sigma + h).delta - greeks_at(f, t, sigma - h).delta) / (2.0 * h)).abs() < 1e-4);
+            assert!((g.volga - (greeks_at(f, t, sigma + h).vega - greeks_at(f, t, sigma - h).vega) / (2.0 * h)).abs() < 1e-4);
+            assert!((g.charm + (greeks_at(f, t + h, sigma).delta - greeks_at(f, t - h, sigma).delta) / (2.0 * h)).abs() < 1e-4);
+            assert!((g.speed - (greeks_at(f + h, t, sigma).gamma - greeks_at(f - h, t, sigma).gamma) / (2.0 * h)).abs() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn implied_volatility_round_trip() {
+        for &k in &[-0.01, 0.0, 0.005, 0.02] {
+            for kind in [OptionType::Call, OptionType::Put] {
+                let price = bachelier_price(0.005, k, 3.0, 0.01, 0.008, kind).unwrap();
+                let iv = bachelier_implied_volatility(price, 0.005, k, 3.0, 0.01, kind).unwrap();
+                assert!((iv - 0.008).abs() < 1e-8, "K={k} {kind:?}");
+            }
+        }
+        let err = bachelier_implied_volatility(0.0, 0.02, 0.01, 1.0, 0.0, OptionType::Call).unwrap_err();
+        assert_eq!(err, FinanceError::ArbitrageViolation);
+    }
+
+    #[test]
+    fn vol_conversion_round_trip() {
+        let (f, t) = (0.03, 5.0);
+        for &k in &[0.02, 0.03, 0.045] {
+            let sigma_n = lognormal_to_normal_vol(f, k, t, 0.25).unwrap();
+            // Aproximación de primer orden: σ_N ≈ σ_LN √(F K)
+            let approx = 0.25 * (f * k).sqrt();
+            assert!((sigma_n - approx).abs() < 0.03 * approx);
+            let sigma_ln = normal_to_lognormal_vol(f, k, t, sigma_n).unwrap();
+            assert!((sigma_ln - 0.25).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn validates_inputs() {
+        assert_eq!(bachelier_call_price(f64::NAN, 0.0, 1.0, 0.0, 0.01).unwrap_err(), FinanceError::InvalidMonetaryValue);
+        assert_eq!(bachelier_call_price(0.0, 0.0, -1.0, 0.0, 0.01).unwrap_err(), FinanceError::InvalidPeriods);
+        assert_eq!(bachelier_call_price(0.0, 0.0, 1.0, 0.0, -0.01).unwrap_err(), FinanceError::InvalidInterestRate);
+        assert_eq!(normal_to_lognormal_vol(-0.01, 0.01, 1.0, 0.01).unwrap_err(), FinanceError::InvalidMonetaryValue);
+    }
+}
@@ -0,0 +1,375 @@
+//! Opciones barrera europeas en forma cerrada (Reiner y Rubinstein, 1991).
+//!
+//! Con b = r - q, φ = ±1 (call/put), η = ±1 (barrera abajo/arriba) y
+//! μ = (b - σ²/2)/σ², λ = √(μ² + 2r/σ²), el precio se compone de los bloques
+//! A = φS e^{(b-r)T} N(φx1) - φK e^{-rT} N(φx1 - φσ√T)
+//! B = φS e^{(b-r)T} N(φx2) - φK e^{-rT} N(φx2 - φσ√T)
+//! C = φS e^{(b-r)T} (H/S)^{2(μ+1)} N(ηy1) - φK e^{-rT} (H/S)^{2μ} N(ηy1 - ησ√T)
+//! D = φS e^{(b-r)T} (H/S)^{2(μ+1)} N(ηy2) - φK e^{-rT} (H/S)^{2μ} N(ηy2 - ησ√T)
+//! E = R e^{-rT} [N(ηx2 - ησ√T) - (H/S)^{2μ} N(ηy2 - ησ√T)]
+//! F = R [(H/S)^{μ+λ} N(ηz) + (H/S)^{μ-λ} N(ηz - 2ηλσ√T)]
+//! donde x1 = ln(S/K)/(σ√T) + (1+μ)σ√T, x2 = ln(S/H)/(σ√T) + (1+μ)σ√T,
+//! y1 = ln(H²/(SK))/(σ√T) + (1+μ)σ√T, y2 = ln(H/S)/(σ√T) + (1+μ)σ√T y
+//! z = ln(H/S)/(σ√T) + λσ√T. E es el rebate de las knock-in (pagado al
+//! vencimiento si no se toca) y F el de las knock-out (pagado al tocar).
+//!
+//! Observación discreta cada Δt: corrección de Broadie, Glasserman y Kou
+//! (1997), que valora con la fórmula continua desplazando la barrera
+//! H → H e^{±βσ√Δt} (alejándola del spot), con β = -ζ(1/2)/√(2π) ≈ 0.5826.
+
+use crate::common::{
+    FinanceError, FinanceResult,
+    MonetaryValue, TimeInYears, DividendYield,
+    SpotPrice, StrikePrice, TimeToMaturity, InterestRate, Volatility,
+    OptionType, BarrierType
+};
+use super::black_scholes::{normal_cdf, option_price_dividend, validate_inputs, EPS_TIME, EPS_VOL};
+
+/// Constante de Broadie-Glasserman-Kou: -ζ(1/2)/√(2π)
+pub const BGK_BETA: f64 = 0.582_597_157_939_010_6;
+
+/// Frecuencia de observación de la barrera
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BarrierMonitoring {
+    /// Observación continua
+    Continuous,
+    /// Observación discreta con el intervalo indicado (en años)
+    Discrete(TimeInYears),
+}
+
+/// Opción barrera europea
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BarrierOption {
+    /// Tipo de barrera
+    pub barrier_type: BarrierType,
+    /// Nivel de la barrera (H)
+    pub level: SpotPrice,
+    /// Rebate: pagado al tocar en las knock-out y al vencimiento si no se
+    /// toca en las knock-in
+    pub rebate: MonetaryValue,
+    /// Rendimiento por dividendo continuo del subyacente
+    pub dividend_yield: DividendYield,
+    /// Frecuencia de observación de la barrera
+    pub monitoring: BarrierMonitoring,
+}
+
+impl BarrierOption {
+    /// Crea una barrera de observación continua, sin rebate ni dividendos.
+    ///
+    /// # Errores
+    /// * `InvalidMonetaryValue` - Si el nivel no es positivo
+    pub fn new(barrier_type: BarrierType, level: SpotPrice) -> FinanceResult<Self> {
+        if !level.is_finite() || level <= 0.0 {
+            return Err(FinanceError::InvalidMonetaryValue);
+        }
+        Ok(BarrierOption {
+            barrier_type,
+            level,
+            rebate: 0.0,
+            dividend_yield: 0.0,
+            monitoring: BarrierMonitoring::Continuous,
+        })
+    }
+
+    /// Añade un rebate.
+    ///
+    /// # Errores
+    /// * `InvalidMonetaryValue` - Si el rebate es negativo
+    pub fn with_rebate(mut self, rebate: MonetaryValue) -> FinanceResult<Self> {
+        if !rebate.is_finite() || rebate < 0.0 {
+            return Err(FinanceError::InvalidMonetaryValue);
+        }
+        self.rebate = rebate;
+        Ok(self)
+    }
+
+    /// Cambia el rendimiento por dividendo continuo.
+    ///
+    /// # Errores
+    /// * `InvalidInterestRate` - Si no es finito
+    pub fn with_dividend_yield(mut self, q: DividendYield) -> FinanceResult<Self> {
+        if !q.is_finite() {
+            return Err(FinanceError::InvalidInterestRate);
+        }
+        self.dividend_yield = q;
+        Ok(self)
+    }
+
+    /// Cambia la frecuencia de observación.
+    ///
+    /// # Errores
+    /// * `InvalidPeriods` - Si el intervalo discreto no es positivo
+    pub fn with_monitoring(mut self, monitoring: BarrierMonitoring) -> FinanceResult<Self> {
+        if let BarrierMonitoring::Discrete(dt) = monitoring && (!dt.is_finite() || dt <= 0.0) {
+            return Err(FinanceError::InvalidPeriods);
+        }
+        self.monitoring = monitoring;
+        Ok(self)
+    }
+
+    /// Nivel usado en la fórmula continua: H con observación continua y H
+    /// desplazada según Broadie-Glasserman-Kou con observación discreta.
+    pub fn effective_level(&self, sigma: Volatility) -> SpotPrice {
+        match self.monitoring {
+            BarrierMonitoring::Continuous => self.level,
+            BarrierMonitoring::Discrete(dt) => {
+                let shift = BGK_BETA * sigma * dt.sqrt();
+                if self.barrier_type.is_down() { self.level * (-shift).exp() } else { self.level * shift.exp() }
+            }
+        }
+    }
+
+    /// Precio de la opción barrera.
+    ///
+    /// Si el spot ya está en la barrera o más allá, la knock-in vale la
+    /// vanilla y la knock-out el rebate.
+    ///
+    /// # Errores
+    /// * `InvalidInterestRate` - Si `sigma` es nula o negativa
+    pub fn price(&self, s0: SpotPrice, k: StrikePrice, t: TimeToMaturity, r: InterestRate, sigma: Volatility, kind: OptionType) -> FinanceResult<f64> {
+        validate_inputs(s0, k, t, r, sigma)?;
+        let knock_in = self.barrier_type.is_knock_in();
+
+        if self.barrier_type.is_breached(s0, self.level) {
+            if !knock_in {
+                return Ok(self.rebate);
+            }
+            return option_price_dividend(s0, k, t, r, self.dividend_yield, sigma, kind);
+        }
+        if t < EPS_TIME {
+            return Ok(if knock_in { self.rebate } else { kind.payoff(s0, k) });
+        }
+        if sigma < EPS_VOL {
+            return Err(FinanceError::InvalidInterestRate);
+        }
+
+        let blocks = self.blocks(s0, k, t, r, sigma, kind);
+        let (a, b, c, d) = (blocks.a(), blocks.b(), blocks.c(), blocks.d());
+        let e = blocks.e(self.rebate);
+        let f = blocks.f(self.rebate);
+        let above = k > blocks.h;
+
+        let price = match (self.barrier_type, kind) {
+            (BarrierType::DownAndIn, OptionType::Call) => if above { c + e } else { a - b + d + e },
+            (BarrierType::UpAndIn, OptionType::Call) => if above { a + e } else { b - c + d + e },
+            (BarrierType::DownAndIn, OptionType::Put) => if above { b - c + d + e } else { a + e },
+            (BarrierType::UpAndIn, OptionType::Put) => if above { a - b + d + e } else { c + e },
+            (BarrierType::DownAndOut, OptionType::Call) => if above { a - c + f } else { b - d + f },
+            (BarrierType::UpAndOut, OptionType::Call) => if above { f } else { a - b + c - d + f },
+            (BarrierType::DownAndOut, OptionType::Put) => if above { a - b + c - d + f } else { f },
+            (BarrierType::UpAndOut, OptionType::Put) => if above { b - d + f } else { a - c + f },
+        };
+        Ok(price.max(0.0))
+    }
+
+    /// Términos de Reiner-Rubinstein con la barrera efectiva.
+    fn blocks(&self, s0: SpotPrice, k: StrikePrice, t: TimeToMaturity, r: InterestRate, sigma: Volatility, kind: OptionType) -> Blocks {
+        let v2 = sigma * sigma;
+        let b = r - self.dividend_yield;
+        let mu = (b - 0.5 * v2) / v2;
+        Blocks {
+            s: s0,
+            k,
+            h: self.effective_level(sigma),
+            phi: if kind == OptionType::Call { 1.0 } else { -1.0 },
+            eta: if self.barrier_type.is_down() { 1.0 } else { -1.0 },
+            mu,
+            lambda: (mu * mu + 2.0 * r / v2).sqrt(),
+            sigma_sqrt_t: sigma * t.sqrt(),
+            df: (-r * t).exp(),
+            carry_df: ((b - r) * t).exp(),
+        }
+    }
+}
+
+/// Términos comunes de las fórmulas de Reiner-Rubinstein.
+struct Blocks {
+    s: SpotPrice,
+    k: StrikePrice,
+    h: SpotPrice,
+    /// φ: +1 call, -1 put
+    phi: f64,
+    /// η: +1 barrera abajo, -1 arriba
+    eta: f64,
+    mu: f64,
+    lambda: f64,
+    sigma_sqrt_t: f64,
+    df: f64,
+    carry_df: f64,
+}
+
+impl Blocks {
+    /// ln(x)/(σ√T) + (1 + μ)σ√T
+    fn point(&self, x: f64) -> f64 {
+        x.ln() / self.sigma_sqrt_t + (1.0 + self.mu) * self.sigma_sqrt_t
+    }
+
+    /// Término tipo vanilla: φS e^{(b-r)T} N(φx) - φK e^{-rT} N(φx - φσ√T)
+    fn vanilla_term(&self, x: f64) -> f64 {
+        let phi = self.phi;
+        phi * self.s * self.carry_df * normal_cdf(phi * x)
+            - phi * self.k * self.df * normal_cdf(phi * x - phi * self.sigma_sqrt_t)
+    }
+
+    /// Término reflejado en la barrera
+    fn reflected_term(&self, y: f64) -> f64 {
+        let (phi, eta) = (self.phi, self.eta);
+        let ratio = self.h / self.s;
+        phi * self.s * self.carry_df * ratio.powf(2.0 * (self.mu + 1.0)) * normal_cdf(eta * y)
+            - phi * self.k * self.df * ratio.powf(2.0 * self.mu) * normal_cdf(eta * y - eta * self.sigma_sqrt_t)
+    }
+
+    fn a(&self) -> f64 {
+        self.vanilla_term(self.point(self.s / self.k))
+    }
+
+    fn b(&self) -> f64 {
+        self.vanilla_term(self.point(self.s / self.h))
+    }
+
+    fn c(&self) -> f64 {
+        self.reflected_term(self.point(self.h * self.h / (self.s * self.k)))
+    }
+
+    fn d(&self) -> f64 {
+        self.reflected_term(self.point(self.h / self.s))
+    }
+
+    /// Rebate de knock-in pagado al vencimiento si no se toca la barrera
+    fn e(&self, rebate: MonetaryValue) -> f64 {
+        if rebate == 0.0 {
+            return 0.0;
+        }
+        let eta = self.eta;
+        let x2 = self.point(self.s / self.h);
+        let y2 = self.point(self.h / self.s);
+        rebate * self.df * (
+            normal_cdf(eta * x2 - eta * self.sigma_sqrt_t)
+            - (self.h / self.s).powf(2.0 * self.mu) * normal_cdf(eta * y2 - eta * self.sigma_sqrt_t)
+        )
+    }
+
+    /// Rebate de knock-out pagado al tocar la barrera
+    fn f(&self, rebate: MonetaryValue) -> f64 {
+        if rebate == 0.0 {
+            return 0.0;
+        }
+        let eta = self.eta;
+        let ratio = self.h / self.s;
+        let z = ratio.ln() / self.sigma_sqrt_t + self.lambda * self.sigma_sqrt_t;
+        rebate * (
+            ratio.powf(self.mu + self.lambda) * normal_cdf(eta * z)
+            + ratio.powf(self.mu - self.lambda) * normal_cdf(eta * z - 2.0 * eta * self.lambda * self.sigma_sqrt_t)
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::options::{option_price, TrinomialTree, LatticeBarrier};
+
+    const TYPES: [BarrierType; 4] = [
+        BarrierType::DownAndOut,
+        BarrierType::DownAndIn,
+        BarrierType::UpAndOut,
+        BarrierType::UpAndIn,
+    ];
+
+    #[test]
+    fn haug_reference_values() {
+        // Haug (2007), tabla 4-13: S=100, R=3, T=0.5, r=8%, b=4%, σ=25%
+        let cases = [
+            (BarrierType::DownAndOut, OptionType::Call, 95.0, [9.0246, 6.7924, 4.8759]),
+            (BarrierType::DownAndOut, OptionType::Call, 100.0, [3.0, 3.0, 3.0]),
+            (BarrierType::UpAndOut, OptionType::Call, 105.0, [2.6789, 2.3580, 2.3453]),
+            (BarrierType::DownAndIn, OptionType::Call, 95.0, [7.7627, 4.0109, 2.0576]),
+            (BarrierType::DownAndIn, OptionType::Call, 100.0, [13.8333, 7.8494, 3.9795]),
+            (BarrierType::UpAndIn, OptionType::Call, 105.0, [14.1112, 8.4482, 4.5910]),
+            (BarrierType::DownAndOut, OptionType::Put, 95.0, [2.2798, 2.2947, 2.6252]),
+            (BarrierType::UpAndOut, OptionType::Put, 105.0, [3.7760, 5.4932, 7.5187]),
+            (BarrierType::DownAndIn, OptionType::Put, 95.0, [2.9586, 6.5677, 11.9752]),
+            (BarrierType::UpAndIn, OptionType::Put, 105.0, [1.4653, 3.3721, 7.0846]),
+        ];
+        for (barrier_type, kind, h, expected) in cases {
+            let option = BarrierOption::new(barrier_type, h).unwrap()
+                .with_rebate(3.0).unwrap()
+                .with_dividend_yield(0.04).unwrap();
+            for (k, reference) in [90.0, 100.0, 110.0].into_iter().zip(expected) {
+                let price = option.price(100.0, k, 0.5, 0.08, 0.25, kind).unwrap();
+                assert!((price - reference).abs() < 1e-3, "{barrier_type:?} {kind:?} H={h} K={k}: {price}");
+            }
+        }
+    }
+
+    #[test]
+    fn in_out_parity() {
+        let (s0, t, r, sigma) = (100.0, 1.0, 0.05, 0.3);
+        for kind in [OptionType::Call, OptionType::Put] {
+            for (out_type, in_type, h) in [(BarrierType::DownAndOut, BarrierType::DownAndIn, 85.0), (BarrierType::UpAndOut, BarrierType::UpAndIn, 120.0)] {
+                for k in [80.0, 100.0, 130.0] {
+                    let vanilla = option_price(s0, k, t, r, sigma, kind).unwrap();
+                    let out = BarrierOption::new(out_type, h).unwrap().price(s0, k, t, r, sigma, kind).unwrap();
+                    let knock_in = BarrierOption::new(in_type, h).unwrap().price(s0, k, t, r, sigma, kind).unwrap();
+                    assert!((out + knock_in - vanilla).abs() < 1e-10, "{kind:?} {out_type:?} K={k}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn matches_trinomial_tree() {
+        let (s0, k, t, r, sigma) = (100.0, 100.0, 1.0, 0.05, 0.25);
+        for (barrier_type, h) in [(BarrierType::DownAndOut, 90.0), (BarrierType::UpAndOut, 115.0), (BarrierType::DownAndIn, 90.0), (BarrierType::UpAndIn, 115.0)] {
+            let lattice = LatticeBarrier { barrier_type, level: h, rebate: 2.0 };
+            let tree = TrinomialTree::new(2000).unwrap().with_barrier(lattice).unwrap();
+            let option = BarrierOption::new(barrier_type, h).unwrap().with_rebate(2.0).unwrap();
+            for kind in [OptionType::Call, OptionType::Put] {
+                let reference = tree.price(s0, k, t, r, sigma, kind).unwrap();
+                let price = option.price(s0, k, t, r, sigma, kind).unwrap();
+                assert!((price - reference).abs() < 5e-3, "{barrier_type:?} {kind:?}: {price} vs {reference}");
+            }
+        }
+    }
+
+    #[test]
+    fn breached_and_expired() {
+        for barrier_type in TYPES {
+            let h = if barrier_type.is_down() { 105.0 } else { 95.0 };
+            let option = BarrierOption::new(barrier_type, h).unwrap().with_rebate(1.5).unwrap();
+            let price = option.price(100.0, 100.0, 1.0, 0.05, 0.2, OptionType::Call).unwrap();
+            let expected = if barrier_type.is_knock_in() { option_price_dividend(100.0, 100.0, 1.0, 0.05, 0.0, 0.2, OptionType::Call).unwrap() } else { 1.5 };
+            assert!((price - expected).abs() < 1e-12);
+
+            let h = if barrier_type.is_down() { 90.0 } else { 110.0 };
+            let option = BarrierOption::new(barrier_type, h).unwrap().with_rebate(1.5).unwrap();
+            let expired = option.price(105.0, 100.0, 0.0, 0.05, 0.2, OptionType::Call).unwrap();
+            assert_eq!(expired, if barrier_type.is_knock_in() { 1.5 } else { 5.0 });
+        }
+    }
+
+    #[test]
+    fn discrete_monitoring_correction() {
+        let (s0, k, t, r, sigma) = (100.0, 100.0, 0.2, 0.1, 0.3);
+        let continuous = BarrierOption::new(BarrierType::UpAndOut, 130.0).unwrap();
+        let daily = continuous.with_monitoring(BarrierMonitoring::Discrete(1.0 / 250.0)).unwrap();
+        let weekly = continuous.with_monitoring(BarrierMonitoring::Discrete(1.0 / 50.0)).unwrap();
+        let c = continuous.price(s0, k, t, r, sigma, OptionType::Call).unwrap();
+        let cd = daily.price(s0, k, t, r, sigma, OptionType::Call).unwrap();
+        let cw = weekly.price(s0, k, t, r, sigma, OptionType::Call).unwrap();
+        // Observar menos a menudo hace menos probable la cancelación
+        assert!(c < cd && cd < cw);
+        assert!(weekly.effective_level(sigma) > daily.effective_level(sigma));
+        // Monte Carlo con 50 observaciones (3·10⁵ trayectorias): 4.779 ± 0.013
+        assert!((cd - 4.779).abs() < 0.05, "{cd}");
+    }
+
+    #[test]
+    fn invalid_configuration() {
+        assert_eq!(BarrierOption::new(BarrierType::DownAndOut, 0.0).unwrap_err(), FinanceError::InvalidMonetaryValue);
+        let option = BarrierOption::new(BarrierType::DownAndOut, 90.0).unwrap();
+        assert_eq!(option.with_rebate(-1.0).unwrap_err(), FinanceError::InvalidMonetaryValue);
+        assert_eq!(option.with_monitoring(BarrierMonitoring::Discrete(0.0)).unwrap_err(), FinanceError::InvalidPeriods);
+        assert_eq!(option.price(100.0, 100.0, 1.0, 0.05, 0.0, OptionType::Call).unwrap_err(), FinanceError::InvalidInterestRate);
+    }
+}
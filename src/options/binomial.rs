@@ -0,0 +1,467 @@
+//! Árboles binomiales recombinantes para opciones europeas, americanas y
+//! bermudas.
+//!
+//! Parametrizaciones disponibles, con Δt = T/n:
+//! - Cox-Ross-Rubinstein: u = e^{σ√Δt}, d = 1/u, p = (e^{rΔt} - d) / (u - d)
+//! - Jarrow-Rudd: u, d = e^{(r - σ²/2)Δt ± σ√Δt}, p = 1/2
+//! - Leisen-Reimer: p = h(d2), p' = h(d1), u = e^{rΔt} p'/p,
+//!   d = (e^{rΔt} - p u) / (1 - p), con h la inversión de Peizer-Pratt.
+//!   Requiere n impar (si se pide un n par se usa n + 1). Con dividendos
+//!   discretos d1 y d2 se evalúan en S0 - VP(dividendos).
+//!
+//! Dividendos discretos: el árbol modela el precio real del subyacente y en
+//! cada fecha ex-dividendo el valor de la opción en el nodo S se sustituye por
+//! el valor (interpolado linealmente sobre los nodos del mismo paso) en S - D,
+//! siguiendo a Vellekoop y Nieuwenhuis (2006). Así el árbol sigue siendo
+//! recombinante y el salto del subyacente se trata de forma exacta.
+
+use crate::common::{
+    FinanceError, FinanceResult,
+    SpotPrice, StrikePrice, TimeToMaturity, InterestRate, Volatility,
+    OptionType, ExerciseStyle
+};
+use super::black_scholes::{d1_d2, validate_inputs, EPS_TIME, EPS_VOL};
+use super::dividends::{CashDividend, escrowed_spot, validate_dividends};
+
+/// Parametrización del árbol binomial
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BinomialModel {
+    CoxRossRubinstein,
+    JarrowRudd,
+    LeisenReimer,
+}
+
+/// Precio y sensibilidades obtenidas directamente de los nodos de un retículo.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LatticeGreeks {
+    /// Precio de la opción, en unidades monetarias
+    pub price: f64,
+    /// ∂V/∂S, en unidades monetarias por unidad de subyacente
+    pub delta: f64,
+    /// ∂²V/∂S², por unidad de subyacente al cuadrado
+    pub gamma: f64,
+    /// ∂V/∂t = −∂V/∂T por año de calendario; negativa cuando la opción pierde
+    /// valor con el paso del tiempo (mismo convenio que `Greeks::theta`)
+    pub theta: f64,
+}
+
+/// Configuración de un árbol binomial
+#[derive(Debug, Clone, PartialEq)]
+pub struct BinomialTree {
+    /// Parametrización (u, d, p)
+    pub model: BinomialModel,
+    /// Número de pasos temporales
+    pub steps: usize,
+    /// Estilo de ejercicio
+    pub exercise: ExerciseStyle,
+    /// Dividendos discretos conocidos
+    pub dividends: Vec<CashDividend>,
+}
+
+impl BinomialTree {
+    /// Crea un árbol CRR europeo con `steps` pasos y sin dividendos.
+    ///
+    /// # Errores
+    /// * `InvalidPeriods` - Si `steps` es cero
+    pub fn new(steps: usize) -> FinanceResult<Self> {
+        if steps == 0 {
+            return Err(FinanceError::InvalidPeriods);
+        }
+        Ok(BinomialTree {
+            model: BinomialModel::CoxRossRubinstein,
+            steps,
+            exercise: ExerciseStyle::European,
+            dividends: Vec::new(),
+        })
+    }
+
+    /// Cambia la parametrización del árbol.
+    pub fn with_model(mut self, model: BinomialModel) -> Self {
+        self.model = model;
+        self
+    }
+
+    /// Cambia el estilo de ejercicio.
+    pub fn with_exercise(mut self, exercise: ExerciseStyle) -> FinanceResult<Self> {
+        validate_exercise(&exercise)?;
+        self.exercise = exercise;
+        Ok(self)
+    }
+
+    /// Añade un calendario de dividendos discretos.
+    pub fn with_dividends(mut self, dividends: Vec<CashDividend>) -> FinanceResult<Self> {
+        validate_dividends(&dividends)?;
+        self.dividends = dividends;
+        Ok(self)
+    }
+
+    /// Precio por inducción hacia atrás.
+    ///
+    /// # Errores
+    /// * `InvalidInterestRate` - Si `sigma` es nula o negativa
+    /// * `InvalidInput` - Si la probabilidad neutral al riesgo sale de (0, 1)
+    /// * `InvalidMonetaryValue` - Si en Leisen-Reimer el VP de los dividendos agota el spot
+    pub fn price(&self, s0: SpotPrice, k: StrikePrice, t: TimeToMaturity, r: InterestRate, sigma: Volatility, kind: OptionType) -> FinanceResult<f64> {
+        validate_inputs(s0, k, t, r, sigma)?;
+        if t < EPS_TIME {
+            return Ok(kind.payoff(s0, k));
+        }
+        Ok(self.roll_back(s0, k, t, r, sigma, kind)?.values[0][0])
+    }
+
+    /// Precio, delta, gamma y theta a partir de los nodos de los pasos 0, 1 y 2.
+    ///
+    /// Δ = (V₁₁ - V₁₀) / (S₁₁ - S₁₀)
+    /// Γ = [(V₂₂ - V₂₁)/(S₂₂ - S₂₁) - (V₂₁ - V₂₀)/(S₂₁ - S₂₀)] / [(S₂₂ - S₂₀)/2]
+    /// Θ = (V₂₁ - Δ₂₁ ΔS - ½Γ ΔS² - V₀₀) / (2Δt), con ΔS = S₂₁ - S₀
+    ///
+    /// El nodo central del paso 2 solo coincide con S₀ si u·d = 1 (CRR); en
+    /// Jarrow-Rudd y Leisen-Reimer se lleva V₂₁ a S₀ con un desarrollo de
+    /// Taylor de segundo orden, con Δ₂₁ la pendiente en S₂₁ de la parábola
+    /// que pasa por los tres nodos del paso 2.
+    ///
+    /// # Errores
+    /// * `InvalidPeriods` - Si el árbol tiene menos de 2 pasos o `t < EPS_TIME`
+    pub fn greeks(&self, s0: SpotPrice, k: StrikePrice, t: TimeToMaturity, r: InterestRate, sigma: Volatility, kind: OptionType) -> FinanceResult<LatticeGreeks> {
+        validate_inputs(s0, k, t, r, sigma)?;
+        if self.steps < 2 || t < EPS_TIME {
+            return Err(FinanceError::InvalidPeriods);
+        }
+        let lattice = self.roll_back(s0, k, t, r, sigma, kind)?;
+        let node = |i: usize, j: usize| lattice.node(s0, i, j);
+        let [v0, v1, v2] = &lattice.values;
+
+        let delta = (v1[1] - v1[0]) / (node(1, 1) - node(1, 0));
+        let delta_up = (v2[2] - v2[1]) / (node(2, 2) - node(2, 1));
+        let delta_down = (v2[1] - v2[0]) / (node(2, 1) - node(2, 0));
+        let gamma = (delta_up - delta_down) / (0.5 * (node(2, 2) - node(2, 0)));
+        let (h_down, h_up) = (node(2, 1) - node(2, 0), node(2, 2) - node(2, 1));
+        let delta_middle = (delta_down * h_up + delta_up * h_down) / (h_down + h_up);
+        let shift = node(2, 1) - s0;
+        let v_spot = v2[1] - delta_middle * shift - 0.5 * gamma * shift * shift;
+        let theta = (v_spot - v0[0]) / (2.0 * lattice.dt);
+
+        Ok(LatticeGreeks { price: v0[0], delta, gamma, theta })
+    }
+
+    /// Parámetros (n, u, d, p) del árbol.
+    fn parameters(&self, s0: SpotPrice, k: StrikePrice, t: TimeToMaturity, r: InterestRate, sigma: Volatility) -> FinanceResult<(usize, f64, f64, f64)> {
+        match self.model {
+            BinomialModel::CoxRossRubinstein => {
+                let dt = t / self.steps as f64;
+                let u = (sigma * dt.sqrt()).exp();
+                let d = 1.0 / u;
+                Ok((self.steps, u, d, ((r * dt).exp() - d) / (u - d)))
+            }
+            BinomialModel::JarrowRudd => {
+                let dt = t / self.steps as f64;
+                let drift = (r - 0.5 * sigma * sigma) * dt;
+                let diffusion = sigma * dt.sqrt();
+                Ok((self.steps, (drift + diffusion).exp(), (drift - diffusion).exp(), 0.5))
+            }
+            BinomialModel::LeisenReimer => {
+                let n = if self.steps.is_multiple_of(2) { self.steps + 1 } else { self.steps };
+                let dt = t / n as f64;
+                // Centrado en el strike con el spot sin dividendos, S0 - VP(D)
+                let spot = escrowed_spot(s0, &self.dividends, t, r)?;
+                let (d1, d2) = d1_d2(spot, k, t, r, sigma)?;
+                let p = peizer_pratt(d2, n);
+                let growth = (r * dt).exp();
+                let u = growth * peizer_pratt(d1, n) / p;
+                let d = (growth - p * u) / (1.0 - p);
+                Ok((n, u, d, p))
+            }
+        }
+    }
+
+    /// Inducción hacia atrás completa; conserva los valores de los pasos 0, 1 y 2.
+    fn roll_back(&self, s0: SpotPrice, k: StrikePrice, t: TimeToMaturity, r: InterestRate, sigma: Volatility, kind: OptionType) -> FinanceResult<Lattice> {
+        if sigma < EPS_VOL {
+            return Err(FinanceError::InvalidInterestRate);
+        }
+
+        let (n, u, d, p) = self.parameters(s0, k, t, r, sigma)?;
+        if !(p > 0.0 && p < 1.0 && u > d) {
+            return Err(FinanceError::InvalidInput);
+        }
+        let dt = t / n as f64;
+        let disc = (-r * dt).exp();
+        let lattice = Lattice { u, d, dt, values: Default::default() };
+        let node = |i: usize, j: usize| lattice.node(s0, i, j);
+
+        // Dividendos agregados por paso ex-dividendo
+        let mut dividend_at_step = vec![0.0; n + 1];
+        for dividend in self.dividends.iter().filter(|d| d.time > 0.0 && d.time <= t) {
+            let step = ((dividend.time / dt).round() as usize).clamp(1, n);
+            dividend_at_step[step] += dividend.amount;
+        }
+        let exercisable = exercise_steps(&self.exercise, t, n);
+
+        let mut values: Vec<f64> = (0..=n).map(|j| kind.payoff(node(n, j), k)).collect();
+        if dividend_at_step[n] > 0.0 {
+            apply_dividend(&mut values, n, dividend_at_step[n], &node);
+        }
+
+        let mut kept: [Vec<f64>; 3] = Default::default();
+        if n <= 2 {
+            kept[n] = values.clone();
+        }
+        for i in (0..n).rev() {
+            for j in 0..=i {
+                values[j] = disc * (p * values[j + 1] + (1.0 - p) * values[j]);
+            }
+            values.truncate(i + 1);
+            if dividend_at_step[i] > 0.0 {
+                apply_dividend(&mut values, i, dividend_at_step[i], &node);
+            }
+            if exercisable[i] {
+                for (j, value) in values.iter_mut().enumerate() {
+                    *value = value.max(kind.payoff(node(i, j), k));
+                }
+            }
+            if i <= 2 {
+                kept[i] = values.clone();
+            }
+        }
+        Ok(Lattice { values: kept, ..lattice })
+    }
+}
+
+/// Estado del árbol tras la inducción hacia atrás.
+struct Lattice {
+    u: f64,
+    d: f64,
+    dt: f64,
+    values: [Vec<f64>; 3],
+}
+
+impl Lattice {
+    /// Precio del subyacente en el nodo j del paso i: S0 u^j d^{i-j}
+    fn node(&self, s0: SpotPrice, i: usize, j: usize) -> f64 {
+        s0 * self.u.powi(j as i32) * self.d.powi((i - j) as i32)
+    }
+}
+
+/// Inversión de Peizer-Pratt (método 2) usada por Leisen-Reimer.
+fn peizer_pratt(z: f64, n: usize) -> f64 {
+    let n = n as f64;
+    let x = z / (n + 1.0 / 3.0 + 0.1 / (n + 1.0));
+    0.5 + 0.5 * z.signum() * (1.0 - (-x * x * (n + 1.0 / 6.0)).exp()).sqrt()
+}
+
+/// Validación de las fechas de ejercicio de una bermuda.
+pub(crate) fn validate_exercise(exercise: &ExerciseStyle) -> FinanceResult<()> {
+    match exercise {
+        ExerciseStyle::Bermudan(dates) if dates.iter().any(|d| !d.is_finite() || *d < 0.0) => {
+            Err(FinanceError::InvalidPeriods)
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Pasos de un retículo de `n` pasos sobre [0, T] en los que se permite ejercer.
+///
+/// Las fechas bermudas se asignan al paso más cercano; el vencimiento no se
+/// marca porque allí el valor ya es el payoff.
+pub(crate) fn exercise_steps(exercise: &ExerciseStyle, t: TimeToMaturity, n: usize) -> Vec<bool> {
+    match exercise {
+        ExerciseStyle::European => vec![false; n + 1],
+        ExerciseStyle::American => vec![true; n + 1],
+        ExerciseStyle::Bermudan(dates) => {
+            let mut steps = vec![false; n + 1];
+            for date in dates.iter().filter(|d| **d <= t) {
+                steps[((date / t * n as f64).round() as usize).min(n)] = true;
+            }
+            steps
+        }
+    }
+}
+
+/// Sustituye V(S_j) por V(S_j - D) interpolando sobre los nodos del paso `i`;
+/// si S_j - D cae por debajo del nodo inferior se toma el valor de ese nodo.
+fn apply_dividend<F: Fn(usize, usize) -> f64>(values: &mut [f64], i: usize, amount: f64, node: &F) {
+    let ex_values = values.to_vec();
+    for (j, value) in values.iter_mut().enumerate() {
+        // Fuera de [S_0, S_i] no se extrapola: una call podría salir negativa
+        let target = (node(i, j) - amount).clamp(node(i, 0), node(i, i));
+        // Índice del tramo [S_m, S_{m+1}] que contiene al objetivo
+        let m = (0..i).rev().find(|&m| node(i, m) <= target).unwrap_or(0);
+        let (lo, hi) = (node(i, m), node(i, m + 1));
+        let w = (target - lo) / (hi - lo);
+        *value = ex_values[m] + w * (ex_values[m + 1] - ex_values[m]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::options::{call_price, put_price, option_greeks};
+
+    const MODELS: [BinomialModel; 3] = [
+        BinomialModel::CoxRossRubinstein,
+        BinomialModel::JarrowRudd,
+        BinomialModel::LeisenReimer,
+    ];
+
+    #[test]
+    fn converges_to_black_scholes() {
+        let (s0, k, t, r, sigma) = (100.0, 105.0, 1.0, 0.05, 0.2);
+        let bs_call = call_price(s0, k, t, r, sigma).unwrap();
+        let bs_put = put_price(s0, k, t, r, sigma).unwrap();
+        for model in MODELS {
+            let tree = BinomialTree::new(1000).unwrap().with_model(model);
+            let c = tree.price(s0, k, t, r, sigma, OptionType::Call).unwrap();
+            let p = tree.price(s0, k, t, r, sigma, OptionType::Put).unwrap();
+            assert!((c - bs_call).abs() < 0.01, "{model:?}");
+            assert!((p - bs_put).abs() < 0.01, "{model:?}");
+        }
+    }
+
+    #[test]
+    fn leisen_reimer_converges_fast() {
+        let (s0, k, t, r, sigma) = (100.0, 95.0, 0.5, 0.04, 0.3);
+        let tree = BinomialTree::new(101).unwrap().with_model(BinomialModel::LeisenReimer);
+        let c = tree.price(s0, k, t, r, sigma, OptionType::Call).unwrap();
+        assert!((c - call_price(s0, k, t, r, sigma).unwrap()).abs() < 1e-3);
+        // Un número par de pasos se redondea al impar siguiente
+        let even = BinomialTree::new(100).unwrap().with_model(BinomialModel::LeisenReimer);
+        assert_eq!(even.price(s0, k, t, r, sigma, OptionType::Call).unwrap(), c);
+    }
+
+    #[test]
+    fn american_put_reference_value() {
+        // S=K=50, T=5/12, r=10%, σ=40% (Hull); valor convergido ≈ 4.2842
+        for model in MODELS {
+            let tree = BinomialTree::new(2001).unwrap().with_model(model).with_exercise(ExerciseStyle::American).unwrap();
+            let p = tree.price(50.0, 50.0, 5.0 / 12.0, 0.10, 0.40, OptionType::Put).unwrap();
+            assert!((p - 4.2842).abs() < 2e-3, "{model:?}: {p}");
+        }
+    }
+
+    #[test]
+    fn american_call_without_dividends_is_european() {
+        let (s0, k, t, r, sigma) = (100.0, 100.0, 1.0, 0.05, 0.25);
+        let european = BinomialTree::new(500).unwrap();
+        let american = european.clone().with_exercise(ExerciseStyle::American).unwrap();
+        let c_eu = european.price(s0, k, t, r, sigma, OptionType::Call).unwrap();
+        let c_am = american.price(s0, k, t, r, sigma, OptionType::Call).unwrap();
+        assert!((c_am - c_eu).abs() < 1e-10);
+    }
+
+    #[test]
+    fn american_call_with_dividend_has_early_exercise_premium() {
+        let divs = vec![CashDividend::new(8.0, 0.5).unwrap()];
+        let european = BinomialTree::new(500).unwrap().with_dividends(divs).unwrap();
+        let american = european.clone().with_exercise(ExerciseStyle::American).unwrap();
+        let c_eu = european.price(110.0, 100.0, 1.0, 0.05, 0.2, OptionType::Call).unwrap();
+        let c_am = american.price(110.0, 100.0, 1.0, 0.05, 0.2, OptionType::Call).unwrap();
+        assert!(c_am > c_eu + 0.1);
+    }
+
+    #[test]
+    fn bermudan_lies_between_european_and_american() {
+        let (s0, k, t, r, sigma) = (100.0, 110.0, 1.0, 0.06, 0.2);
+        let base = BinomialTree::new(600).unwrap();
+        let bermudan = base.clone().with_exercise(ExerciseStyle::Bermudan(vec![0.25, 0.5, 0.75])).unwrap();
+        let american = base.clone().with_exercise(ExerciseStyle::American).unwrap();
+        let p_eu = base.price(s0, k, t, r, sigma, OptionType::Put).unwrap();
+        let p_be = bermudan.price(s0, k, t, r, sigma, OptionType::Put).unwrap();
+        let p_am = american.price(s0, k, t, r, sigma, OptionType::Put).unwrap();
+        assert!(p_eu < p_be && p_be < p_am);
+    }
+
+    #[test]
+    fn tree_greeks_match_black_scholes() {
+        let (s0, k, t, r, sigma) = (100.0, 100.0, 1.0, 0.05, 0.2);
+        for kind in [OptionType::Call, OptionType::Put] {
+            let bs = option_greeks(s0, k, t, r, sigma, kind).unwrap();
+            let g = BinomialTree::new(1000).unwrap().greeks(s0, k, t, r, sigma, kind).unwrap();
+            assert!((g.delta - bs.delta).abs() < 2e-3);
+            assert!((g.gamma - bs.gamma).abs() < 2e-4);
+            assert!((g.theta - bs.theta).abs() < 2e-2);
+        }
+    }
+
+    #[test]
+    fn tree_theta_without_recombining_at_spot() {
+        // En JR y LR el nodo central del paso 2 no está en S₀
+        let (s0, k, t, r, sigma) = (100.0, 100.0, 1.0, 0.05, 0.3);
+        for model in [BinomialModel::JarrowRudd, BinomialModel::LeisenReimer] {
+            let tree = BinomialTree::new(501).unwrap().with_model(model);
+            for kind in [OptionType::Call, OptionType::Put] {
+                let bs = option_greeks(s0, k, t, r, sigma, kind).unwrap();
+                let g = tree.greeks(s0, k, t, r, sigma, kind).unwrap();
+                assert!((g.theta - bs.theta).abs() < 2e-2);
+            }
+        }
+    }
+
+    #[test]
+    fn discrete_dividend_put_call_parity() {
+        let (s0, k, t, r, sigma) = (100.0, 100.0, 1.0, 0.05, 0.3);
+        let divs = vec![
+            CashDividend::new(3.0, 0.3).unwrap(),
+            CashDividend::new(3.0, 0.8).unwrap(),
+        ];
+        let tree = BinomialTree::new(400).unwrap().with_dividends(divs).unwrap();
+        let c = tree.price(s0, k, t, r, sigma, OptionType::Call).unwrap();
+        let p = tree.price(s0, k, t, r, sigma, OptionType::Put).unwrap();
+        let pv = 3.0 * (-r * 0.3f64).exp() + 3.0 * (-r * 0.8f64).exp();
+        assert!((c - p - (s0 - pv - k * (-r * t).exp())).abs() < 1e-8);
+    }
+
+    #[test]
+    fn leisen_reimer_with_dividends_matches_crr() {
+        let (s0, k, t, r, sigma) = (100.0, 100.0, 1.0, 0.05, 0.3);
+        let divs = vec![
+            CashDividend::new(15.0, 0.25).unwrap(),
+            CashDividend::new(15.0, 0.75).unwrap(),
+        ];
+        // El tratamiento de los dividendos es de primer orden en Δt en ambos árboles
+        let crr = BinomialTree::new(4001).unwrap().with_dividends(divs.clone()).unwrap();
+        let lr = BinomialTree::new(2001).unwrap().with_model(BinomialModel::LeisenReimer).with_dividends(divs).unwrap();
+        for kind in [OptionType::Call, OptionType::Put] {
+            let reference = crr.price(s0, k, t, r, sigma, kind).unwrap();
+            let price = lr.price(s0, k, t, r, sigma, kind).unwrap();
+            assert!((price - reference).abs() < 2e-3, "{kind:?}: {price} vs {reference}");
+        }
+    }
+
+    #[test]
+    fn dividend_lowers_call_and_raises_put() {
+        let plain = BinomialTree::new(300).unwrap();
+        let with_div = BinomialTree::new(300).unwrap()
+            .with_dividends(vec![CashDividend::new(5.0, 0.5).unwrap()]).unwrap();
+        let args = (100.0, 100.0, 1.0, 0.03, 0.25);
+        let c0 = plain.price(args.0, args.1, args.2, args.3, args.4, OptionType::Call).unwrap();
+        let c1 = with_div.price(args.0, args.1, args.2, args.3, args.4, OptionType::Call).unwrap();
+        let p0 = plain.price(args.0, args.1, args.2, args.3, args.4, OptionType::Put).unwrap();
+        let p1 = with_div.price(args.0, args.1, args.2, args.3, args.4, OptionType::Put).unwrap();
+        assert!(c1 < c0);
+        assert!(p1 > p0);
+    }
+
+    #[test]
+    fn dividend_below_lowest_node_does_not_extrapolate() {
+        // Con 4 pasos el nodo inferior del paso 2 es 100 e^{-0.3} ≈ 74.1 < 80
+        let tree = BinomialTree::new(4).unwrap()
+            .with_dividends(vec![CashDividend::new(80.0, 0.5).unwrap()]).unwrap();
+        let lattice = tree.roll_back(100.0, 20.0, 1.0, 0.05, 0.3, OptionType::Call).unwrap();
+        assert!(lattice.values.iter().flatten().all(|v| *v >= 0.0), "{:?}", lattice.values);
+        let c = tree.price(100.0, 20.0, 1.0, 0.05, 0.3, OptionType::Call).unwrap();
+        assert!(c >= 0.0);
+    }
+
+    #[test]
+    fn invalid_configuration() {
+        assert_eq!(BinomialTree::new(0).unwrap_err(), FinanceError::InvalidPeriods);
+        let tree = BinomialTree::new(10).unwrap();
+        assert_eq!(tree.price(100.0, 100.0, 1.0, 0.05, 0.0, OptionType::Call).unwrap_err(), FinanceError::InvalidInterestRate);
+        // r Δt > σ √Δt: probabilidad fuera de (0, 1)
+        assert_eq!(tree.price(100.0, 100.0, 1.0, 0.5, 0.01, OptionType::Call).unwrap_err(), FinanceError::InvalidInput);
+        let err = tree.clone().with_exercise(ExerciseStyle::Bermudan(vec![-0.5])).unwrap_err();
+        assert_eq!(err, FinanceError::InvalidPeriods);
+        let one_step = BinomialTree::new(1).unwrap();
+        assert_eq!(one_step.greeks(100.0, 100.0, 1.0, 0.05, 0.2, OptionType::Call).unwrap_err(), FinanceError::InvalidPeriods);
+    }
+}
@@ -0,0 +1,126 @@
+//! Modelo de Black (1976) para opciones sobre futuros y forwards.
+//!
+//! Fórmulas implementadas, con df = e^{-rT}:
+//! C = df [F N(d1) - K N(d2)]
+//! P = df [K N(-d2) - F N(-d1)]
+//! d1 = [ln(F/K) + σ^2 T / 2] / (σ √T)
+//! d2 = d1 - σ √T
+//!
+//! Equivale a Black-Scholes-Merton con S0 = F y rendimiento q = r, por lo que
+//! precio, griegas y volatilidad implícita reutilizan esas rutinas. La única
+//! diferencia es rho, que aquí se calcula con el forward fijo: ρ = -T V.
+
+use crate::common::{
+    FinanceResult,
+    ForwardPrice, StrikePrice, TimeToMaturity, InterestRate, Volatility,
+    OptionType
+};
+use super::black_scholes::{
+    Greeks,
+    d1_d2_dividend, option_price_dividend, option_greeks_dividend, implied_volatility_dividend
+};
+
+/// Calcula (d1, d2) de Black-76.
+pub fn black76_d1_d2(f: ForwardPrice, k: StrikePrice, t: TimeToMaturity, r: InterestRate, sigma: Volatility) -> FinanceResult<(f64, f64)> {
+    d1_d2_dividend(f, k, t, r, r, sigma)
+}
+
+/// Precio de call europea sobre un forward/futuro.
+pub fn black76_call_price(f: ForwardPrice, k: StrikePrice, t: TimeToMaturity, r: InterestRate, sigma: Volatility) -> FinanceResult<f64> {
+    black76_price(f, k, t, r, sigma, OptionType::Call)
+}
+
+/// Precio de put europea sobre un forward/futuro.
+pub fn black76_put_price(f: ForwardPrice, k: StrikePrice, t: TimeToMaturity, r: InterestRate, sigma: Volatility) -> FinanceResult<f64> {
+    black76_price(f, k, t, r, sigma, OptionType::Put)
+}
+
+/// Precio genérico de Black-76 según OptionType.
+pub fn black76_price(f: ForwardPrice, k: StrikePrice, t: TimeToMaturity, r: InterestRate, sigma: Volatility, kind: OptionType) -> FinanceResult<f64> {
+    option_price_dividend(f, k, t, r, r, sigma, kind)
+}
+
+/// Griegas de Black-76; `delta`, `gamma`, `vanna` y `speed` respecto al forward.
+pub fn black76_greeks(f: ForwardPrice, k: StrikePrice, t: TimeToMaturity, r: InterestRate, sigma: Volatility, kind: OptionType) -> FinanceResult<Greeks> {
+    let greeks = option_greeks_dividend(f, k, t, r, r, sigma, kind)?;
+    let price = black76_price(f, k, t, r, sigma, kind)?;
+    Ok(Greeks { rho: -t * price, ..greeks })
+}
+
+/// Volatilidad implícita de Black-76.
+///
+/// # Errores
+/// * `ArbitrageViolation` - Si el precio está fuera de [df·max(±(F-K), 0), df·F] (call)
+///   o [df·max(±(K-F), 0), df·K] (put)
+pub fn black76_implied_volatility(market_price: f64, f: ForwardPrice, k: StrikePrice, t: TimeToMaturity, r: InterestRate, kind: OptionType) -> FinanceResult<Volatility> {
+    implied_volatility_dividend(market_price, f, k, t, r, r, kind)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::FinanceError;
+
+    #[test]
+    fn reference_value() {
+        // Hull, ej. 18.6: F=20, K=20, T=4/12, r=9%, σ=25% => P ≈ 1.12
+        let p = black76_put_price(20.0, 20.0, 4.0 / 12.0, 0.09, 0.25).unwrap();
+        assert!((p - 1.1166).abs() < 1e-3);
+    }
+
+    #[test]
+    fn put_call_parity() {
+        let (f, k, t, r, sigma) = (105.0, 100.0, 0.5, 0.04, 0.3);
+        let c = black76_call_price(f, k, t, r, sigma).unwrap();
+        let p = black76_put_price(f, k, t, r, sigma).unwrap();
+        assert!((c - p - (-r * t).exp() * (f - k)).abs() < 1e-10);
+    }
+
+    #[test]
+    fn d1_d2_has_no_drift() {
+        let (d1, d2) = black76_d1_d2(100.0, 100.0, 1.0, 0.05, 0.2).unwrap();
+        assert!((d1 - 0.1).abs() < 1e-12);
+        assert!((d2 + 0.1).abs() < 1e-12);
+    }
+
+    #[test]
+    fn greeks_match_finite_differences() {
+        let (f, k, t, r, sigma) = (98.0, 100.0, 0.75, 0.03, 0.22);
+        for kind in [OptionType::Call, OptionType::Put] {
+            let g = black76_greeks(f, k, t, r, sigma, kind).unwrap();
+            let price = |f: f64, t: f64, r: f64| black76_price(f, k, t, r, sigma, kind).unwrap();
+
+            let h = 0.01;
+            let fd_delta = (price(f + h, t, r) - price(f - h, t, r)) / (2.0 * h);
+            assert!((g.delta - fd_delta).abs() < 1e-4);
+
+            let hr = 1e-4;
+            let fd_rho = (price(f, t, r + hr) - price(f, t, r - hr)) / (2.0 * hr);
+            assert!((g.rho - fd_rho).abs() < 1e-3);
+
+            let fd_theta = -(price(f, t + hr, r) - price(f, t - hr, r)) / (2.0 * hr);
+            assert!((g.theta - fd_theta).abs() < 1e-2);
+        }
+    }
+
+    #[test]
+    fn implied_volatility_round_trip() {
+        for &k in &[80.0, 100.0, 120.0] {
+            for kind in [OptionType::Call, OptionType::Put] {
+                let price = black76_price(100.0, k, 2.0, 0.05, 0.35, kind).unwrap();
+                let iv = black76_implied_volatility(price, 100.0, k, 2.0, 0.05, kind).unwrap();
+                assert!((iv - 0.35).abs() < 1e-6);
+            }
+        }
+        let err = black76_implied_volatility(100.0, 100.0, 100.0, 1.0, 0.05, OptionType::Call).unwrap_err();
+        assert_eq!(err, FinanceError::ArbitrageViolation);
+    }
+
+    #[test]
+    fn validates_inputs() {
+        let err = black76_call_price(-1.0, 100.0, 1.0, 0.05, 0.2).unwrap_err();
+        assert_eq!(err, FinanceError::InvalidMonetaryValue);
+        let err = black76_call_price(100.0, 100.0, -1.0, 0.05, 0.2).unwrap_err();
+        assert_eq!(err, FinanceError::InvalidPeriods);
+    }
+}
@@ -1,46 +1,196 @@
-//! Modelo Black-Scholes-Merton para opciones europeas (sin dividendos).
+//! Modelo Black-Scholes-Merton para opciones europeas.
 //!
-//! Fórmulas implementadas
+//! Fórmulas implementadas (sin dividendos)
 //! C = S0 N(d1) - K e^{-rT} N(d2)
 //! P = K e^{-rT} N(-d2) - S0 N(-d1)
 //! d1 = [ln(S0/K) + (r + σ^2/2) T] / (σ √T)
 //! d2 = d1 - σ √T
+//!
+//! Variante de Merton (1973) con rendimiento por dividendo continuo q
+//! C = S0 e^{-qT} N(d1) - K e^{-rT} N(d2)
+//! P = K e^{-rT} N(-d2) - S0 e^{-qT} N(-d1)
+//! d1 = [ln(S0/K) + (r - q + σ^2/2) T] / (σ √T)
 
 use std::f64::consts::PI;
 use crate::common::{
     FinanceError, FinanceResult,
     SpotPrice, StrikePrice, TimeToMaturity, InterestRate, Volatility,
-    OptionType
+    DividendYield, OptionType
 };
+use crate::num::newton_safeguarded;
+
+pub(crate) const EPS_TIME: f64 = 1e-12;
+pub(crate) const EPS_VOL: f64 = 1e-12;
 
-const EPS_TIME: f64 = 1e-12;
-const EPS_VOL: f64 = 1e-12;
+/// Volatilidad máxima explorada al acotar la volatilidad implícita.
+const MAX_IMPLIED_VOL: f64 = 100.0;
+/// Tolerancia (en unidades de volatilidad) del solver de volatilidad implícita.
+const IMPLIED_VOL_TOL: f64 = 1e-12;
+const IMPLIED_VOL_MAX_ITER: usize = 200;
 
 /// PDF de la normal estándar φ(x)
 #[inline]
-fn normal_pdf(x: f64) -> f64 {
+pub(crate) fn normal_pdf(x: f64) -> f64 {
     (-0.5 * x * x).exp() / (2.0 * PI).sqrt()
 }
 
-/// CDF de la normal estándar N(x) (aprox. Abramowitz & Stegun 7.1.26).
+/// CDF de la normal estándar N(x) en doble precisión (West, 2005).
+///
+/// Algoritmo 5666 de Hart: N(-|x|) = e^{-x²/2} P(|x|)/Q(|x|) con P y Q
+/// polinomios de grado 6 y 7 para |x| < 5√2 y una fracción continua en la
+/// cola. El error absoluto es del orden de 1e-16.
 #[inline]
-fn normal_cdf(x: f64) -> f64 {
-    let b1 = 0.319381530;
-    let b2 = -0.356563782;
-    let b3 = 1.781477937;
-    let b4 = -1.821255978;
-    let b5 = 1.330274429;
-    let p = 0.2316419;
+pub(crate) fn normal_cdf(x: f64) -> f64 {
+    const P: [f64; 7] = [3.52624965998911e-2, 0.700383064443688, 6.37396220353165, 33.912866078383, 112.079291497871, 221.213596169931, 220.206867912376];
+    const Q: [f64; 8] = [8.83883476483184e-2, 1.75566716318264, 16.064177579207, 86.7807322029461, 296.564248779674, 637.333633378831, 793.826512519948, 440.413735824752];
+
+    if x.is_nan() {
+        return f64::NAN;
+    }
+    let z = x.abs();
+    let tail = if z > 37.0 {
+        0.0
+    } else if z < 7.07106781186547 {
+        let p = P.iter().fold(0.0, |acc, c| acc * z + c);
+        let q = Q.iter().fold(0.0, |acc, c| acc * z + c);
+        (-0.5 * z * z).exp() * p / q
+    } else {
+        let fraction = z + 1.0 / (z + 2.0 / (z + 3.0 / (z + 4.0 / (z + 0.65))));
+        (-0.5 * z * z).exp() / fraction / (2.0 * PI).sqrt()
+    };
+    if x > 0.0 { 1.0 - tail } else { tail }
+}
+
+/// Inversa de la CDF normal N^{-1}(p) (aprox. racional de Acklam + un paso de Halley).
+///
+/// El paso de Halley se hace contra `normal_cdf` y lleva el error relativo
+/// de la aproximación de Acklam (~1e-9) a la precisión de la máquina.
+pub(crate) fn normal_inv_cdf(p: f64) -> f64 {
+    const A: [f64; 6] = [-3.969683028665376e+01, 2.209460984245205e+02, -2.759285104469687e+02, 1.38357751867269e+02, -3.066479806614716e+01, 2.506628277459239e+00];
+    const B: [f64; 5] = [-5.447609879822406e+01, 1.615858368580409e+02, -1.556989798598866e+02, 6.680131188771972e+01, -1.328068155288572e+01];
+    const C: [f64; 6] = [-7.784894002430293e-03, -3.223964580411365e-01, -2.400758277161838e+00, -2.549671010229297e+00, 4.374664141464968e+00, 2.938163982698783e+00];
+    const D: [f64; 4] = [7.784695709041462e-03, 3.224671290700398e-01, 2.445134137142996e+00, 3.754408661907416e+00];
+    const P_LOW: f64 = 0.02425;
+
+    if p.is_nan() {
+        return f64::NAN;
+    }
+    if p <= 0.0 {
+        return f64::NEG_INFINITY;
+    }
+    if p >= 1.0 {
+        return f64::INFINITY;
+    }
 
-    let t = 1.0 / (1.0 + p * x.abs());
-    let poly = ((((b5 * t + b4) * t + b3) * t + b2) * t + b1) * t;
-    let approx = 1.0 - normal_pdf(x.abs()) * poly;
-    if x >= 0.0 { approx } else { 1.0 - approx }
+    let tail = |q: f64| (((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5])
+        / ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.0);
+    let x = if p < P_LOW {
+        tail((-2.0 * p.ln()).sqrt())
+    } else if p <= 1.0 - P_LOW {
+        let q = p - 0.5;
+        let r = q * q;
+        (((((A[0] * r + A[1]) * r + A[2]) * r + A[3]) * r + A[4]) * r + A[5]) * q
+            / (((((B[0] * r + B[1]) * r + B[2]) * r + B[3]) * r + B[4]) * r + 1.0)
+    } else {
+        -tail((-2.0 * (1.0 - p).ln()).sqrt())
+    };
+
+    let e = normal_cdf(x) - p;
+    let u = e / normal_pdf(x);
+    x - u / (1.0 + 0.5 * x * u)
+}
+
+/// CDF de la normal bivariante estándar M(a, b; ρ) = P(X ≤ a, Y ≤ b), corr(X, Y) = ρ.
+///
+/// Algoritmo de Drezner-Wesolowsky (1990) en la versión de Genz (2004):
+/// cuadratura de Gauss-Legendre de 6, 12 o 20 puntos según |ρ| y un
+/// desarrollo asintótico para |ρ| ≥ 0.925. Con la N(x) univariante en doble
+/// precisión el error total es del orden de 1e-15.
+pub fn bivariate_normal_cdf(a: f64, b: f64, rho: f64) -> f64 {
+    const W: [&[f64]; 3] = [
+        &[0.1713244923791705, 0.3607615730481384, 0.4679139345726904],
+        &[0.04717533638651177, 0.1069393259953183, 0.1600783285433464, 0.2031674267230659, 0.2334925365383547, 0.2491470458134029],
+        &[0.01761400713915212, 0.04060142980038694, 0.06267204833410906, 0.08327674157670475, 0.1019301198172404, 0.1181945319615184, 0.1316886384491766, 0.1420961093183821, 0.1491729864726037, 0.1527533871307259],
+    ];
+    const X: [&[f64]; 3] = [
+        &[-0.9324695142031522, -0.6612093864662647, -0.238619186083197],
+        &[-0.9815606342467191, -0.904117256370475, -0.769902674194305, -0.5873179542866171, -0.3678314989981802, -0.1252334085114692],
+        &[-0.9931285991850949, -0.9639719272779138, -0.912234428251326, -0.8391169718222188, -0.7463319064601508, -0.636053680726515, -0.5108670019508271, -0.3737060887154196, -0.2277858511416451, -0.07652652113349733],
+    ];
+
+    // Genz trabaja con la cola superior P(X > h, Y > k)
+    let h = -a;
+    let mut k = -b;
+    let r = rho.clamp(-1.0, 1.0);
+    if h == f64::INFINITY || k == f64::INFINITY {
+        return 0.0;
+    }
+    if h == f64::NEG_INFINITY {
+        return if k == f64::NEG_INFINITY { 1.0 } else { normal_cdf(-k) };
+    }
+    if k == f64::NEG_INFINITY {
+        return normal_cdf(-h);
+    }
+
+    let ng = if r.abs() < 0.3 { 0 } else if r.abs() < 0.75 { 1 } else { 2 };
+    let (w, x) = (W[ng], X[ng]);
+    let mut hk = h * k;
+    let mut bvn = 0.0;
+
+    if r.abs() < 0.925 {
+        let hs = 0.5 * (h * h + k * k);
+        let asr = r.asin();
+        for (wi, xi) in w.iter().zip(x) {
+            for sign in [1.0, -1.0] {
+                let sn = (0.5 * asr * (1.0 + sign * xi)).sin();
+                bvn += wi * ((sn * hk - hs) / (1.0 - sn * sn)).exp();
+            }
+        }
+        bvn = bvn * asr / (4.0 * PI) + normal_cdf(-h) * normal_cdf(-k);
+    } else {
+        if r < 0.0 {
+            k = -k;
+            hk = -hk;
+        }
+        if r.abs() < 1.0 {
+            let a_s = (1.0 - r) * (1.0 + r);
+            let mut a = a_s.sqrt();
+            let bs = (h - k) * (h - k);
+            let c = (4.0 - hk) / 8.0;
+            let d = (12.0 - hk) / 16.0;
+            bvn = a * (-0.5 * (bs / a_s + hk)).exp()
+                * (1.0 - c * (bs - a_s) * (1.0 - d * bs / 5.0) / 3.0 + c * d * a_s * a_s / 5.0);
+            if hk > -160.0 {
+                let b = bs.sqrt();
+                bvn -= (-0.5 * hk).exp() * (2.0 * PI).sqrt() * normal_cdf(-b / a) * b
+                    * (1.0 - c * bs * (1.0 - d * bs / 5.0) / 3.0);
+            }
+            a *= 0.5;
+            for (wi, xi) in w.iter().zip(x) {
+                for sign in [1.0, -1.0] {
+                    let xs = (a * (sign * xi + 1.0)).powi(2);
+                    let rs = (1.0 - xs).sqrt();
+                    bvn += a * wi * ((-bs / (2.0 * xs) - hk / (1.0 + rs)).exp() / rs
+                        - (-0.5 * (bs / xs + hk)).exp() * (1.0 + c * xs * (1.0 + d * xs)));
+                }
+            }
+            bvn = -bvn / (2.0 * PI);
+        }
+        if r > 0.0 {
+            bvn += normal_cdf(-h.max(k));
+        } else {
+            bvn = -bvn;
+            if k > h {
+                bvn += if h < 0.0 { normal_cdf(k) - normal_cdf(h) } else { normal_cdf(-h) - normal_cdf(-k) };
+            }
+        }
+    }
+    bvn.clamp(0.0, 1.0)
 }
 
 /// Validación básica de entradas con errores ya existentes.
 #[inline]
-fn validate_inputs(s0: SpotPrice, k: StrikePrice, t: TimeToMaturity, r: InterestRate, sigma: Volatility) -> FinanceResult<()> {
+pub(crate) fn validate_inputs(s0: SpotPrice, k: StrikePrice, t: TimeToMaturity, r: InterestRate, sigma: Volatility) -> FinanceResult<()> {
     if !s0.is_finite() || !k.is_finite() || !t.is_finite() || !r.is_finite() || !sigma.is_finite() {
         return Err(FinanceError::InvalidMonetaryValue);
     }
@@ -58,7 +208,15 @@ fn validate_inputs(s0: SpotPrice, k: StrikePrice, t: TimeToMaturity, r: Interest
 
 /// Calcula (d1, d2) de Black_Scholes.
 pub fn d1_d2(s0: SpotPrice, k: StrikePrice, t: TimeToMaturity, r: InterestRate, sigma: Volatility) -> FinanceResult<(f64, f64)> {
+    d1_d2_dividend(s0, k, t, r, 0.0, sigma)
+}
+
+/// Calcula (d1, d2) de Black-Scholes-Merton con rendimiento por dividendo continuo q.
+pub fn d1_d2_dividend(s0: SpotPrice, k: StrikePrice, t: TimeToMaturity, r: InterestRate, q: DividendYield, sigma: Volatility) -> FinanceResult<(f64, f64)> {
     validate_inputs(s0, k, t, r, sigma)?;
+    if !q.is_finite() {
+        return Err(FinanceError::InvalidInterestRate);
+    }
 
     if t < EPS_TIME {
         let sign = (s0 - k).signum();
@@ -66,7 +224,7 @@ pub fn d1_d2(s0: SpotPrice, k: StrikePrice, t: TimeToMaturity, r: InterestRate,
         return Ok((v, v));
     }
     if sigma < EPS_VOL {
-        let num = (s0 / k).ln() + r * t;
+        let num = (s0 / k).ln() + (r - q) * t;
         let v = if num > 0.0 { f64::INFINITY } else if num < 0.0 { f64::NEG_INFINITY } else { 0.0 };
         return Ok((v, v));
     }
@@ -74,43 +232,231 @@ pub fn d1_d2(s0: SpotPrice, k: StrikePrice, t: TimeToMaturity, r: InterestRate,
     let ln_sk = (s0 / k).ln();
     let sqrt_t = t.sqrt();
     let sigma_sqrt_t = sigma * sqrt_t;
-    let d1 = (ln_sk + (r + 0.5 * sigma * sigma) * t) / sigma_sqrt_t;
+    let d1 = (ln_sk + (r - q + 0.5 * sigma * sigma) * t) / sigma_sqrt_t;
     let d2 = d1 - sigma_sqrt_t;
     Ok((d1, d2))
 }
 
 /// Precio de call europeo (sin dividendos).
 pub fn call_price(s0: SpotPrice, k: StrikePrice, t: TimeToMaturity, r: InterestRate, sigma: Volatility) -> FinanceResult<f64> {
-    validate_inputs(s0, k, t, r, sigma)?;
+    call_price_dividend(s0, k, t, r, 0.0, sigma)
+}
+
+/// Precio de put europeo (sin dividendos).
+pub fn put_price(s0: SpotPrice, k: StrikePrice, t: TimeToMaturity, r: InterestRate, sigma: Volatility) -> FinanceResult<f64> {
+    put_price_dividend(s0, k, t, r, 0.0, sigma)
+}
+
+/// Precio genérico según OptionType.
+pub fn option_price(s0: SpotPrice, k: StrikePrice, t: TimeToMaturity, r: InterestRate, sigma: Volatility, kind: OptionType) -> FinanceResult<f64> {
+    match kind {
+        OptionType::Call => call_price(s0, k, t, r, sigma),
+        OptionType::Put  => put_price(s0, k, t, r, sigma),
+    }
+}
+
+/// Precio de call europeo con rendimiento por dividendo continuo q (Merton).
+pub fn call_price_dividend(s0: SpotPrice, k: StrikePrice, t: TimeToMaturity, r: InterestRate, q: DividendYield, sigma: Volatility) -> FinanceResult<f64> {
+    let (d1, d2) = d1_d2_dividend(s0, k, t, r, q, sigma)?;
 
     if t < EPS_TIME {
         return Ok((s0 - k).max(0.0));
     }
-    let (d1, d2) = d1_d2(s0, k, t, r, sigma)?;
     let df = (-r * t).exp();
-    Ok(s0 * normal_cdf(d1) - k * df * normal_cdf(d2))
+    let dq = (-q * t).exp();
+    Ok(s0 * dq * normal_cdf(d1) - k * df * normal_cdf(d2))
 }
 
-/// Precio de put europeo (sin dividendos).
-pub fn put_price(s0: SpotPrice, k: StrikePrice, t: TimeToMaturity, r: InterestRate, sigma: Volatility) -> FinanceResult<f64> {
-    validate_inputs(s0, k, t, r, sigma)?;
+/// Precio de put europeo con rendimiento por dividendo continuo q (Merton).
+pub fn put_price_dividend(s0: SpotPrice, k: StrikePrice, t: TimeToMaturity, r: InterestRate, q: DividendYield, sigma: Volatility) -> FinanceResult<f64> {
+    let (d1, d2) = d1_d2_dividend(s0, k, t, r, q, sigma)?;
 
     if t < EPS_TIME {
         return Ok((k - s0).max(0.0));
     }
-    let (d1, d2) = d1_d2(s0, k, t, r, sigma)?;
     let df = (-r * t).exp();
-    Ok(k * df * normal_cdf(-d2) - s0 * normal_cdf(-d1))
+    let dq = (-q * t).exp();
+    Ok(k * df * normal_cdf(-d2) - s0 * dq * normal_cdf(-d1))
 }
 
-/// Precio genérico según OptionType.
-pub fn option_price(s0: SpotPrice, k: StrikePrice, t: TimeToMaturity, r: InterestRate, sigma: Volatility, kind: OptionType) -> FinanceResult<f64> {
+/// Precio genérico según OptionType con rendimiento por dividendo continuo q.
+pub fn option_price_dividend(s0: SpotPrice, k: StrikePrice, t: TimeToMaturity, r: InterestRate, q: DividendYield, sigma: Volatility, kind: OptionType) -> FinanceResult<f64> {
     match kind {
-        OptionType::Call => call_price(s0, k, t, r, sigma),
-        OptionType::Put  => put_price(s0, k, t, r, sigma),
+        OptionType::Call => call_price_dividend(s0, k, t, r, q, sigma),
+        OptionType::Put  => put_price_dividend(s0, k, t, r, q, sigma),
     }
 }
 
+/// Sensibilidades analíticas (griegas) de una opción europea.
+///
+/// Convenciones: `vega`, `vanna` y `volga` por unidad de volatilidad (no por 1%),
+/// `rho` por unidad de tipo, y `theta`/`charm` por año de calendario (−∂/∂T).
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Greeks {
+    /// ∂V/∂S
+    pub delta: f64,
+    /// ∂²V/∂S²
+    pub gamma: f64,
+    /// ∂V/∂σ
+    pub vega: f64,
+    /// −∂V/∂T
+    pub theta: f64,
+    /// ∂V/∂r
+    pub rho: f64,
+    /// ∂²V/∂S∂σ
+    pub vanna: f64,
+    /// ∂²V/∂σ² (vomma)
+    pub volga: f64,
+    /// −∂Δ/∂T (delta decay)
+    pub charm: f64,
+    /// ∂³V/∂S³
+    pub speed: f64,
+}
+
+/// Griegas analíticas de Black-Scholes (sin dividendos) según OptionType.
+///
+/// Equivale a `option_greeks_dividend` con q = 0.
+pub fn option_greeks(s0: SpotPrice, k: StrikePrice, t: TimeToMaturity, r: InterestRate, sigma: Volatility, kind: OptionType) -> FinanceResult<Greeks> {
+    option_greeks_dividend(s0, k, t, r, 0.0, sigma, kind)
+}
+
+/// Griegas analíticas de Black-Scholes-Merton con dividendo continuo q.
+///
+/// Fórmulas, con df = e^{-rT} y dq = e^{-qT}:
+/// Δ_c = dq N(d1), Δ_p = -dq N(-d1)
+/// Γ = dq φ(d1) / (S0 σ √T), Vega = S0 dq φ(d1) √T
+/// Θ_c = -S0 dq φ(d1) σ / (2√T) - r K df N(d2) + q S0 dq N(d1)
+/// Θ_p = -S0 dq φ(d1) σ / (2√T) + r K df N(-d2) - q S0 dq N(-d1)
+/// ρ_c = K T df N(d2), ρ_p = -K T df N(-d2)
+/// Vanna = -dq φ(d1) d2 / σ, Volga = Vega d1 d2 / σ
+/// Charm_c = q dq N(d1) - dq φ(d1) (2(r-q)T - d2 σ√T) / (2T σ√T)
+/// Charm_p = -q dq N(-d1) - dq φ(d1) (2(r-q)T - d2 σ√T) / (2T σ√T)
+/// Speed = -Γ/S0 (d1/(σ√T) + 1)
+///
+/// En el límite `t < EPS_TIME` o `sigma < EPS_VOL` el payoff es determinista:
+/// se devuelven delta, theta, rho y charm del límite y el resto de griegas a cero.
+pub fn option_greeks_dividend(s0: SpotPrice, k: StrikePrice, t: TimeToMaturity, r: InterestRate, q: DividendYield, sigma: Volatility, kind: OptionType) -> FinanceResult<Greeks> {
+    let (d1, d2) = d1_d2_dividend(s0, k, t, r, q, sigma)?;
+    let df = (-r * t).exp();
+    let dq = (-q * t).exp();
+
+    let (delta, carry_theta, rho, carry_charm) = match kind {
+        OptionType::Call => (
+            dq * normal_cdf(d1),
+            -r * k * df * normal_cdf(d2) + q * s0 * dq * normal_cdf(d1),
+            k * t * df * normal_cdf(d2),
+            q * dq * normal_cdf(d1),
+        ),
+        OptionType::Put => (
+            -dq * normal_cdf(-d1),
+            r * k * df * normal_cdf(-d2) - q * s0 * dq * normal_cdf(-d1),
+            -k * t * df * normal_cdf(-d2),
+            -q * dq * normal_cdf(-d1),
+        ),
+    };
+
+    if t < EPS_TIME || sigma < EPS_VOL {
+        return Ok(Greeks { delta, theta: carry_theta, rho, charm: carry_charm, ..Greeks::default() });
+    }
+
+    let sqrt_t = t.sqrt();
+    let sigma_sqrt_t = sigma * sqrt_t;
+    let pdf_d1 = dq * normal_pdf(d1);
+
+    let gamma = pdf_d1 / (s0 * sigma_sqrt_t);
+    let vega = s0 * pdf_d1 * sqrt_t;
+    let theta = -s0 * pdf_d1 * sigma / (2.0 * sqrt_t) + carry_theta;
+    let vanna = -pdf_d1 * d2 / sigma;
+    let volga = vega * d1 * d2 / sigma;
+    let charm = carry_charm - pdf_d1 * (2.0 * (r - q) * t - d2 * sigma_sqrt_t) / (2.0 * t * sigma_sqrt_t);
+    let speed = -gamma / s0 * (d1 / sigma_sqrt_t + 1.0);
+
+    Ok(Greeks { delta, gamma, vega, theta, rho, vanna, volga, charm, speed })
+}
+
+/// Volatilidad implícita de Black-Scholes a partir de un precio de mercado.
+///
+/// Invierte `option_price` con Newton-Raphson (paso = error / vega) protegido
+/// por bisección sobre un intervalo [0, σ_max] que acota la solución. La
+/// estimación inicial es la de Manaster-Koehler: σ0 = √(2 |ln(S0/K) + rT| / T).
+///
+/// Cotas de no arbitraje (df = e^{-rT}):
+/// max(S0 - K df, 0) ≤ C < S0
+/// max(K df - S0, 0) ≤ P < K df
+///
+/// # Errores
+/// * `ArbitrageViolation` - Si el precio está fuera de las cotas
+/// * `InvalidPeriods` - Si `t < EPS_TIME` (el precio no depende de σ)
+/// * `NoConvergence` - Si el solver no converge
+pub fn implied_volatility(market_price: f64, s0: SpotPrice, k: StrikePrice, t: TimeToMaturity, r: InterestRate, kind: OptionType) -> FinanceResult<Volatility> {
+    implied_volatility_dividend(market_price, s0, k, t, r, 0.0, kind)
+}
+
+/// Volatilidad implícita con rendimiento por dividendo continuo q.
+///
+/// Mismo método que `implied_volatility`, con S0 sustituido por S0 e^{-qT}
+/// en las cotas de no arbitraje y en la estimación inicial.
+pub fn implied_volatility_dividend(market_price: f64, s0: SpotPrice, k: StrikePrice, t: TimeToMaturity, r: InterestRate, q: DividendYield, kind: OptionType) -> FinanceResult<Volatility> {
+    validate_inputs(s0, k, t, r, 0.0)?;
+    if !q.is_finite() {
+        return Err(FinanceError::InvalidInterestRate);
+    }
+    if !market_price.is_finite() || market_price < 0.0 {
+        return Err(FinanceError::InvalidMonetaryValue);
+    }
+    if t < EPS_TIME {
+        return Err(FinanceError::InvalidPeriods);
+    }
+
+    let df = (-r * t).exp();
+    let s0_q = s0 * (-q * t).exp();
+    let (lower, upper) = match kind {
+        OptionType::Call => ((s0_q - k * df).max(0.0), s0_q),
+        OptionType::Put  => ((k * df - s0_q).max(0.0), k * df),
+    };
+    if market_price < lower - IMPLIED_VOL_TOL * upper || market_price >= upper {
+        return Err(FinanceError::ArbitrageViolation);
+    }
+    if market_price <= lower {
+        return Ok(0.0);
+    }
+
+    let objective = |sigma: f64| -> (f64, f64) {
+        let price = option_price_dividend(s0, k, t, r, q, sigma, kind).unwrap_or(f64::NAN);
+        let vega = option_greeks_dividend(s0, k, t, r, q, sigma, kind).map(|g| g.vega).unwrap_or(0.0);
+        (price - market_price, vega)
+    };
+
+    let mut hi = 1.0;
+    while objective(hi).0 < 0.0 {
+        hi *= 2.0;
+        if hi > MAX_IMPLIED_VOL {
+            return Err(FinanceError::NoConvergence);
+        }
+    }
+
+    let guess = (2.0 * ((s0 / k).ln() + (r - q) * t).abs() / t).sqrt();
+    newton_safeguarded(objective, 0.0, hi, guess, IMPLIED_VOL_TOL, IMPLIED_VOL_MAX_ITER)
+}
+
+/// E[e^{-rt} f(S_t)] con S_t = S₀ exp((r - q - σ²/2)t + σ√t Z), por la
+/// regla de los trapecios en Z ∈ [-8, 8] con 2000 paneles. Referencia de los
+/// tests de opciones cuyo valor en t es una función del spot.
+#[cfg(test)]
+pub(crate) fn lognormal_expectation<F: Fn(SpotPrice) -> f64>(s0: SpotPrice, t: TimeToMaturity, r: InterestRate, q: DividendYield, sigma: Volatility, payoff: F) -> f64 {
+    let n = 2000;
+    let (lo, hi) = (-8.0, 8.0);
+    let h = (hi - lo) / n as f64;
+    let mut sum = 0.0;
+    for i in 0..=n {
+        let z = lo + i as f64 * h;
+        let weight = if i == 0 || i == n { 0.5 } else { 1.0 };
+        let s = s0 * ((r - q - 0.5 * sigma * sigma) * t + sigma * t.sqrt() * z).exp();
+        sum += weight * payoff(s) * normal_pdf(z);
+    }
+    (-r * t).exp() * sum * h
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -147,6 +493,79 @@ mod tests {
         assert!((normal_cdf(0.0) - 0.5).abs() < 1e-6);
     }
 
+    #[test]
+    fn normal_inv_cdf_inverts_cdf() {
+        for &p in &[1e-6, 0.01, 0.025, 0.25, 0.5, 0.8, 0.975, 0.999] {
+            let x = normal_inv_cdf(p);
+            assert!((normal_cdf(x) - p).abs() < 1e-7, "p={p}");
+        }
+        assert!((normal_inv_cdf(0.975) - 1.959964).abs() < 1e-5);
+        assert!(normal_inv_cdf(0.0).is_infinite());
+    }
+
+    #[test]
+    fn normal_cdf_reference_values() {
+        // Valores de N(x) redondeados a doble precisión
+        let reference = [
+            (1.0, 0.841_344_746_068_542_9),
+            (-3.0, 0.001_349_898_031_630_094_5),
+            (-8.0, 6.220_960_574_271_784e-16),
+            (0.3, 0.617_911_422_188_952_7),
+            (-1.2, 0.115_069_670_221_708_28),
+            (2.5, 0.993_790_334_674_223_8),
+        ];
+        for (x, expected) in reference {
+            assert!((normal_cdf(x) - expected).abs() < 1e-15, "x={x}");
+        }
+        assert_eq!(normal_cdf(-40.0), 0.0);
+        assert_eq!(normal_cdf(f64::INFINITY), 1.0);
+    }
+
+    #[test]
+    fn bivariate_normal_special_cases() {
+        for &rho in &[-0.95, -0.5, 0.0, 0.2, 0.5, 0.8, 0.95] {
+            // M(0, 0; ρ) = 1/4 + asin(ρ) / (2π)
+            let expected = 0.25 + f64::asin(rho) / (2.0 * PI);
+            assert!((bivariate_normal_cdf(0.0, 0.0, rho) - expected).abs() < 1e-15, "ρ={rho}");
+        }
+        let (a, b) = (0.3, -1.2);
+        let (na, nb) = (normal_cdf(a), normal_cdf(b));
+        assert!((bivariate_normal_cdf(a, b, 0.0) - na * nb).abs() < 1e-15);
+        assert!((bivariate_normal_cdf(a, b, 1.0) - nb).abs() < 1e-15);
+        assert!((bivariate_normal_cdf(a, b, -1.0) - (na + nb - 1.0).max(0.0)).abs() < 1e-15);
+        assert!((bivariate_normal_cdf(f64::INFINITY, b, 0.7) - nb).abs() < 1e-15);
+        assert_eq!(bivariate_normal_cdf(f64::NEG_INFINITY, b, 0.7), 0.0);
+    }
+
+    #[test]
+    fn bivariate_normal_reference_values() {
+        // M(a, b; ρ) = ∫_{-∞}^{a} φ(x) N((b - ρx)/√(1 - ρ²)) dx evaluada con
+        // 30 dígitos
+        let reference = [
+            ((0.5, 1.5, 0.3), 0.660_308_492_753_382_7),
+            ((-1.0, 0.7, -0.6), 0.063_723_073_439_011_48),
+            ((2.0, -0.3, 0.93), 0.382_088_577_804_652_6),
+            ((-0.4, -0.8, -0.97), 8.819_395_589_290_18e-9),
+            ((1.2, 1.0, 0.99), 0.840_250_353_244_921_1),
+            ((-2.5, 0.1, 0.5), 0.005_945_771_332_624_566),
+            ((-3.0, -2.0, 0.8), 0.001_131_426_506_094_111),
+            ((1.0, -1.0, -0.2), 0.120_586_335_176_148_43),
+        ];
+        for ((a, b, rho), expected) in reference {
+            assert!((bivariate_normal_cdf(a, b, rho) - expected).abs() < 1e-14, "({a}, {b}, {rho})");
+        }
+    }
+
+    #[test]
+    fn bivariate_normal_symmetries() {
+        for &(a, b, rho) in &[(0.5, 1.5, 0.3), (-1.0, 0.7, -0.6), (2.0, -0.3, 0.93), (-0.4, -0.8, -0.97)] {
+            let m = bivariate_normal_cdf(a, b, rho);
+            // Simetría en los argumentos y M(a, b; ρ) + M(a, -b; -ρ) = N(a)
+            assert!((m - bivariate_normal_cdf(b, a, rho)).abs() < 1e-15);
+            assert!((m + bivariate_normal_cdf(a, -b, -rho) - normal_cdf(a)).abs() < 1e-15);
+        }
+    }
+
     #[test]
     fn d1_d2_regular_case() {
         let (d1, d2) = d1_d2(100.0, 100.0, 1.0, 0.05, 0.20).unwrap();
@@ -182,6 +601,189 @@ mod tests {
         assert!((put - direct_put).abs() < 1e-12);
     }
 
+    #[test]
+    fn greeks_atm_reference_values() {
+        let g = option_greeks(100.0, 100.0, 1.0, 0.05, 0.20, OptionType::Call).unwrap();
+        assert!((g.delta - 0.636831).abs() < 1e-5);
+        assert!((g.gamma - 0.018762).abs() < 1e-5);
+        assert!((g.vega - 37.524035).abs() < 1e-4);
+        assert!((g.theta - (-6.414028)).abs() < 1e-4);
+        assert!((g.rho - 53.232482).abs() < 1e-4);
+    }
+
+    #[test]
+    fn greeks_call_put_relations() {
+        let (s0, k, t, r, sigma) = (95.0, 105.0, 0.75, 0.03, 0.30);
+        let c = option_greeks(s0, k, t, r, sigma, OptionType::Call).unwrap();
+        let p = option_greeks(s0, k, t, r, sigma, OptionType::Put).unwrap();
+        let df = (-r * t).exp();
+        assert!((c.delta - p.delta - 1.0).abs() < 1e-12);
+        assert!((c.gamma - p.gamma).abs() < 1e-12);
+        assert!((c.vega - p.vega).abs() < 1e-12);
+        assert!((c.vanna - p.vanna).abs() < 1e-12);
+        assert!((c.volga - p.volga).abs() < 1e-12);
+        assert!((c.charm - p.charm).abs() < 1e-12);
+        assert!((c.speed - p.speed).abs() < 1e-12);
+        assert!((c.theta - p.theta - (-r * k * df)).abs() < 1e-10);
+        assert!((c.rho - p.rho - k * t * df).abs() < 1e-10);
+    }
+
+    #[test]
+    fn greeks_match_finite_differences() {
+        let (s0, k, t, r, sigma) = (100.0, 110.0, 0.5, 0.04, 0.25);
+        for kind in [OptionType::Call, OptionType::Put] {
+            let g = option_greeks(s0, k, t, r, sigma, kind).unwrap();
+            let price = |s: f64, t: f64, r: f64, v: f64| option_price(s, k, t, r, v, kind).unwrap();
+            let delta_at = |s: f64, t: f64, v: f64| option_greeks(s, k, t, r, v, kind).unwrap();
+
+            let hs = 0.01;
+            let fd_delta = (price(s0 + hs, t, r, sigma) - price(s0 - hs, t, r, sigma)) / (2.0 * hs);
+            assert!((g.delta - fd_delta).abs() < 1e-4);
+
+            let fd_gamma = (delta_at(s0 + hs, t, sigma).delta - delta_at(s0 - hs, t, sigma).delta) / (2.0 * hs);
+            assert!((g.gamma - fd_gamma).abs() < 1e-6);
+
+            let hv = 1e-4;
+            let fd_vega = (price(s0, t, r, sigma + hv) - price(s0, t, r, sigma - hv)) / (2.0 * hv);
+            assert!((g.vega - fd_vega).abs() < 1e-2);
+
+            let ht = 1e-4;
+            let fd_theta = -(price(s0, t + ht, r, sigma) - price(s0, t - ht, r, sigma)) / (2.0 * ht);
+            assert!((g.theta - fd_theta).abs() < 1e-2);
+
+            let hr = 1e-4;
+            let fd_rho = (price(s0, t, r + hr, sigma) - price(s0, t, r - hr, sigma)) / (2.0 * hr);
+            assert!((g.rho - fd_rho).abs() < 1e-2);
+
+            let fd_vanna = (delta_at(s0, t, sigma + hv).delta - delta_at(s0, t, sigma - hv).delta) / (2.0 * hv);
+            assert!((g.vanna - fd_vanna).abs() < 1e-4);
+
+            let fd_volga = (delta_at(s0, t, sigma + hv).vega - delta_at(s0, t, sigma - hv).vega) / (2.0 * hv);
+            assert!((g.volga - fd_volga).abs() < 1e-2);
+
+            let fd_charm = -(delta_at(s0, t + ht, sigma).delta - delta_at(s0, t - ht, sigma).delta) / (2.0 * ht);
+            assert!((g.charm - fd_charm).abs() < 1e-4);
+
+            let fd_speed = (delta_at(s0 + hs, t, sigma).gamma - delta_at(s0 - hs, t, sigma).gamma) / (2.0 * hs);
+            assert!((g.speed - fd_speed).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn greeks_degenerate_limits() {
+        let at_expiry = option_greeks(120.0, 110.0, 1e-16, 0.05, 0.20, OptionType::Call).unwrap();
+        assert_eq!(at_expiry.delta, 1.0);
+        assert_eq!(at_expiry.gamma, 0.0);
+        assert_eq!(at_expiry.vega, 0.0);
+
+        let zero_vol = option_greeks(90.0, 100.0, 1.0, 0.05, 0.0, OptionType::Put).unwrap();
+        let df = (-0.05f64).exp();
+        assert_eq!(zero_vol.delta, -1.0);
+        assert_eq!(zero_vol.gamma, 0.0);
+        assert!((zero_vol.theta - 0.05 * 100.0 * df).abs() < 1e-12);
+        assert!((zero_vol.rho - (-100.0 * df)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn dividend_put_call_parity() {
+        let (s0, k, t, r, q, sigma) = (100.0, 95.0, 0.8, 0.04, 0.025, 0.22);
+        let c = call_price_dividend(s0, k, t, r, q, sigma).unwrap();
+        let p = put_price_dividend(s0, k, t, r, q, sigma).unwrap();
+        assert!((c - p - (s0 * (-q * t).exp() - k * (-r * t).exp())).abs() < 1e-10);
+    }
+
+    #[test]
+    fn dividend_zero_yield_matches_plain() {
+        let (s0, k, t, r, sigma) = (100.0, 105.0, 0.5, 0.03, 0.25);
+        assert_eq!(call_price_dividend(s0, k, t, r, 0.0, sigma).unwrap(), call_price(s0, k, t, r, sigma).unwrap());
+        assert_eq!(put_price_dividend(s0, k, t, r, 0.0, sigma).unwrap(), put_price(s0, k, t, r, sigma).unwrap());
+    }
+
+    #[test]
+    fn dividend_reference_value() {
+        // Hull, ej. 17.1: S0=930, K=900, T=2/12, r=8%, q=3%, σ=20% => C ≈ 51.83
+        let c = call_price_dividend(930.0, 900.0, 2.0 / 12.0, 0.08, 0.03, 0.20).unwrap();
+        assert!((c - 51.83).abs() < 0.01);
+    }
+
+    #[test]
+    fn dividend_d1_d2_uses_carry() {
+        let (d1, d2) = d1_d2_dividend(100.0, 100.0, 1.0, 0.05, 0.05, 0.20).unwrap();
+        assert!((d1 - 0.10).abs() < 1e-12);
+        assert!((d2 + 0.10).abs() < 1e-12);
+    }
+
+    #[test]
+    fn dividend_greeks_match_finite_differences() {
+        let (s0, k, t, r, q, sigma) = (100.0, 100.0, 0.6, 0.04, 0.03, 0.3);
+        for kind in [OptionType::Call, OptionType::Put] {
+            let g = option_greeks_dividend(s0, k, t, r, q, sigma, kind).unwrap();
+            let price = |s: f64, t: f64| option_price_dividend(s, k, t, r, q, sigma, kind).unwrap();
+            let greeks_at = |s: f64, t: f64| option_greeks_dividend(s, k, t, r, q, sigma, kind).unwrap();
+
+            let hs = 0.01;
+            let fd_delta = (price(s0 + hs, t) - price(s0 - hs, t)) / (2.0 * hs);
+            assert!((g.delta - fd_delta).abs() < 1e-4);
+
+            let fd_gamma = (greeks_at(s0 + hs, t).delta - greeks_at(s0 - hs, t).delta) / (2.0 * hs);
+            assert!((g.gamma - fd_gamma).abs() < 1e-6);
+
+            let ht = 1e-4;
+            let fd_theta = -(price(s0, t + ht) - price(s0, t - ht)) / (2.0 * ht);
+            assert!((g.theta - fd_theta).abs() < 1e-2);
+
+            let fd_charm = -(greeks_at(s0, t + ht).delta - greeks_at(s0, t - ht).delta) / (2.0 * ht);
+            assert!((g.charm - fd_charm).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn implied_volatility_round_trip() {
+        let (s0, t, r) = (100.0, 0.75, 0.03);
+        for &k in &[60.0, 90.0, 100.0, 115.0, 160.0] {
+            for &sigma in &[0.05, 0.2, 0.6, 1.5] {
+                for kind in [OptionType::Call, OptionType::Put] {
+                    let price = option_price(s0, k, t, r, sigma, kind).unwrap();
+                    let iv = implied_volatility(price, s0, k, t, r, kind).unwrap();
+                    let repriced = option_price(s0, k, t, r, iv, kind).unwrap();
+                    assert!((repriced - price).abs() < 1e-9, "K={k} σ={sigma} {kind:?}");
+                    let vega = option_greeks(s0, k, t, r, sigma, kind).unwrap().vega;
+                    if vega > 1e-2 {
+                        assert!((iv - sigma).abs() < 1e-5, "K={k} σ={sigma} {kind:?}");
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn implied_volatility_at_lower_bound_is_zero() {
+        let df = (-0.05f64).exp();
+        let iv = implied_volatility(110.0 - 100.0 * df, 110.0, 100.0, 1.0, 0.05, OptionType::Call).unwrap();
+        assert_eq!(iv, 0.0);
+    }
+
+    #[test]
+    fn implied_volatility_arbitrage_bounds() {
+        // Call por debajo del valor intrínseco descontado
+        let err = implied_volatility(5.0, 110.0, 100.0, 1.0, 0.05, OptionType::Call).unwrap_err();
+        assert_eq!(err, FinanceError::ArbitrageViolation);
+        // Call por encima del subyacente
+        let err = implied_volatility(101.0, 100.0, 100.0, 1.0, 0.05, OptionType::Call).unwrap_err();
+        assert_eq!(err, FinanceError::ArbitrageViolation);
+        // Put por encima de K e^{-rT}
+        let err = implied_volatility(99.0, 100.0, 100.0, 1.0, 0.05, OptionType::Put).unwrap_err();
+        assert_eq!(err, FinanceError::ArbitrageViolation);
+    }
+
+    #[test]
+    fn implied_volatility_invalid_inputs() {
+        let err = implied_volatility(-1.0, 100.0, 100.0, 1.0, 0.05, OptionType::Call).unwrap_err();
+        assert_eq!(err, FinanceError::InvalidMonetaryValue);
+        let err = implied_volatility(5.0, 100.0, 100.0, 0.0, 0.05, OptionType::Call).unwrap_err();
+        assert_eq!(err, FinanceError::InvalidPeriods);
+    }
+
     #[test]
     fn validate_inputs_errors() {
         let err_money = validate_inputs(-1.0, 100.0, 1.0, 0.01, 0.2).unwrap_err();
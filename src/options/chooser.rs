@@ -0,0 +1,211 @@
+//! Opciones chooser: en la fecha de elección t el tenedor decide si la opción
+//! es una call o una put europea.
+//!
+//! - Simple (Rubinstein, 1991), call y put con el mismo K y T:
+//!   w = S e^{(b-r)T} N(d) - K e^{-rT} N(d - σ√T) - S e^{(b-r)T} N(-y) + K e^{-rT} N(-y + σ√t)
+//!   d = [ln(S/K) + (b + σ²/2)T]/(σ√T), y = [ln(S/K) + bT + σ²t/2]/(σ√t)
+//! - Compleja (Rubinstein, 1991), call (Kc, Tc) y put (Kp, Tp): con I el spot
+//!   en el que c(I, Kc, Tc - t) = p(I, Kp, Tp - t), ρ1 = √(t/Tc), ρ2 = √(t/Tp),
+//!   w = S e^{(b-r)Tc} M(d1, y1; ρ1) - Kc e^{-rTc} M(d2, y1 - σ√Tc; ρ1) -
+//!   S e^{(b-r)Tp} M(-d1, -y2; ρ2) + Kp e^{-rTp} M(-d2, -y2 + σ√Tp; ρ2)
+//!   d1 = [ln(S/I) + (b + σ²/2)t]/(σ√t), d2 = d1 - σ√t
+//!   y1 = [ln(S/Kc) + (b + σ²/2)Tc]/(σ√Tc), y2 = [ln(S/Kp) + (b + σ²/2)Tp]/(σ√Tp)
+
+use crate::common::{
+    FinanceError, FinanceResult,
+    DividendYield,
+    SpotPrice, StrikePrice, TimeToMaturity, InterestRate, Volatility,
+    OptionType
+};
+use super::black_scholes::{
+    normal_cdf, bivariate_normal_cdf, option_price_dividend, validate_inputs, EPS_TIME, EPS_VOL
+};
+use super::compound::critical_spot;
+
+/// Opción chooser europea
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ChooserOption {
+    /// Fecha de elección (t)
+    pub choice_time: TimeToMaturity,
+    /// Strike y vencimiento de la call elegible
+    pub call: (StrikePrice, TimeToMaturity),
+    /// Strike y vencimiento de la put elegible
+    pub put: (StrikePrice, TimeToMaturity),
+    /// Rendimiento por dividendo continuo del subyacente
+    pub dividend_yield: DividendYield,
+}
+
+impl ChooserOption {
+    /// Chooser simple: call y put con el mismo strike y vencimiento.
+    ///
+    /// # Errores
+    /// * `InvalidMonetaryValue` - Si el strike no es positivo
+    /// * `InvalidPeriods` - Si no se cumple 0 ≤ t ≤ T
+    pub fn simple(choice_time: TimeToMaturity, k: StrikePrice, t: TimeToMaturity) -> FinanceResult<Self> {
+        Self::complex(choice_time, k, t, k, t)
+    }
+
+    /// Chooser compleja: call y put con strikes y vencimientos distintos.
+    ///
+    /// # Errores
+    /// * `InvalidMonetaryValue` - Si algún strike no es positivo
+    /// * `InvalidPeriods` - Si la elección es posterior a algún vencimiento
+    pub fn complex(choice_time: TimeToMaturity, call_strike: StrikePrice, call_expiry: TimeToMaturity, put_strike: StrikePrice, put_expiry: TimeToMaturity) -> FinanceResult<Self> {
+        if !call_strike.is_finite() || !put_strike.is_finite() || call_strike <= 0.0 || put_strike <= 0.0 {
+            return Err(FinanceError::InvalidMonetaryValue);
+        }
+        if !choice_time.is_finite() || !call_expiry.is_finite() || !put_expiry.is_finite()
+            || choice_time < 0.0 || choice_time > call_expiry || choice_time > put_expiry {
+            return Err(FinanceError::InvalidPeriods);
+        }
+        Ok(ChooserOption {
+            choice_time,
+            call: (call_strike, call_expiry),
+            put: (put_strike, put_expiry),
+            dividend_yield: 0.0,
+        })
+    }
+
+    /// Cambia el rendimiento por dividendo continuo.
+    ///
+    /// # Errores
+    /// * `InvalidInterestRate` - Si no es finito
+    pub fn with_dividend_yield(mut self, q: DividendYield) -> FinanceResult<Self> {
+        if !q.is_finite() {
+            return Err(FinanceError::InvalidInterestRate);
+        }
+        self.dividend_yield = q;
+        Ok(self)
+    }
+
+    /// `true` si la call y la put comparten strike y vencimiento.
+    pub fn is_simple(&self) -> bool {
+        self.call == self.put
+    }
+
+    /// Precio de la chooser.
+    ///
+    /// # Errores
+    /// * `InvalidInterestRate` - Si `sigma` es nula o negativa
+    pub fn price(&self, s0: SpotPrice, r: InterestRate, sigma: Volatility) -> FinanceResult<f64> {
+        let ((kc, tc), (kp, tp)) = (self.call, self.put);
+        validate_inputs(s0, kc, tc, r, sigma)?;
+        validate_inputs(s0, kp, tp, r, sigma)?;
+        let (t, q) = (self.choice_time, self.dividend_yield);
+        let call = |s: f64, tau: f64| option_price_dividend(s, kc, tau, r, q, sigma, OptionType::Call);
+        let put = |s: f64, tau: f64| option_price_dividend(s, kp, tau, r, q, sigma, OptionType::Put);
+        if t < EPS_TIME {
+            return Ok(call(s0, tc)?.max(put(s0, tp)?));
+        }
+        if sigma < EPS_VOL {
+            return Err(FinanceError::InvalidInterestRate);
+        }
+
+        let b = r - q;
+        let v2 = sigma * sigma;
+        if self.is_simple() {
+            let d = ((s0 / kc).ln() + (b + 0.5 * v2) * tc) / (sigma * tc.sqrt());
+            let y = ((s0 / kc).ln() + b * tc + 0.5 * v2 * t) / (sigma * t.sqrt());
+            let asset = s0 * (-q * tc).exp();
+            let cash = kc * (-r * tc).exp();
+            return Ok(asset * normal_cdf(d) - cash * normal_cdf(d - sigma * tc.sqrt())
+                - asset * normal_cdf(-y) + cash * normal_cdf(-y + sigma * t.sqrt()));
+        }
+
+        let critical = critical_spot(
+            |s| match (call(s, tc - t), put(s, tp - t)) {
+                (Ok(c), Ok(p)) => c - p,
+                _ => f64::NAN,
+            },
+            0.5 * (kc + kp),
+        )?;
+        let d1 = ((s0 / critical).ln() + (b + 0.5 * v2) * t) / (sigma * t.sqrt());
+        let d2 = d1 - sigma * t.sqrt();
+        let y1 = ((s0 / kc).ln() + (b + 0.5 * v2) * tc) / (sigma * tc.sqrt());
+        let y2 = ((s0 / kp).ln() + (b + 0.5 * v2) * tp) / (sigma * tp.sqrt());
+        let (rho1, rho2) = ((t / tc).sqrt(), (t / tp).sqrt());
+
+        Ok(s0 * (-q * tc).exp() * bivariate_normal_cdf(d1, y1, rho1)
+            - kc * (-r * tc).exp() * bivariate_normal_cdf(d2, y1 - sigma * tc.sqrt(), rho1)
+            - s0 * (-q * tp).exp() * bivariate_normal_cdf(-d1, -y2, rho2)
+            + kp * (-r * tp).exp() * bivariate_normal_cdf(-d2, -y2 + sigma * tp.sqrt(), rho2))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::options::black_scholes::lognormal_expectation;
+
+    /// max(call, put) en la fecha de elección integrado sobre ln S_t.
+    fn by_quadrature(option: &ChooserOption, s0: f64, r: f64, sigma: f64) -> f64 {
+        let ((kc, tc), (kp, tp)) = (option.call, option.put);
+        let (t, q) = (option.choice_time, option.dividend_yield);
+        lognormal_expectation(s0, t, r, q, sigma, |s| {
+            let c = option_price_dividend(s, kc, tc - t, r, q, sigma, OptionType::Call).unwrap();
+            let p = option_price_dividend(s, kp, tp - t, r, q, sigma, OptionType::Put).unwrap();
+            c.max(p)
+        })
+    }
+
+    #[test]
+    fn haug_reference_values() {
+        // Haug (2007): simple S=50, K=50, t=0.25, T=0.5, r=8%, b=8%, σ=25% → 6.1071
+        let simple = ChooserOption::simple(0.25, 50.0, 0.5).unwrap();
+        let price = simple.price(50.0, 0.08, 0.25).unwrap();
+        assert!((price - 6.1071).abs() < 1e-3, "{price}");
+
+        // Compleja S=50, Kc=55, Kp=48, t=0.25, Tc=0.5, Tp=0.5833, r=10%, b=5%, σ=35% → 6.0508
+        let complex = ChooserOption::complex(0.25, 55.0, 0.5, 48.0, 0.583_3).unwrap().with_dividend_yield(0.05).unwrap();
+        let price = complex.price(50.0, 0.10, 0.35).unwrap();
+        assert!((price - 6.0508).abs() < 1e-3, "{price}");
+    }
+
+    #[test]
+    fn complex_formula_reduces_to_simple() {
+        // Strikes casi iguales fuerzan la rama compleja
+        let (s0, r, sigma) = (100.0, 0.05, 0.3);
+        let simple = ChooserOption::simple(0.4, 100.0, 1.0).unwrap().with_dividend_yield(0.02).unwrap();
+        let complex = ChooserOption::complex(0.4, 100.0, 1.0, 100.0 + 1e-9, 1.0).unwrap().with_dividend_yield(0.02).unwrap();
+        assert!(simple.is_simple() && !complex.is_simple());
+        let a = simple.price(s0, r, sigma).unwrap();
+        let b = complex.price(s0, r, sigma).unwrap();
+        assert!((a - b).abs() < 1e-6, "{a} vs {b}");
+    }
+
+    #[test]
+    fn matches_quadrature() {
+        let (s0, r, sigma) = (100.0, 0.04, 0.25);
+        let options = [
+            ChooserOption::simple(0.5, 105.0, 1.0).unwrap(),
+            ChooserOption::complex(0.3, 110.0, 1.0, 95.0, 0.8).unwrap(),
+            ChooserOption::complex(0.5, 90.0, 0.75, 105.0, 1.5).unwrap().with_dividend_yield(0.03).unwrap(),
+        ];
+        for option in options {
+            let price = option.price(s0, r, sigma).unwrap();
+            let reference = by_quadrature(&option, s0, r, sigma);
+            assert!((price - reference).abs() < 1e-4, "{option:?}: {price} vs {reference}");
+        }
+    }
+
+    #[test]
+    fn choice_time_limits() {
+        // t = 0: la mejor de las dos; t = T: straddle
+        let (s0, k, t, r, sigma) = (100.0, 95.0, 1.0, 0.05, 0.2);
+        let c = option_price_dividend(s0, k, t, r, 0.0, sigma, OptionType::Call).unwrap();
+        let p = option_price_dividend(s0, k, t, r, 0.0, sigma, OptionType::Put).unwrap();
+        let now = ChooserOption::simple(0.0, k, t).unwrap().price(s0, r, sigma).unwrap();
+        assert_eq!(now, c.max(p));
+        let at_expiry = ChooserOption::simple(t, k, t).unwrap().price(s0, r, sigma).unwrap();
+        assert!((at_expiry - (c + p)).abs() < 1e-6, "{at_expiry}");
+    }
+
+    #[test]
+    fn invalid_configuration() {
+        assert_eq!(ChooserOption::simple(0.5, 0.0, 1.0).unwrap_err(), FinanceError::InvalidMonetaryValue);
+        assert_eq!(ChooserOption::simple(1.5, 100.0, 1.0).unwrap_err(), FinanceError::InvalidPeriods);
+        assert_eq!(ChooserOption::complex(0.5, 100.0, 1.0, 100.0, 0.25).unwrap_err(), FinanceError::InvalidPeriods);
+        let option = ChooserOption::simple(0.5, 100.0, 1.0).unwrap();
+        assert_eq!(option.price(100.0, 0.05, 0.0).unwrap_err(), FinanceError::InvalidInterestRate);
+    }
+}
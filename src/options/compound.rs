@@ -0,0 +1,243 @@
+//! Opciones compuestas (opción sobre opción) de Geske (1979).
+//!
+//! La compuesta vence en T1 con strike K1 y da derecho a comprar (call) o
+//! vender (put) una opción europea de strike K2 que vence en T2 > T1.
+//! Con b = r - q, I el spot crítico en el que la opción subyacente vale K1 en
+//! T1, ρ = √(T1/T2) y
+//! y1 = [ln(S/I) + (b + σ²/2)T1]/(σ√T1),  y2 = y1 - σ√T1
+//! z1 = [ln(S/K2) + (b + σ²/2)T2]/(σ√T2), z2 = z1 - σ√T2
+//!
+//! Call sobre call: S e^{(b-r)T2} M(z1, y1; ρ) - K2 e^{-rT2} M(z2, y2; ρ) - K1 e^{-rT1} N(y2)
+//! Put sobre call:  K2 e^{-rT2} M(z2, -y2; -ρ) - S e^{(b-r)T2} M(z1, -y1; -ρ) + K1 e^{-rT1} N(-y2)
+//! Call sobre put:  K2 e^{-rT2} M(-z2, -y2; ρ) - S e^{(b-r)T2} M(-z1, -y1; ρ) - K1 e^{-rT1} N(-y2)
+//! Put sobre put:   S e^{(b-r)T2} M(-z1, y1; -ρ) - K2 e^{-rT2} M(-z2, y2; -ρ) + K1 e^{-rT1} N(y2)
+//!
+//! Si K1 supera el valor máximo de la put subyacente (K2 e^{-r(T2-T1)}), la
+//! call sobre put nunca se ejerce y la put sobre put siempre.
+
+use crate::common::{
+    FinanceError, FinanceResult,
+    DividendYield,
+    SpotPrice, StrikePrice, TimeToMaturity, InterestRate, Volatility,
+    OptionType
+};
+use crate::num::brent;
+use super::black_scholes::{
+    normal_cdf, bivariate_normal_cdf, option_price_dividend, validate_inputs, EPS_TIME, EPS_VOL
+};
+
+/// Tolerancia relativa del spot crítico.
+const CRITICAL_TOL: f64 = 1e-10;
+const CRITICAL_MAX_ITER: usize = 200;
+
+/// Opción compuesta europea sobre una opción europea
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CompoundOption {
+    /// Tipo de la compuesta (call: derecho a comprar la opción subyacente)
+    pub kind: OptionType,
+    /// Precio de ejercicio de la compuesta (K1)
+    pub strike: StrikePrice,
+    /// Vencimiento de la compuesta (T1)
+    pub expiry: TimeToMaturity,
+    /// Rendimiento por dividendo continuo del subyacente
+    pub dividend_yield: DividendYield,
+}
+
+impl CompoundOption {
+    /// Crea una compuesta sin dividendos.
+    ///
+    /// # Errores
+    /// * `InvalidMonetaryValue` - Si el strike no es positivo
+    /// * `InvalidPeriods` - Si el vencimiento es negativo o no finito
+    pub fn new(kind: OptionType, strike: StrikePrice, expiry: TimeToMaturity) -> FinanceResult<Self> {
+        if !strike.is_finite() || strike <= 0.0 {
+            return Err(FinanceError::InvalidMonetaryValue);
+        }
+        if !expiry.is_finite() || expiry < 0.0 {
+            return Err(FinanceError::InvalidPeriods);
+        }
+        Ok(CompoundOption { kind, strike, expiry, dividend_yield: 0.0 })
+    }
+
+    /// Cambia el rendimiento por dividendo continuo.
+    ///
+    /// # Errores
+    /// * `InvalidInterestRate` - Si no es finito
+    pub fn with_dividend_yield(mut self, q: DividendYield) -> FinanceResult<Self> {
+        if !q.is_finite() {
+            return Err(FinanceError::InvalidInterestRate);
+        }
+        self.dividend_yield = q;
+        Ok(self)
+    }
+
+    /// Precio de la compuesta sobre la opción europea (`k`, `t`, `underlying`).
+    ///
+    /// # Argumentos
+    /// * `k` - Strike de la opción subyacente (K2)
+    /// * `t` - Vencimiento de la opción subyacente (T2)
+    /// * `underlying` - Tipo de la opción subyacente
+    ///
+    /// # Errores
+    /// * `InvalidPeriods` - Si la compuesta vence después que la subyacente
+    /// * `InvalidInterestRate` - Si `sigma` es nula o negativa
+    pub fn price(&self, s0: SpotPrice, k: StrikePrice, t: TimeToMaturity, r: InterestRate, sigma: Volatility, underlying: OptionType) -> FinanceResult<f64> {
+        validate_inputs(s0, k, t, r, sigma)?;
+        let (k1, t1, q) = (self.strike, self.expiry, self.dividend_yield);
+        if t1 > t {
+            return Err(FinanceError::InvalidPeriods);
+        }
+        if t1 < EPS_TIME {
+            let value = option_price_dividend(s0, k, t, r, q, sigma, underlying)?;
+            return Ok(self.kind.payoff(value, k1));
+        }
+        if sigma < EPS_VOL {
+            return Err(FinanceError::InvalidInterestRate);
+        }
+
+        let tau = t - t1;
+        let df1 = (-r * t1).exp();
+        if underlying == OptionType::Put && k1 >= k * (-r * tau).exp() {
+            return Ok(match self.kind {
+                OptionType::Call => 0.0,
+                OptionType::Put => df1 * k1 - option_price_dividend(s0, k, t, r, q, sigma, underlying)?,
+            });
+        }
+
+        let sign = if underlying == OptionType::Call { 1.0 } else { -1.0 };
+        let critical = critical_spot(
+            |s| match option_price_dividend(s, k, tau, r, q, sigma, underlying) {
+                Ok(v) => sign * (v - k1),
+                Err(_) => f64::NAN,
+            },
+            k,
+        )?;
+
+        let v2 = sigma * sigma;
+        let b = r - q;
+        let y1 = ((s0 / critical).ln() + (b + 0.5 * v2) * t1) / (sigma * t1.sqrt());
+        let y2 = y1 - sigma * t1.sqrt();
+        let z1 = ((s0 / k).ln() + (b + 0.5 * v2) * t) / (sigma * t.sqrt());
+        let z2 = z1 - sigma * t.sqrt();
+        let rho = (t1 / t).sqrt();
+        let asset = s0 * (-q * t).exp();
+        let cash = k * (-r * t).exp();
+
+        let price = match (self.kind, underlying) {
+            (OptionType::Call, OptionType::Call) => {
+                asset * bivariate_normal_cdf(z1, y1, rho) - cash * bivariate_normal_cdf(z2, y2, rho) - df1 * k1 * normal_cdf(y2)
+            }
+            (OptionType::Put, OptionType::Call) => {
+                cash * bivariate_normal_cdf(z2, -y2, -rho) - asset * bivariate_normal_cdf(z1, -y1, -rho) + df1 * k1 * normal_cdf(-y2)
+            }
+            (OptionType::Call, OptionType::Put) => {
+                cash * bivariate_normal_cdf(-z2, -y2, rho) - asset * bivariate_normal_cdf(-z1, -y1, rho) - df1 * k1 * normal_cdf(-y2)
+            }
+            (OptionType::Put, OptionType::Put) => {
+                asset * bivariate_normal_cdf(-z1, y1, -rho) - cash * bivariate_normal_cdf(-z2, y2, -rho) + df1 * k1 * normal_cdf(y2)
+            }
+        };
+        Ok(price.max(0.0))
+    }
+}
+
+/// Raíz de una función creciente en (0, ∞), acotándola geométricamente
+/// alrededor de `guess`. Devuelve el spot crítico de compuestas y choosers.
+pub(super) fn critical_spot<F: Fn(f64) -> f64>(g: F, guess: SpotPrice) -> FinanceResult<SpotPrice> {
+    let (mut lo, mut hi) = (guess, guess);
+    for _ in 0..CRITICAL_MAX_ITER {
+        let (g_lo, g_hi) = (g(lo), g(hi));
+        if g_lo.is_nan() || g_hi.is_nan() {
+            return Err(FinanceError::NoConvergence);
+        }
+        if g_lo <= 0.0 && g_hi >= 0.0 {
+            return brent(&g, lo, hi, CRITICAL_TOL * guess, CRITICAL_MAX_ITER);
+        }
+        if g_lo > 0.0 {
+            lo *= 0.5;
+        }
+        if g_hi < 0.0 {
+            hi *= 2.0;
+        }
+    }
+    Err(FinanceError::NoConvergence)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::options::black_scholes::lognormal_expectation;
+
+    /// Valor descontado del payoff en T1 integrando sobre ln S_T1.
+    fn by_quadrature(option: &CompoundOption, s0: f64, k: f64, t: f64, r: f64, sigma: f64, underlying: OptionType) -> f64 {
+        let (t1, q) = (option.expiry, option.dividend_yield);
+        lognormal_expectation(s0, t1, r, q, sigma, |s1| {
+            let value = option_price_dividend(s1, k, t - t1, r, q, sigma, underlying).unwrap();
+            option.kind.payoff(value, option.strike)
+        })
+    }
+
+    #[test]
+    fn haug_reference_value() {
+        // Haug (2007): put sobre call, S=500, K1=50, K2=520, T1=0.25, T2=0.5,
+        // r=8%, b=5%, σ=35% → 21.1965
+        let option = CompoundOption::new(OptionType::Put, 50.0, 0.25).unwrap().with_dividend_yield(0.03).unwrap();
+        let price = option.price(500.0, 520.0, 0.5, 0.08, 0.35, OptionType::Call).unwrap();
+        assert!((price - 21.1965).abs() < 1e-3, "{price}");
+    }
+
+    #[test]
+    fn matches_quadrature() {
+        let (s0, k, t, r, sigma) = (100.0, 105.0, 1.0, 0.05, 0.3);
+        for kind in [OptionType::Call, OptionType::Put] {
+            for underlying in [OptionType::Call, OptionType::Put] {
+                for (k1, t1, q) in [(6.0, 0.25, 0.0), (12.0, 0.5, 0.02), (3.0, 0.75, -0.01)] {
+                    let option = CompoundOption::new(kind, k1, t1).unwrap().with_dividend_yield(q).unwrap();
+                    let price = option.price(s0, k, t, r, sigma, underlying).unwrap();
+                    let reference = by_quadrature(&option, s0, k, t, r, sigma, underlying);
+                    assert!((price - reference).abs() < 1e-4, "{kind:?} sobre {underlying:?} K1={k1}: {price} vs {reference}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn compound_put_call_parity() {
+        // Call sobre V - put sobre V = V - K1 e^{-rT1}
+        let (s0, k, t, r, q, sigma) = (95.0, 100.0, 0.8, 0.04, 0.01, 0.25);
+        let (k1, t1) = (7.0, 0.3);
+        for underlying in [OptionType::Call, OptionType::Put] {
+            let call = CompoundOption::new(OptionType::Call, k1, t1).unwrap().with_dividend_yield(q).unwrap();
+            let put = CompoundOption::new(OptionType::Put, k1, t1).unwrap().with_dividend_yield(q).unwrap();
+            let value = option_price_dividend(s0, k, t, r, q, sigma, underlying).unwrap();
+            let c = call.price(s0, k, t, r, sigma, underlying).unwrap();
+            let p = put.price(s0, k, t, r, sigma, underlying).unwrap();
+            assert!((c - p - (value - k1 * (-r * t1).exp())).abs() < 1e-6, "{underlying:?}");
+        }
+    }
+
+    #[test]
+    fn put_underlying_never_worth_strike() {
+        // K1 ≥ K2 e^{-r(T2-T1)}: la call sobre put no vale nada
+        let (s0, k, t, r, sigma) = (100.0, 100.0, 1.0, 0.05, 0.2);
+        let call = CompoundOption::new(OptionType::Call, 99.0, 0.5).unwrap();
+        let put = CompoundOption::new(OptionType::Put, 99.0, 0.5).unwrap();
+        assert_eq!(call.price(s0, k, t, r, sigma, OptionType::Put).unwrap(), 0.0);
+        let value = option_price_dividend(s0, k, t, r, 0.0, sigma, OptionType::Put).unwrap();
+        let p = put.price(s0, k, t, r, sigma, OptionType::Put).unwrap();
+        assert!((p - (99.0 * (-r * 0.5f64).exp() - value)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn invalid_and_degenerate_inputs() {
+        assert_eq!(CompoundOption::new(OptionType::Call, 0.0, 0.5).unwrap_err(), FinanceError::InvalidMonetaryValue);
+        assert_eq!(CompoundOption::new(OptionType::Call, 5.0, -0.5).unwrap_err(), FinanceError::InvalidPeriods);
+        let option = CompoundOption::new(OptionType::Call, 5.0, 0.5).unwrap();
+        assert_eq!(option.price(100.0, 100.0, 0.25, 0.05, 0.2, OptionType::Call).unwrap_err(), FinanceError::InvalidPeriods);
+        assert_eq!(option.price(100.0, 100.0, 1.0, 0.05, 0.0, OptionType::Call).unwrap_err(), FinanceError::InvalidInterestRate);
+        // Compuesta vencida: payoff sobre el valor actual de la subyacente
+        let expired = CompoundOption::new(OptionType::Call, 5.0, 0.0).unwrap();
+        let value = option_price_dividend(100.0, 100.0, 1.0, 0.05, 0.0, 0.2, OptionType::Call).unwrap();
+        assert!((expired.price(100.0, 100.0, 1.0, 0.05, 0.2, OptionType::Call).unwrap() - (value - 5.0)).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,254 @@
+//! Opciones digitales (binarias) y gap sobre Black-Scholes, a partir de (d1, d2).
+//!
+//! Con df = e^{-rT}:
+//! - Cash-or-nothing (paga 1): C = df N(d2), P = df N(-d2)
+//! - Asset-or-nothing (entrega S_T): C = S0 N(d1), P = S0 N(-d1)
+//! - Gap con strike de pago K1 y de activación K2 (d1, d2 calculados con K2):
+//!   C = S0 N(d1) - K1 df N(d2), P = K1 df N(-d2) - S0 N(-d1)
+//!
+//! La vanilla es la gap con K1 = K2, es decir C = AoN - K · CoN.
+//!
+//! Griegas de la cash-or-nothing call, con φ = φ(d2) y ∂d2/∂T = [2(r - σ²/2)T/(σ√T) - d2]/(2T):
+//! Δ = df φ/(S0 σ√T), Γ = -df φ d1/(S0² σ² T), Vega = -df φ d1/σ
+//! Θ = r df N(d2) - df φ ∂d2/∂T, ρ = -T df N(d2) + df φ √T/σ
+//!
+//! Griegas de la asset-or-nothing call, con φ = φ(d1):
+//! Δ = N(d1) + φ/(σ√T), Γ = -φ d2/(S0 σ² T), Vega = -S0 φ d2/σ
+//! Θ = -S0 φ ∂d1/∂T, ρ = S0 φ √T/σ
+//!
+//! Las puts se obtienen de P_CoN = df - C_CoN y P_AoN = S0 - C_AoN.
+
+use crate::common::{
+    FinanceResult,
+    SpotPrice, StrikePrice, TimeToMaturity, InterestRate, Volatility,
+    OptionType
+};
+use super::black_scholes::{Greeks, d1_d2, normal_cdf, normal_pdf, EPS_TIME, EPS_VOL};
+
+/// Precio de una cash-or-nothing que paga 1 unidad monetaria.
+pub fn cash_or_nothing_price(s0: SpotPrice, k: StrikePrice, t: TimeToMaturity, r: InterestRate, sigma: Volatility, kind: OptionType) -> FinanceResult<f64> {
+    let (_, d2) = d1_d2(s0, k, t, r, sigma)?;
+    let df = (-r * t).exp();
+    Ok(match kind {
+        OptionType::Call => df * normal_cdf(d2),
+        OptionType::Put => df * normal_cdf(-d2),
+    })
+}
+
+/// Precio de una asset-or-nothing que entrega el subyacente.
+pub fn asset_or_nothing_price(s0: SpotPrice, k: StrikePrice, t: TimeToMaturity, r: InterestRate, sigma: Volatility, kind: OptionType) -> FinanceResult<f64> {
+    let (d1, _) = d1_d2(s0, k, t, r, sigma)?;
+    Ok(match kind {
+        OptionType::Call => s0 * normal_cdf(d1),
+        OptionType::Put => s0 * normal_cdf(-d1),
+    })
+}
+
+/// Precio de una opción gap: paga S_T - K1 (call) o K1 - S_T (put) si S_T
+/// supera (call) o queda por debajo (put) del strike de activación K2.
+///
+/// # Argumentos
+/// * `k1` - Strike de pago
+/// * `k2` - Strike de activación
+pub fn gap_price(s0: SpotPrice, k1: StrikePrice, k2: StrikePrice, t: TimeToMaturity, r: InterestRate, sigma: Volatility, kind: OptionType) -> FinanceResult<f64> {
+    let asset = asset_or_nothing_price(s0, k2, t, r, sigma, kind)?;
+    let cash = cash_or_nothing_price(s0, k2, t, r, sigma, kind)?;
+    Ok(match kind {
+        OptionType::Call => asset - k1 * cash,
+        OptionType::Put => k1 * cash - asset,
+    })
+}
+
+/// Griegas analíticas de la cash-or-nothing (mismas convenciones que [`Greeks`]).
+///
+/// En el límite `t < EPS_TIME` o `sigma < EPS_VOL` se devuelven theta y rho
+/// del límite y el resto de griegas a cero.
+pub fn cash_or_nothing_greeks(s0: SpotPrice, k: StrikePrice, t: TimeToMaturity, r: InterestRate, sigma: Volatility, kind: OptionType) -> FinanceResult<Greeks> {
+    let (d1, d2) = d1_d2(s0, k, t, r, sigma)?;
+    let df = (-r * t).exp();
+    let (sign, itm) = match kind {
+        OptionType::Call => (1.0, normal_cdf(d2)),
+        OptionType::Put => (-1.0, normal_cdf(-d2)),
+    };
+    let carry = Greeks { theta: r * df * itm, rho: -t * df * itm, ..Greeks::default() };
+    if t < EPS_TIME || sigma < EPS_VOL {
+        return Ok(carry);
+    }
+
+    let sqrt_t = t.sqrt();
+    let sigma_sqrt_t = sigma * sqrt_t;
+    let pdf = df * normal_pdf(d2);
+    let dd2_dt = (2.0 * (r - 0.5 * sigma * sigma) * t / sigma_sqrt_t - d2) / (2.0 * t);
+
+    let delta = pdf / (s0 * sigma_sqrt_t);
+    let diffusion = Greeks {
+        delta,
+        gamma: -pdf * d1 / (s0 * s0 * sigma * sigma * t),
+        vega: -pdf * d1 / sigma,
+        theta: -pdf * dd2_dt,
+        rho: pdf * sqrt_t / sigma,
+        vanna: pdf * (d1 * d2 - 1.0) / (s0 * sigma * sigma * sqrt_t),
+        volga: pdf * (d1 + d2 - d1 * d1 * d2) / (sigma * sigma),
+        charm: delta * (r + d2 * dd2_dt + 0.5 / t),
+        speed: pdf / (sigma * sigma * t * s0.powi(3)) * ((d1 * d2 - 1.0) / sigma_sqrt_t + 2.0 * d1),
+    };
+    Ok(combine(&carry, 1.0, &diffusion, sign))
+}
+
+/// Griegas analíticas de la asset-or-nothing (mismas convenciones que [`Greeks`]).
+///
+/// En el límite `t < EPS_TIME` o `sigma < EPS_VOL` se devuelve la delta del
+/// límite y el resto de griegas a cero.
+pub fn asset_or_nothing_greeks(s0: SpotPrice, k: StrikePrice, t: TimeToMaturity, r: InterestRate, sigma: Volatility, kind: OptionType) -> FinanceResult<Greeks> {
+    let (d1, d2) = d1_d2(s0, k, t, r, sigma)?;
+    let (sign, itm) = match kind {
+        OptionType::Call => (1.0, normal_cdf(d1)),
+        OptionType::Put => (-1.0, normal_cdf(-d1)),
+    };
+    let carry = Greeks { delta: itm, ..Greeks::default() };
+    if t < EPS_TIME || sigma < EPS_VOL {
+        return Ok(carry);
+    }
+
+    let sqrt_t = t.sqrt();
+    let sigma_sqrt_t = sigma * sqrt_t;
+    let pdf = normal_pdf(d1);
+    let dd1_dt = (2.0 * (r + 0.5 * sigma * sigma) * t / sigma_sqrt_t - d1) / (2.0 * t);
+
+    let diffusion = Greeks {
+        delta: pdf / sigma_sqrt_t,
+        gamma: -pdf * d2 / (s0 * sigma * sigma * t),
+        vega: -s0 * pdf * d2 / sigma,
+        theta: -s0 * pdf * dd1_dt,
+        rho: s0 * pdf * sqrt_t / sigma,
+        vanna: -pdf * d2 / sigma + pdf * (d1 * d2 - 1.0) / (sigma * sigma * sqrt_t),
+        volga: s0 * pdf * (d1 + d2 - d1 * d2 * d2) / (sigma * sigma),
+        charm: -pdf * dd1_dt + pdf / sigma_sqrt_t * (d1 * dd1_dt + 0.5 / t),
+        speed: pdf / (sigma * sigma * t * s0 * s0) * ((d1 * d2 - 1.0) / sigma_sqrt_t + d2),
+    };
+    Ok(combine(&carry, 1.0, &diffusion, sign))
+}
+
+/// Griegas de la opción gap como combinación de asset- y cash-or-nothing.
+pub fn gap_greeks(s0: SpotPrice, k1: StrikePrice, k2: StrikePrice, t: TimeToMaturity, r: InterestRate, sigma: Volatility, kind: OptionType) -> FinanceResult<Greeks> {
+    let asset = asset_or_nothing_greeks(s0, k2, t, r, sigma, kind)?;
+    let cash = cash_or_nothing_greeks(s0, k2, t, r, sigma, kind)?;
+    Ok(match kind {
+        OptionType::Call => combine(&asset, 1.0, &cash, -k1),
+        OptionType::Put => combine(&cash, k1, &asset, -1.0),
+    })
+}
+
+/// wa · a + wb · b, griega a griega.
+fn combine(a: &Greeks, wa: f64, b: &Greeks, wb: f64) -> Greeks {
+    Greeks {
+        delta: wa * a.delta + wb * b.delta,
+        gamma: wa * a.gamma + wb * b.gamma,
+        vega: wa * a.vega + wb * b.vega,
+        theta: wa * a.theta + wb * b.theta,
+        rho: wa * a.rho + wb * b.rho,
+        vanna: wa * a.vanna + wb * b.vanna,
+        volga: wa * a.volga + wb * b.volga,
+        charm: wa * a.charm + wb * b.charm,
+        speed: wa * a.speed + wb * b.speed,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::options::{option_price, option_greeks};
+
+    type Pricer = fn(f64, f64, f64, f64, f64, OptionType) -> FinanceResult<f64>;
+    type GreeksFn = fn(f64, f64, f64, f64, f64, OptionType) -> FinanceResult<Greeks>;
+
+    #[test]
+    fn vanilla_is_asset_minus_strike_times_cash() {
+        let (s0, t, r, sigma) = (100.0, 0.75, 0.05, 0.3);
+        for k in [80.0, 100.0, 125.0] {
+            let call = option_price(s0, k, t, r, sigma, OptionType::Call).unwrap();
+            let aon = asset_or_nothing_price(s0, k, t, r, sigma, OptionType::Call).unwrap();
+            let con = cash_or_nothing_price(s0, k, t, r, sigma, OptionType::Call).unwrap();
+            assert!((call - (aon - k * con)).abs() < 1e-12);
+
+            let put = option_price(s0, k, t, r, sigma, OptionType::Put).unwrap();
+            let aon = asset_or_nothing_price(s0, k, t, r, sigma, OptionType::Put).unwrap();
+            let con = cash_or_nothing_price(s0, k, t, r, sigma, OptionType::Put).unwrap();
+            assert!((put - (k * con - aon)).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn gap_greeks_with_equal_strikes_are_vanilla_greeks() {
+        let (s0, k, t, r, sigma) = (100.0, 105.0, 0.5, 0.04, 0.25);
+        for kind in [OptionType::Call, OptionType::Put] {
+            let gap = gap_greeks(s0, k, k, t, r, sigma, kind).unwrap();
+            let vanilla = option_greeks(s0, k, t, r, sigma, kind).unwrap();
+            let pairs = [
+                (gap.delta, vanilla.delta), (gap.gamma, vanilla.gamma), (gap.vega, vanilla.vega),
+                (gap.theta, vanilla.theta), (gap.rho, vanilla.rho), (gap.vanna, vanilla.vanna),
+                (gap.volga, vanilla.volga), (gap.charm, vanilla.charm), (gap.speed, vanilla.speed),
+            ];
+            for (i, (a, b)) in pairs.into_iter().enumerate() {
+                assert!((a - b).abs() < 1e-7 * b.abs().max(1.0), "{kind:?} griega {i}: {a} vs {b}");
+            }
+        }
+    }
+
+    #[test]
+    fn gap_reference_value() {
+        // Haug (2007): S=50, pago 57 si S_T > 50, T=0.5, r=9%, σ=20% → -0.0053
+        let c = gap_price(50.0, 57.0, 50.0, 0.5, 0.09, 0.20, OptionType::Call).unwrap();
+        assert!((c + 0.0053).abs() < 1e-4, "{c}");
+    }
+
+    #[test]
+    fn digital_greeks_match_finite_differences() {
+        let (s0, k, t, r, sigma) = (100.0, 95.0, 0.5, 0.04, 0.25);
+        let cases: [(Pricer, GreeksFn); 2] = [
+            (cash_or_nothing_price, cash_or_nothing_greeks),
+            (asset_or_nothing_price, asset_or_nothing_greeks),
+        ];
+        for (pricer, greeks) in cases {
+            for kind in [OptionType::Call, OptionType::Put] {
+                let g = greeks(s0, k, t, r, sigma, kind).unwrap();
+                let price = |s: f64, t: f64, r: f64, v: f64| pricer(s, k, t, r, v, kind).unwrap();
+                let at = |s: f64, t: f64, v: f64| greeks(s, k, t, r, v, kind).unwrap();
+                let (hs, hv, ht, hr) = (0.01, 1e-4, 1e-4, 1e-4);
+                // Escala relativa: las cash-or-nothing tienen griegas del orden de 1e-2
+                let scale = price(s0, t, r, sigma).max(1.0);
+
+                let fd_delta = (price(s0 + hs, t, r, sigma) - price(s0 - hs, t, r, sigma)) / (2.0 * hs);
+                assert!((g.delta - fd_delta).abs() < 1e-5 * scale);
+                let fd_gamma = (at(s0 + hs, t, sigma).delta - at(s0 - hs, t, sigma).delta) / (2.0 * hs);
+                assert!((g.gamma - fd_gamma).abs() < 1e-6 * scale);
+                let fd_vega = (price(s0, t, r, sigma + hv) - price(s0, t, r, sigma - hv)) / (2.0 * hv);
+                assert!((g.vega - fd_vega).abs() < 1e-3 * scale);
+                let fd_theta = -(price(s0, t + ht, r, sigma) - price(s0, t - ht, r, sigma)) / (2.0 * ht);
+                assert!((g.theta - fd_theta).abs() < 1e-3 * scale);
+                let fd_rho = (price(s0, t, r + hr, sigma) - price(s0, t, r - hr, sigma)) / (2.0 * hr);
+                assert!((g.rho - fd_rho).abs() < 1e-3 * scale);
+                let fd_vanna = (at(s0, t, sigma + hv).delta - at(s0, t, sigma - hv).delta) / (2.0 * hv);
+                assert!((g.vanna - fd_vanna).abs() < 1e-5 * scale);
+                let fd_volga = (at(s0, t, sigma + hv).vega - at(s0, t, sigma - hv).vega) / (2.0 * hv);
+                assert!((g.volga - fd_volga).abs() < 1e-3 * scale);
+                let fd_charm = -(at(s0, t + ht, sigma).delta - at(s0, t - ht, sigma).delta) / (2.0 * ht);
+                assert!((g.charm - fd_charm).abs() < 1e-5 * scale);
+                let fd_speed = (at(s0 + hs, t, sigma).gamma - at(s0 - hs, t, sigma).gamma) / (2.0 * hs);
+                assert!((g.speed - fd_speed).abs() < 1e-7 * scale);
+            }
+        }
+    }
+
+    #[test]
+    fn expiry_limits() {
+        assert_eq!(cash_or_nothing_price(105.0, 100.0, 0.0, 0.05, 0.2, OptionType::Call).unwrap(), 1.0);
+        assert_eq!(cash_or_nothing_price(105.0, 100.0, 0.0, 0.05, 0.2, OptionType::Put).unwrap(), 0.0);
+        assert_eq!(asset_or_nothing_price(95.0, 100.0, 0.0, 0.05, 0.2, OptionType::Put).unwrap(), 95.0);
+        let g = asset_or_nothing_greeks(105.0, 100.0, 0.0, 0.05, 0.2, OptionType::Call).unwrap();
+        assert_eq!(g, Greeks { delta: 1.0, ..Greeks::default() });
+        let g = cash_or_nothing_greeks(105.0, 100.0, 1.0, 0.05, 0.0, OptionType::Call).unwrap();
+        assert!((g.theta - 0.05 * (-0.05f64).exp()).abs() < 1e-12);
+        assert_eq!(g.delta, 0.0);
+    }
+}
@@ -0,0 +1,147 @@
+//! Dividendos discretos en efectivo para opciones sobre acciones.
+//!
+//! Modelo de dividendo "escrowed": el subyacente se descompone en la parte
+//! arriesgada S* y el valor presente de los dividendos conocidos hasta el
+//! vencimiento, y se aplica Black-Scholes sobre S*:
+//! S* = S0 - Σ D_i e^{-r t_i},  0 < t_i ≤ T
+//!
+//! Para el tratamiento exacto en árbol ver [`BinomialTree::with_dividends`].
+//!
+//! [`BinomialTree::with_dividends`]: crate::options::BinomialTree::with_dividends
+
+use crate::common::{
+    FinanceError, FinanceResult,
+    MonetaryValue, TimeInYears,
+    SpotPrice, StrikePrice, TimeToMaturity, InterestRate, Volatility,
+    OptionType
+};
+use super::black_scholes::{option_price, validate_inputs};
+
+/// Dividendo discreto conocido
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CashDividend {
+    /// Importe del dividendo por acción
+    pub amount: MonetaryValue,
+    /// Fecha ex-dividendo en años desde hoy
+    pub time: TimeInYears,
+}
+
+impl CashDividend {
+    /// Crea un dividendo validando importe y fecha.
+    ///
+    /// # Errores
+    /// * `InvalidMonetaryValue` - Si el importe es negativo o no finito
+    /// * `InvalidPeriods` - Si la fecha no es finita
+    pub fn new(amount: MonetaryValue, time: TimeInYears) -> FinanceResult<Self> {
+        let dividend = CashDividend { amount, time };
+        validate_dividends(&[dividend])?;
+        Ok(dividend)
+    }
+}
+
+/// Validación del calendario de dividendos.
+pub(crate) fn validate_dividends(dividends: &[CashDividend]) -> FinanceResult<()> {
+    for dividend in dividends {
+        if !dividend.amount.is_finite() || dividend.amount < 0.0 {
+            return Err(FinanceError::InvalidMonetaryValue);
+        }
+        if !dividend.time.is_finite() {
+            return Err(FinanceError::InvalidPeriods);
+        }
+    }
+    Ok(())
+}
+
+/// Valor presente de los dividendos con fecha ex-dividendo en (0, T].
+pub fn dividends_present_value(dividends: &[CashDividend], t: TimeToMaturity, r: InterestRate) -> FinanceResult<MonetaryValue> {
+    validate_dividends(dividends)?;
+    Ok(dividends
+        .iter()
+        .filter(|d| d.time > 0.0 && d.time <= t)
+        .map(|d| d.amount * (-r * d.time).exp())
+        .sum())
+}
+
+/// Precio "escrowed" del subyacente: S* = S0 - VP(dividendos).
+///
+/// # Errores
+/// * `InvalidMonetaryValue` - Si los dividendos agotan el valor del subyacente
+pub fn escrowed_spot(s0: SpotPrice, dividends: &[CashDividend], t: TimeToMaturity, r: InterestRate) -> FinanceResult<SpotPrice> {
+    let spot = s0 - dividends_present_value(dividends, t, r)?;
+    if spot <= 0.0 {
+        return Err(FinanceError::InvalidMonetaryValue);
+    }
+    Ok(spot)
+}
+
+/// Precio Black-Scholes de una opción europea con dividendos discretos
+/// (modelo escrowed). `sigma` es la volatilidad de la parte arriesgada S*.
+pub fn escrowed_option_price(s0: SpotPrice, k: StrikePrice, t: TimeToMaturity, r: InterestRate, sigma: Volatility, dividends: &[CashDividend], kind: OptionType) -> FinanceResult<f64> {
+    validate_inputs(s0, k, t, r, sigma)?;
+    let spot = escrowed_spot(s0, dividends, t, r)?;
+    option_price(spot, k, t, r, sigma, kind)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::options::{BinomialTree, call_price, put_price};
+
+    fn schedule() -> Vec<CashDividend> {
+        vec![
+            CashDividend::new(2.0, 0.25).unwrap(),
+            CashDividend::new(2.0, 0.75).unwrap(),
+        ]
+    }
+
+    #[test]
+    fn escrowed_spot_discounts_dividends() {
+        let spot = escrowed_spot(100.0, &schedule(), 1.0, 0.05).unwrap();
+        let expected = 100.0 - 2.0 * (-0.0125f64).exp() - 2.0 * (-0.0375f64).exp();
+        assert!((spot - expected).abs() < 1e-12);
+    }
+
+    #[test]
+    fn dividends_outside_horizon_are_ignored() {
+        let divs = vec![
+            CashDividend::new(3.0, -0.1).unwrap(),
+            CashDividend::new(3.0, 1.5).unwrap(),
+        ];
+        assert_eq!(dividends_present_value(&divs, 1.0, 0.05).unwrap(), 0.0);
+        let c = escrowed_option_price(100.0, 100.0, 1.0, 0.05, 0.2, &divs, OptionType::Call).unwrap();
+        assert_eq!(c, call_price(100.0, 100.0, 1.0, 0.05, 0.2).unwrap());
+    }
+
+    #[test]
+    fn escrowed_put_call_parity() {
+        let (s0, k, t, r, sigma) = (100.0, 95.0, 1.0, 0.05, 0.25);
+        let divs = schedule();
+        let c = escrowed_option_price(s0, k, t, r, sigma, &divs, OptionType::Call).unwrap();
+        let p = escrowed_option_price(s0, k, t, r, sigma, &divs, OptionType::Put).unwrap();
+        let pv = dividends_present_value(&divs, t, r).unwrap();
+        assert!((c - p - (s0 - pv - k * (-r * t).exp())).abs() < 1e-10);
+        assert!((p - put_price(s0 - pv, k, t, r, sigma).unwrap()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn escrowed_and_tree_are_comparable() {
+        let (s0, k, t, r, sigma) = (100.0, 100.0, 1.0, 0.05, 0.25);
+        let divs = schedule();
+        let tree = BinomialTree::new(800).unwrap().with_dividends(divs.clone()).unwrap();
+        for kind in [OptionType::Call, OptionType::Put] {
+            let escrowed = escrowed_option_price(s0, k, t, r, sigma, &divs, kind).unwrap();
+            let exact = tree.price(s0, k, t, r, sigma, kind).unwrap();
+            // La volatilidad del precio real es mayor que la de S*, luego el árbol vale algo más
+            assert!(exact > escrowed);
+            assert!((exact - escrowed) / escrowed < 0.05);
+        }
+    }
+
+    #[test]
+    fn invalid_dividends() {
+        assert_eq!(CashDividend::new(-1.0, 0.5).unwrap_err(), FinanceError::InvalidMonetaryValue);
+        assert_eq!(CashDividend::new(1.0, f64::NAN).unwrap_err(), FinanceError::InvalidPeriods);
+        let huge = vec![CashDividend::new(150.0, 0.5).unwrap()];
+        assert_eq!(escrowed_spot(100.0, &huge, 1.0, 0.05).unwrap_err(), FinanceError::InvalidMonetaryValue);
+    }
+}
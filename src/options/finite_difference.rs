@@ -0,0 +1,702 @@
+//! Diferencias finitas para la EDP de Black-Scholes en una dimensión.
+//!
+//! En x = ln S y tiempo hasta vencimiento τ la ecuación tiene coeficientes
+//! constantes:
+//! V_τ = ½σ² V_xx + ν V_x - r V, con ν = r - q - σ²/2,
+//! que se discretiza con diferencias centradas en una malla uniforme en x y
+//! el esquema θ: (I - θΔτ L) V^{n+1} = (I + (1 - θ)Δτ L) V^n, con θ = 0
+//! (explícito), θ = 1 (implícito) o θ = ½ (Crank-Nicolson). El suavizado de
+//! Rannacher sustituye los primeros pasos de Crank-Nicolson por dos
+//! semipasos implícitos cada uno, lo que amortigua las oscilaciones que
+//! genera la discontinuidad de la derivada del payoff en el strike.
+//!
+//! Contornos: en los extremos lejanos se impone el valor asintótico
+//! max(φ(S e^{-qτ} - K e^{-rτ}), 0) (y el payoff si hay ejercicio
+//! anticipado). Con barrera, un extremo de la malla coincide con ella y se
+//! impone el rebate; las knock-in europeas se obtienen por paridad in-out
+//! sobre la misma malla.
+//!
+//! Ejercicio americano: el problema de complementariedad lineal de cada paso
+//! se resuelve con SOR proyectado (PSOR) o con el método de penalización de
+//! Forsyth y Vetzal (2002). Las fechas bermudas se imponen proyectando sobre
+//! el payoff tras el paso correspondiente.
+
+use crate::common::{
+    FinanceError, FinanceResult,
+    DividendYield,
+    SpotPrice, StrikePrice, TimeToMaturity, InterestRate, Volatility,
+    OptionType, ExerciseStyle, BarrierType
+};
+use crate::num::solve_tridiagonal;
+use super::black_scholes::{option_price_dividend, validate_inputs, EPS_TIME, EPS_VOL};
+use super::binomial::{LatticeGreeks, exercise_steps, validate_exercise};
+use super::trinomial::LatticeBarrier;
+
+/// Anchura por defecto de la malla a cada lado del spot, en desviaciones
+/// típicas σ√T del logaritmo del precio.
+const DEFAULT_WIDTH: f64 = 5.0;
+/// Pasos de Crank-Nicolson sustituidos por defecto en el arranque de Rannacher.
+const DEFAULT_RANNACHER_STEPS: usize = 2;
+/// Factor de relajación de PSOR.
+const PSOR_OMEGA: f64 = 1.2;
+/// Tolerancia (relativa) de las iteraciones de PSOR y penalización.
+const LCP_TOL: f64 = 1e-10;
+/// Máximo de iteraciones de PSOR por paso.
+const PSOR_MAX_ITER: usize = 10_000;
+/// Máximo de iteraciones de penalización por paso.
+const PENALTY_MAX_ITER: usize = 100;
+
+/// Esquema de discretización temporal
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FdScheme {
+    /// Euler explícito (θ = 0): condicionalmente estable
+    Explicit,
+    /// Euler implícito (θ = 1): primer orden en el tiempo
+    Implicit,
+    /// Crank-Nicolson (θ = ½): segundo orden en el tiempo
+    CrankNicolson,
+}
+
+impl FdScheme {
+    fn theta(self) -> f64 {
+        match self {
+            FdScheme::Explicit => 0.0,
+            FdScheme::Implicit => 1.0,
+            FdScheme::CrankNicolson => 0.5,
+        }
+    }
+}
+
+/// Método para la restricción de ejercicio americano en esquemas implícitos
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EarlyExerciseMethod {
+    /// SOR proyectado
+    Psor,
+    /// Penalización (Forsyth-Vetzal)
+    Penalty,
+}
+
+/// Configuración del solver de diferencias finitas
+#[derive(Debug, Clone, PartialEq)]
+pub struct FiniteDifference {
+    /// Esquema temporal
+    pub scheme: FdScheme,
+    /// Número aproximado de intervalos en x (con barrera se ajusta para que
+    /// la barrera y el spot caigan sobre nodos)
+    pub space_steps: usize,
+    /// Número de pasos temporales
+    pub time_steps: usize,
+    /// Anchura de la malla a cada lado del spot, en unidades de σ√T
+    pub width: f64,
+    /// Pasos iniciales de Crank-Nicolson sustituidos por semipasos implícitos
+    pub rannacher_steps: usize,
+    /// Estilo de ejercicio
+    pub exercise: ExerciseStyle,
+    /// Método para el ejercicio americano
+    pub early_exercise: EarlyExerciseMethod,
+    /// Barrera opcional (observación continua)
+    pub barrier: Option<LatticeBarrier>,
+    /// Rendimiento por dividendo continuo del subyacente
+    pub dividend_yield: DividendYield,
+}
+
+/// Superficie de precios V(t_i, S_j) sobre la malla completa.
+///
+/// `values[i][j]` es el valor en el instante de calendario `times[i]`
+/// (`times[0] = 0`) con subyacente `spots[j]`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FdSurface {
+    /// Nodos del subyacente, equiespaciados en logaritmos
+    pub spots: Vec<f64>,
+    /// Instantes de calendario, de hoy al vencimiento
+    pub times: Vec<f64>,
+    /// Valores por instante y nodo
+    pub values: Vec<Vec<f64>>,
+}
+
+impl FdSurface {
+    /// Valor en el instante `times[i]` interpolando cuadráticamente en ln S.
+    ///
+    /// # Errores
+    /// * `InvalidInput` - Si `i` no es un instante de la malla o `s` cae fuera
+    pub fn value(&self, i: usize, s: SpotPrice) -> FinanceResult<f64> {
+        Ok(self.local_quadratic(i, s)?.0)
+    }
+
+    /// Precio, delta, gamma y theta en `s` hoy.
+    ///
+    /// Δ = V_x / S, Γ = (V_xx - V_x) / S² con las derivadas en x de la
+    /// interpolación cuadrática, y Θ = (V(Δt, S) - V(0, S)) / Δt.
+    ///
+    /// # Errores
+    /// * `InvalidInput` - Si `s` cae fuera de la malla
+    pub fn greeks(&self, s: SpotPrice) -> FinanceResult<LatticeGreeks> {
+        let (price, v_x, v_xx) = self.local_quadratic(0, s)?;
+        let theta = (self.value(1, s)? - price) / self.times[1];
+        Ok(LatticeGreeks {
+            price,
+            delta: v_x / s,
+            gamma: (v_xx - v_x) / (s * s),
+            theta,
+        })
+    }
+
+    /// (V, V_x, V_xx) de la parábola por los tres nodos más cercanos a `s`.
+    fn local_quadratic(&self, i: usize, s: SpotPrice) -> FinanceResult<(f64, f64, f64)> {
+        let m = self.spots.len() - 1;
+        if i >= self.values.len() || !(s >= self.spots[0] && s <= self.spots[m]) {
+            return Err(FinanceError::InvalidInput);
+        }
+        let dx = (self.spots[1] / self.spots[0]).ln();
+        let x = (s / self.spots[0]).ln();
+        let j = ((x / dx).round() as usize).clamp(1, m - 1);
+        let u = x / dx - j as f64;
+        let v = &self.values[i];
+        let first = 0.5 * (v[j + 1] - v[j - 1]);
+        let second = v[j + 1] - 2.0 * v[j] + v[j - 1];
+        Ok((
+            v[j] + u * first + 0.5 * u * u * second,
+            (first + u * second) / dx,
+            second / (dx * dx),
+        ))
+    }
+}
+
+/// Malla uniforme en x = ln S: nodos x_min + j Δx, j = 0..=m.
+struct Grid {
+    x_min: f64,
+    dx: f64,
+    m: usize,
+    n: usize,
+    dt: f64,
+}
+
+impl Grid {
+    fn spot(&self, j: usize) -> f64 {
+        (self.x_min + j as f64 * self.dx).exp()
+    }
+}
+
+impl FiniteDifference {
+    /// Crea un solver Crank-Nicolson europeo con suavizado de Rannacher,
+    /// `space_steps` intervalos en ln S y `time_steps` pasos temporales.
+    ///
+    /// # Errores
+    /// * `InvalidPeriods` - Si `space_steps < 4` o `time_steps == 0`
+    pub fn new(space_steps: usize, time_steps: usize) -> FinanceResult<Self> {
+        if space_steps < 4 || time_steps == 0 {
+            return Err(FinanceError::InvalidPeriods);
+        }
+        Ok(FiniteDifference {
+            scheme: FdScheme::CrankNicolson,
+            space_steps,
+            time_steps,
+            width: DEFAULT_WIDTH,
+            rannacher_steps: DEFAULT_RANNACHER_STEPS,
+            exercise: ExerciseStyle::European,
+            early_exercise: EarlyExerciseMethod::Psor,
+            barrier: None,
+            dividend_yield: 0.0,
+        })
+    }
+
+    /// Cambia el esquema temporal.
+    pub fn with_scheme(mut self, scheme: FdScheme) -> Self {
+        self.scheme = scheme;
+        self
+    }
+
+    /// Cambia la anchura de la malla (en desviaciones típicas σ√T).
+    ///
+    /// # Errores
+    /// * `InvalidInput` - Si no es positiva
+    pub fn with_width(mut self, width: f64) -> FinanceResult<Self> {
+        if !width.is_finite() || width <= 0.0 {
+            return Err(FinanceError::InvalidInput);
+        }
+        self.width = width;
+        Ok(self)
+    }
+
+    /// Cambia el número de pasos de arranque de Rannacher (0 lo desactiva).
+    pub fn with_rannacher_steps(mut self, steps: usize) -> Self {
+        self.rannacher_steps = steps;
+        self
+    }
+
+    /// Cambia el estilo de ejercicio.
+    pub fn with_exercise(mut self, exercise: ExerciseStyle) -> FinanceResult<Self> {
+        validate_exercise(&exercise)?;
+        self.exercise = exercise;
+        Ok(self)
+    }
+
+    /// Cambia el método para el ejercicio americano.
+    pub fn with_early_exercise(mut self, method: EarlyExerciseMethod) -> Self {
+        self.early_exercise = method;
+        self
+    }
+
+    /// Añade una barrera de observación continua.
+    ///
+    /// # Errores
+    /// * `InvalidMonetaryValue` - Si el nivel no es positivo o el rebate es negativo
+    pub fn with_barrier(mut self, barrier: LatticeBarrier) -> FinanceResult<Self> {
+        if !barrier.level.is_finite() || barrier.level <= 0.0 || !barrier.rebate.is_finite() || barrier.rebate < 0.0 {
+            return Err(FinanceError::InvalidMonetaryValue);
+        }
+        self.barrier = Some(barrier);
+        Ok(self)
+    }
+
+    /// Cambia el rendimiento por dividendo continuo.
+    ///
+    /// # Errores
+    /// * `InvalidInterestRate` - Si no es finito
+    pub fn with_dividend_yield(mut self, q: DividendYield) -> FinanceResult<Self> {
+        if !q.is_finite() {
+            return Err(FinanceError::InvalidInterestRate);
+        }
+        self.dividend_yield = q;
+        Ok(self)
+    }
+
+    /// Precio hoy en `s0`.
+    ///
+    /// # Ejemplos
+    /// ```
+    /// use quant_finance::{OptionType, put_price};
+    /// use quant_finance::options::FiniteDifference;
+    ///
+    /// let solver = FiniteDifference::new(400, 200).unwrap();
+    /// let p = solver.price(100.0, 105.0, 1.0, 0.05, 0.2, OptionType::Put).unwrap();
+    /// assert!((p - put_price(100.0, 105.0, 1.0, 0.05, 0.2).unwrap()).abs() < 1e-3);
+    /// ```
+    ///
+    /// # Errores
+    /// Los de [`FiniteDifference::solve`].
+    pub fn price(&self, s0: SpotPrice, k: StrikePrice, t: TimeToMaturity, r: InterestRate, sigma: Volatility, kind: OptionType) -> FinanceResult<f64> {
+        validate_inputs(s0, k, t, r, sigma)?;
+        if t < EPS_TIME {
+            return Ok(self.expiry_value(s0, k, kind));
+        }
+        // Barrera ya tocada: rebate en las knock-out, vanilla en las knock-in
+        if let Some(b) = self.barrier.filter(|b| b.barrier_type.is_breached(s0, b.level)) {
+            if !b.barrier_type.is_knock_in() {
+                return Ok(b.rebate);
+            }
+            return FiniteDifference { barrier: None, ..self.clone() }.price(s0, k, t, r, sigma, kind);
+        }
+        self.solve(s0, k, t, r, sigma, kind)?.value(0, s0)
+    }
+
+    /// Precio, delta, gamma y theta en `s0` a partir de la superficie.
+    ///
+    /// # Errores
+    /// Los de [`FiniteDifference::solve`].
+    pub fn greeks(&self, s0: SpotPrice, k: StrikePrice, t: TimeToMaturity, r: InterestRate, sigma: Volatility, kind: OptionType) -> FinanceResult<LatticeGreeks> {
+        self.solve(s0, k, t, r, sigma, kind)?.greeks(s0)
+    }
+
+    /// Superficie completa de precios.
+    ///
+    /// # Errores
+    /// * `InvalidInterestRate` - Si `sigma` es nula o negativa
+    /// * `InvalidPeriods` - Si `t < EPS_TIME`
+    /// * `InvalidInput` - Si el esquema explícito es inestable con esta malla,
+    ///   si el spot ya ha tocado la barrera o si se pide una knock-in no europea
+    /// * `NoConvergence` - Si PSOR o la penalización no convergen
+    pub fn solve(&self, s0: SpotPrice, k: StrikePrice, t: TimeToMaturity, r: InterestRate, sigma: Volatility, kind: OptionType) -> FinanceResult<FdSurface> {
+        validate_inputs(s0, k, t, r, sigma)?;
+        if t < EPS_TIME {
+            return Err(FinanceError::InvalidPeriods);
+        }
+        if sigma < EPS_VOL {
+            return Err(FinanceError::InvalidInterestRate);
+        }
+        let q = self.dividend_yield;
+        let grid = self.grid(s0, t, sigma)?;
+        let operator = Operator::new(&grid, r, q, sigma);
+        if self.scheme == FdScheme::Explicit && !operator.is_stable(grid.dt) {
+            return Err(FinanceError::InvalidInput);
+        }
+        let payoff = |s: f64| kind.payoff(s, k);
+        let early = self.exercise != ExerciseStyle::European;
+        let far_field = |tau: f64, s: f64| {
+            let forward = match kind {
+                OptionType::Call => s * (-q * tau).exp() - k * (-r * tau).exp(),
+                OptionType::Put => k * (-r * tau).exp() - s * (-q * tau).exp(),
+            };
+            if early { forward.max(payoff(s)) } else { forward.max(0.0) }
+        };
+
+        let values = match self.barrier {
+            None => self.roll_back(&grid, &operator, &payoff, &far_field, &far_field)?,
+            Some(b) if !b.barrier_type.is_knock_in() => {
+                let rebate = |_: f64, _: f64| b.rebate;
+                if b.barrier_type.is_down() {
+                    self.roll_back(&grid, &operator, &payoff, &rebate, &far_field)?
+                } else {
+                    self.roll_back(&grid, &operator, &payoff, &far_field, &rebate)?
+                }
+            }
+            Some(b) => {
+                if early {
+                    return Err(FinanceError::InvalidInput);
+                }
+                // Paridad in-out: KI = vanilla - KO(sin rebate) + rebate × no-touch.
+                // La vanilla usa como contorno en la barrera su valor exacto.
+                let exact = |tau: f64, s: f64| {
+                    if tau < EPS_TIME { payoff(s) } else { option_price_dividend(s, k, tau, r, q, sigma, kind).unwrap_or(0.0) }
+                };
+                let zero = |_: f64, _: f64| 0.0;
+                let no_touch = |tau: f64, _: f64| b.rebate * (-r * tau).exp();
+                let rebate_payoff = |_: f64| b.rebate;
+                let (vanilla, out, touch) = if b.barrier_type.is_down() {
+                    (
+                        self.roll_back(&grid, &operator, &payoff, &exact, &far_field)?,
+                        self.roll_back(&grid, &operator, &payoff, &zero, &far_field)?,
+                        self.roll_back(&grid, &operator, &rebate_payoff, &zero, &no_touch)?,
+                    )
+                } else {
+                    (
+                        self.roll_back(&grid, &operator, &payoff, &far_field, &exact)?,
+                        self.roll_back(&grid, &operator, &payoff, &far_field, &zero)?,
+                        self.roll_back(&grid, &operator, &rebate_payoff, &no_touch, &zero)?,
+                    )
+                };
+                vanilla.iter().zip(&out).zip(&touch)
+                    .map(|((v, o), n)| v.iter().zip(o).zip(n).map(|((v, o), n)| v - o + n).collect())
+                    .collect()
+            }
+        };
+
+        Ok(FdSurface {
+            spots: (0..=grid.m).map(|j| grid.spot(j)).collect(),
+            times: (0..=grid.n).map(|i| i as f64 * grid.dt).collect(),
+            values,
+        })
+    }
+
+    /// Valor cuando ya no queda tiempo.
+    fn expiry_value(&self, s0: SpotPrice, k: StrikePrice, kind: OptionType) -> f64 {
+        match self.barrier {
+            Some(b) if b.barrier_type.is_breached(s0, b.level) => {
+                if b.barrier_type.is_knock_in() { kind.payoff(s0, k) } else { b.rebate }
+            }
+            Some(b) if b.barrier_type.is_knock_in() => b.rebate,
+            _ => kind.payoff(s0, k),
+        }
+    }
+
+    /// Malla con el spot sobre un nodo y, si hay barrera, la barrera en un
+    /// extremo (el spot solo cae entre nodos si está a menos de medio Δx).
+    fn grid(&self, s0: SpotPrice, t: TimeToMaturity, sigma: Volatility) -> FinanceResult<Grid> {
+        let x0 = s0.ln();
+        let half_width = self.width * sigma * t.sqrt();
+        let target = 2.0 * half_width / self.space_steps as f64;
+        let (x_min, dx, m) = match self.barrier {
+            None => {
+                let j0 = self.space_steps.div_ceil(2);
+                (x0 - j0 as f64 * target, target, 2 * j0)
+            }
+            Some(b) => {
+                if b.barrier_type.is_breached(s0, b.level) {
+                    return Err(FinanceError::InvalidInput);
+                }
+                let distance = (x0 - b.level.ln()).abs();
+                // Con la barrera a menos de medio paso se conserva Δx y el
+                // spot queda entre nodos (interpolación cuadrática)
+                let near = (distance / target).round() as usize;
+                let dx = if near == 0 { target } else { distance / near as f64 };
+                let m = near.max(1) + ((half_width / dx).ceil() as usize).max(1);
+                match b.barrier_type {
+                    BarrierType::DownAndOut | BarrierType::DownAndIn => (b.level.ln(), dx, m),
+                    BarrierType::UpAndOut | BarrierType::UpAndIn => (b.level.ln() - m as f64 * dx, dx, m),
+                }
+            }
+        };
+        let n = self.time_steps;
+        Ok(Grid { x_min, dx, m, n, dt: t / n as f64 })
+    }
+
+    /// Integra hacia atrás desde el vencimiento; devuelve los valores por
+    /// instante de calendario (el primero es hoy).
+    ///
+    /// `lower` y `upper` dan el valor en los extremos en función de (τ, S).
+    /// El mismo payoff se usa al vencimiento y en las fechas de ejercicio.
+    fn roll_back<P, L, U>(&self, grid: &Grid, operator: &Operator, payoff: &P, lower: &L, upper: &U) -> FinanceResult<Vec<Vec<f64>>>
+    where
+        P: Fn(f64) -> f64,
+        L: Fn(f64, f64) -> f64,
+        U: Fn(f64, f64) -> f64,
+    {
+        let m = grid.m;
+        let spots: Vec<f64> = (0..=m).map(|j| grid.spot(j)).collect();
+        let intrinsic: Vec<f64> = spots.iter().map(|&s| payoff(s)).collect();
+
+        let exercisable = exercise_steps(&self.exercise, grid.n as f64 * grid.dt, grid.n);
+        let american = self.exercise == ExerciseStyle::American;
+
+        let mut layers = Vec::with_capacity(grid.n + 1);
+        let mut v = intrinsic.clone();
+        v[0] = lower(0.0, spots[0]);
+        v[m] = upper(0.0, spots[m]);
+        layers.push(v.clone());
+
+        for step in 0..grid.n {
+            // Paso de calendario al que se llega: i = n - (step + 1)
+            let i = grid.n - step - 1;
+            let constraint = if exercisable[i] { Some(intrinsic.as_slice()) } else { None };
+            let sub_steps = if self.scheme == FdScheme::CrankNicolson && step < self.rannacher_steps {
+                vec![(1.0, 0.5); 2]
+            } else {
+                vec![(self.scheme.theta(), 1.0)]
+            };
+            let mut tau = step as f64 * grid.dt;
+            for (theta, fraction) in sub_steps {
+                let h = fraction * grid.dt;
+                tau += h;
+                let boundary = (lower(tau, spots[0]), upper(tau, spots[m]));
+                v = operator.step(&v, theta, h, boundary, constraint.filter(|_| american), self.early_exercise)?;
+            }
+            if let Some(g) = constraint.filter(|_| !american) {
+                for (value, g) in v.iter_mut().zip(g).take(m).skip(1) {
+                    *value = value.max(*g);
+                }
+            }
+            layers.push(v.clone());
+        }
+        layers.reverse();
+        Ok(layers)
+    }
+}
+
+/// Operador discreto L V_j = a V_{j-1} + b V_j + c V_{j+1} (constante en la malla).
+struct Operator {
+    a: f64,
+    b: f64,
+    c: f64,
+}
+
+impl Operator {
+    fn new(grid: &Grid, r: InterestRate, q: DividendYield, sigma: Volatility) -> Self {
+        let variance = 0.5 * sigma * sigma / (grid.dx * grid.dx);
+        let drift = (r - q - 0.5 * sigma * sigma) / (2.0 * grid.dx);
+        Operator { a: variance - drift, b: -2.0 * variance - r, c: variance + drift }
+    }
+
+    /// Condición de estabilidad (y monotonía) del esquema explícito con paso `dt`
+    fn is_stable(&self, dt: f64) -> bool {
+        self.a >= 0.0 && self.c >= 0.0 && 1.0 + dt * self.b >= 0.0
+    }
+
+    /// Un paso del esquema θ de longitud `h`; `boundary` son los valores
+    /// nuevos en los extremos y `constraint` el obstáculo americano, si lo hay.
+    fn step(&self, v: &[f64], theta: f64, h: f64, boundary: (f64, f64), constraint: Option<&[f64]>, method: EarlyExerciseMethod) -> FinanceResult<Vec<f64>> {
+        let m = v.len() - 1;
+        let mut next = vec![0.0; m + 1];
+        next[0] = boundary.0;
+        next[m] = boundary.1;
+        let explicit: Vec<f64> = (1..m)
+            .map(|j| v[j] + (1.0 - theta) * h * (self.a * v[j - 1] + self.b * v[j] + self.c * v[j + 1]))
+            .collect();
+
+        if theta == 0.0 {
+            next[1..m].copy_from_slice(&explicit);
+            if let Some(g) = constraint {
+                for (value, g) in next.iter_mut().zip(g).take(m).skip(1) {
+                    *value = value.max(*g);
+                }
+            }
+            return Ok(next);
+        }
+
+        let size = m - 1;
+        let lower = vec![-theta * h * self.a; size];
+        let diag = vec![1.0 - theta * h * self.b; size];
+        let upper = vec![-theta * h * self.c; size];
+        let mut rhs = explicit;
+        rhs[0] += theta * h * self.a * next[0];
+        rhs[size - 1] += theta * h * self.c * next[m];
+
+        let interior = match constraint {
+            None => solve_tridiagonal(&lower, &diag, &upper, &rhs)?,
+            Some(g) => {
+                let g = &g[1..m];
+                let start: Vec<f64> = v[1..m].iter().zip(g).map(|(v, g)| v.max(*g)).collect();
+                match method {
+                    EarlyExerciseMethod::Psor => psor(&lower, &diag, &upper, &rhs, g, start)?,
+                    EarlyExerciseMethod::Penalty => penalty(&lower, &diag, &upper, &rhs, g, start)?,
+                }
+            }
+        };
+        next[1..m].copy_from_slice(&interior);
+        Ok(next)
+    }
+}
+
+/// SOR proyectado para A x = b con x ≥ g, A tridiagonal.
+fn psor(lower: &[f64], diag: &[f64], upper: &[f64], rhs: &[f64], g: &[f64], mut x: Vec<f64>) -> FinanceResult<Vec<f64>> {
+    let n = x.len();
+    let scale = rhs.iter().fold(1.0f64, |m, b| m.max(b.abs()));
+    for _ in 0..PSOR_MAX_ITER {
+        let mut change = 0.0f64;
+        for j in 0..n {
+            let left = if j > 0 { lower[j] * x[j - 1] } else { 0.0 };
+            let right = if j + 1 < n { upper[j] * x[j + 1] } else { 0.0 };
+            let gauss_seidel = (rhs[j] - left - right) / diag[j];
+            let updated = (x[j] + PSOR_OMEGA * (gauss_seidel - x[j])).max(g[j]);
+            change = change.max((updated - x[j]).abs());
+            x[j] = updated;
+        }
+        if change <= LCP_TOL * scale {
+            return Ok(x);
+        }
+    }
+    Err(FinanceError::NoConvergence)
+}
+
+/// Penalización: (A + P) x = b + P g, con P_jj = 1/tol donde x_j < g_j,
+/// iterando hasta que el conjunto activo no cambia.
+fn penalty(lower: &[f64], diag: &[f64], upper: &[f64], rhs: &[f64], g: &[f64], mut x: Vec<f64>) -> FinanceResult<Vec<f64>> {
+    let large = 1.0 / LCP_TOL;
+    let mut active: Vec<bool> = x.iter().zip(g).map(|(x, g)| x <= g).collect();
+    for _ in 0..PENALTY_MAX_ITER {
+        let penalized_diag: Vec<f64> = diag.iter().zip(&active).map(|(d, a)| if *a { d + large } else { *d }).collect();
+        let penalized_rhs: Vec<f64> = rhs.iter().zip(g).zip(&active).map(|((b, g), a)| if *a { b + large * g } else { *b }).collect();
+        x = solve_tridiagonal(lower, &penalized_diag, upper, &penalized_rhs)?;
+        let next: Vec<bool> = x.iter().zip(g).map(|(x, g)| x < g).collect();
+        if next == active {
+            return Ok(x.iter().zip(g).map(|(x, g)| x.max(*g)).collect());
+        }
+        active = next;
+    }
+    Err(FinanceError::NoConvergence)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::options::{call_price, put_price, option_greeks, BinomialTree, BarrierOption};
+
+    #[test]
+    fn schemes_converge_to_black_scholes() {
+        let (s0, k, t, r, sigma) = (100.0, 105.0, 1.0, 0.05, 0.2);
+        let bs_call = call_price(s0, k, t, r, sigma).unwrap();
+        let bs_put = put_price(s0, k, t, r, sigma).unwrap();
+        for (scheme, time_steps, tol) in [(FdScheme::CrankNicolson, 200, 2e-3), (FdScheme::Implicit, 2000, 5e-3), (FdScheme::Explicit, 2000, 5e-3)] {
+            let solver = FiniteDifference::new(400, time_steps).unwrap().with_scheme(scheme);
+            let c = solver.price(s0, k, t, r, sigma, OptionType::Call).unwrap();
+            let p = solver.price(s0, k, t, r, sigma, OptionType::Put).unwrap();
+            assert!((c - bs_call).abs() < tol, "{scheme:?}: {c} vs {bs_call}");
+            assert!((p - bs_put).abs() < tol, "{scheme:?}: {p} vs {bs_put}");
+        }
+    }
+
+    #[test]
+    fn surface_greeks_match_black_scholes() {
+        let (s0, k, t, r, sigma) = (100.0, 100.0, 1.0, 0.05, 0.2);
+        let solver = FiniteDifference::new(400, 400).unwrap();
+        for kind in [OptionType::Call, OptionType::Put] {
+            let bs = option_greeks(s0, k, t, r, sigma, kind).unwrap();
+            let surface = solver.solve(s0, k, t, r, sigma, kind).unwrap();
+            let g = surface.greeks(s0).unwrap();
+            assert!((g.delta - bs.delta).abs() < 1e-3);
+            assert!((g.gamma - bs.gamma).abs() < 1e-4);
+            assert!((g.theta - bs.theta).abs() < 1e-2);
+            // Fuera de los nodos también se interpola
+            let off_node = surface.value(0, 103.3).unwrap();
+            assert!((off_node - option_price_dividend(103.3, k, t, r, 0.0, sigma, kind).unwrap()).abs() < 2e-3);
+        }
+    }
+
+    #[test]
+    fn rannacher_removes_gamma_oscillations() {
+        // Pasos temporales largos frente a Δx: Crank-Nicolson puro oscila en el strike
+        let (s0, k, t, r, sigma) = (100.0, 100.0, 0.25, 0.05, 0.3);
+        let bs = option_greeks(s0, k, t, r, sigma, OptionType::Call).unwrap();
+        let smoothed = FiniteDifference::new(800, 20).unwrap();
+        let plain = smoothed.clone().with_rannacher_steps(0);
+        let g_smoothed = smoothed.greeks(s0, k, t, r, sigma, OptionType::Call).unwrap();
+        let g_plain = plain.greeks(s0, k, t, r, sigma, OptionType::Call).unwrap();
+        assert!((g_smoothed.gamma - bs.gamma).abs() < 1e-4);
+        assert!((g_plain.gamma - bs.gamma).abs() > 0.1);
+    }
+
+    #[test]
+    fn american_put_matches_binomial() {
+        let (s0, k, t, r, sigma) = (50.0, 50.0, 5.0 / 12.0, 0.10, 0.40);
+        let tree = BinomialTree::new(2001).unwrap().with_exercise(ExerciseStyle::American).unwrap();
+        let reference = tree.price(s0, k, t, r, sigma, OptionType::Put).unwrap();
+        let base = FiniteDifference::new(400, 400).unwrap().with_exercise(ExerciseStyle::American).unwrap();
+        let p_psor = base.price(s0, k, t, r, sigma, OptionType::Put).unwrap();
+        let p_penalty = base.clone().with_early_exercise(EarlyExerciseMethod::Penalty).price(s0, k, t, r, sigma, OptionType::Put).unwrap();
+        let p_explicit = base.clone().with_scheme(FdScheme::Explicit).with_rannacher_steps(0);
+        let p_explicit = FiniteDifference { time_steps: 4000, ..p_explicit }.price(s0, k, t, r, sigma, OptionType::Put).unwrap();
+        assert!((p_psor - reference).abs() < 3e-3, "{p_psor} vs {reference}");
+        assert!((p_penalty - p_psor).abs() < 1e-6);
+        assert!((p_explicit - reference).abs() < 3e-3, "{p_explicit} vs {reference}");
+        assert!(p_psor > put_price(s0, k, t, r, sigma).unwrap());
+    }
+
+    #[test]
+    fn bermudan_lies_between_european_and_american() {
+        let (s0, k, t, r, sigma) = (100.0, 110.0, 1.0, 0.06, 0.2);
+        let base = FiniteDifference::new(300, 300).unwrap();
+        let bermudan = base.clone().with_exercise(ExerciseStyle::Bermudan(vec![0.25, 0.5, 0.75])).unwrap();
+        let american = base.clone().with_exercise(ExerciseStyle::American).unwrap();
+        let p_eu = base.price(s0, k, t, r, sigma, OptionType::Put).unwrap();
+        let p_be = bermudan.price(s0, k, t, r, sigma, OptionType::Put).unwrap();
+        let p_am = american.price(s0, k, t, r, sigma, OptionType::Put).unwrap();
+        assert!(p_eu < p_be && p_be < p_am);
+    }
+
+    #[test]
+    fn barriers_match_closed_form() {
+        let (s0, k, t, r, sigma) = (100.0, 100.0, 1.0, 0.05, 0.25);
+        let solver = FiniteDifference::new(400, 400).unwrap();
+        let cases = [
+            (BarrierType::DownAndOut, 90.0, 0.0, OptionType::Call),
+            (BarrierType::UpAndOut, 120.0, 2.0, OptionType::Put),
+            (BarrierType::DownAndIn, 90.0, 3.0, OptionType::Call),
+            (BarrierType::UpAndIn, 120.0, 0.0, OptionType::Put),
+        ];
+        for (barrier_type, level, rebate, kind) in cases {
+            let expected = BarrierOption::new(barrier_type, level).unwrap().with_rebate(rebate).unwrap()
+                .price(s0, k, t, r, sigma, kind).unwrap();
+            let barrier = LatticeBarrier { barrier_type, level, rebate };
+            let v = solver.clone().with_barrier(barrier).unwrap().price(s0, k, t, r, sigma, kind).unwrap();
+            assert!((v - expected).abs() < 5e-3, "{barrier_type:?}: {v} vs {expected}");
+        }
+        // Barrera ya tocada
+        let touched = LatticeBarrier { barrier_type: BarrierType::DownAndOut, level: 105.0, rebate: 1.5 };
+        assert_eq!(solver.clone().with_barrier(touched).unwrap().price(s0, k, t, r, sigma, OptionType::Call).unwrap(), 1.5);
+
+        // Barrera pegada al spot: la malla mantiene su tamaño
+        for (barrier_type, level) in [(BarrierType::DownAndOut, 99.9999), (BarrierType::UpAndOut, 100.0001)] {
+            let near = LatticeBarrier { barrier_type, level, rebate: 0.0 };
+            let surface = solver.clone().with_barrier(near).unwrap().solve(s0, k, t, r, sigma, OptionType::Call).unwrap();
+            assert!(surface.spots.len() < 500);
+            let expected = BarrierOption::new(barrier_type, level).unwrap().price(s0, k, t, r, sigma, OptionType::Call).unwrap();
+            assert!((surface.value(0, s0).unwrap() - expected).abs() < 5e-3);
+        }
+    }
+
+    #[test]
+    fn invalid_configuration() {
+        assert_eq!(FiniteDifference::new(3, 10).unwrap_err(), FinanceError::InvalidPeriods);
+        assert_eq!(FiniteDifference::new(100, 0).unwrap_err(), FinanceError::InvalidPeriods);
+        assert_eq!(FiniteDifference::new(100, 10).unwrap().with_width(0.0).unwrap_err(), FinanceError::InvalidInput);
+        // Explícito con Δt ≫ Δx²/σ²: inestable
+        let unstable = FiniteDifference::new(400, 10).unwrap().with_scheme(FdScheme::Explicit);
+        assert_eq!(unstable.price(100.0, 100.0, 1.0, 0.05, 0.2, OptionType::Call).unwrap_err(), FinanceError::InvalidInput);
+        let solver = FiniteDifference::new(100, 10).unwrap();
+        assert_eq!(solver.solve(100.0, 100.0, 0.0, 0.05, 0.2, OptionType::Call).unwrap_err(), FinanceError::InvalidPeriods);
+        let knock_in = LatticeBarrier { barrier_type: BarrierType::DownAndIn, level: 80.0, rebate: 0.0 };
+        let american = solver.with_exercise(ExerciseStyle::American).unwrap().with_barrier(knock_in).unwrap();
+        assert_eq!(american.price(100.0, 100.0, 1.0, 0.05, 0.2, OptionType::Put).unwrap_err(), FinanceError::InvalidInput);
+        let surface = FiniteDifference::new(100, 10).unwrap().solve(100.0, 100.0, 1.0, 0.05, 0.2, OptionType::Call).unwrap();
+        assert_eq!(surface.value(0, 1e6).unwrap_err(), FinanceError::InvalidInput);
+    }
+}
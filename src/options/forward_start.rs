@@ -0,0 +1,114 @@
+//! Opciones forward-start (Rubinstein, 1990).
+//!
+//! El strike se fija en t1 como α·S_{t1}. Por homogeneidad de Black-Scholes,
+//! el valor en t1 es S_{t1} · BS(1, α, T - t1), de modo que
+//! V = S e^{-q t1} · BS(1, α, T - t1; r, q, σ)
+//! con BS el precio de Merton (call o put) sobre un spot unitario.
+
+use crate::common::{
+    FinanceError, FinanceResult,
+    DividendYield,
+    SpotPrice, TimeToMaturity, InterestRate, Volatility,
+    OptionType
+};
+use super::black_scholes::{option_price_dividend, validate_inputs};
+
+/// Opción forward-start europea
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ForwardStartOption {
+    /// Fecha en la que se fija el strike (t1)
+    pub start: TimeToMaturity,
+    /// Strike como fracción del spot en `start` (α; 1 es at-the-money)
+    pub moneyness: f64,
+    /// Rendimiento por dividendo continuo del subyacente
+    pub dividend_yield: DividendYield,
+}
+
+impl ForwardStartOption {
+    /// Crea una forward-start sin dividendos.
+    ///
+    /// # Errores
+    /// * `InvalidPeriods` - Si `start` es negativo o no finito
+    /// * `InvalidInput` - Si `moneyness` no es positivo
+    pub fn new(start: TimeToMaturity, moneyness: f64) -> FinanceResult<Self> {
+        if !start.is_finite() || start < 0.0 {
+            return Err(FinanceError::InvalidPeriods);
+        }
+        if !moneyness.is_finite() || moneyness <= 0.0 {
+            return Err(FinanceError::InvalidInput);
+        }
+        Ok(ForwardStartOption { start, moneyness, dividend_yield: 0.0 })
+    }
+
+    /// Cambia el rendimiento por dividendo continuo.
+    ///
+    /// # Errores
+    /// * `InvalidInterestRate` - Si no es finito
+    pub fn with_dividend_yield(mut self, q: DividendYield) -> FinanceResult<Self> {
+        if !q.is_finite() {
+            return Err(FinanceError::InvalidInterestRate);
+        }
+        self.dividend_yield = q;
+        Ok(self)
+    }
+
+    /// Precio de la forward-start con vencimiento `t`.
+    ///
+    /// # Errores
+    /// * `InvalidPeriods` - Si el strike se fija después del vencimiento
+    pub fn price(&self, s0: SpotPrice, t: TimeToMaturity, r: InterestRate, sigma: Volatility, kind: OptionType) -> FinanceResult<f64> {
+        validate_inputs(s0, self.moneyness * s0, t, r, sigma)?;
+        if self.start > t {
+            return Err(FinanceError::InvalidPeriods);
+        }
+        let q = self.dividend_yield;
+        let unit = option_price_dividend(1.0, self.moneyness, t - self.start, r, q, sigma, kind)?;
+        Ok(s0 * (-q * self.start).exp() * unit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn haug_reference_value() {
+        // Haug (2007): S=60, α=1.1, t1=0.25, T=1, r=8%, b=4%, σ=30% → 4.4064
+        let option = ForwardStartOption::new(0.25, 1.1).unwrap().with_dividend_yield(0.04).unwrap();
+        let price = option.price(60.0, 1.0, 0.08, 0.30, OptionType::Call).unwrap();
+        assert!((price - 4.4064).abs() < 1e-3, "{price}");
+    }
+
+    #[test]
+    fn immediate_start_is_vanilla() {
+        let option = ForwardStartOption::new(0.0, 0.95).unwrap().with_dividend_yield(0.01).unwrap();
+        for kind in [OptionType::Call, OptionType::Put] {
+            let price = option.price(80.0, 0.5, 0.03, 0.2, kind).unwrap();
+            let vanilla = option_price_dividend(80.0, 76.0, 0.5, 0.03, 0.01, 0.2, kind).unwrap();
+            assert!((price - vanilla).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn forward_start_parity() {
+        // C - P = S e^{-q t1} (e^{-q τ} - α e^{-r τ})
+        let (s0, t, r, q, sigma) = (100.0, 1.5, 0.04, 0.02, 0.25);
+        let option = ForwardStartOption::new(0.5, 1.05).unwrap().with_dividend_yield(q).unwrap();
+        let c = option.price(s0, t, r, sigma, OptionType::Call).unwrap();
+        let p = option.price(s0, t, r, sigma, OptionType::Put).unwrap();
+        let tau = t - 0.5;
+        let forward = s0 * (-q * 0.5f64).exp() * ((-q * tau).exp() - 1.05 * (-r * tau).exp());
+        assert!((c - p - forward).abs() < 1e-10);
+        // El precio es lineal en el spot
+        let doubled = option.price(2.0 * s0, t, r, sigma, OptionType::Call).unwrap();
+        assert!((doubled - 2.0 * c).abs() < 1e-10);
+    }
+
+    #[test]
+    fn invalid_configuration() {
+        assert_eq!(ForwardStartOption::new(-0.1, 1.0).unwrap_err(), FinanceError::InvalidPeriods);
+        assert_eq!(ForwardStartOption::new(0.5, 0.0).unwrap_err(), FinanceError::InvalidInput);
+        let option = ForwardStartOption::new(0.5, 1.0).unwrap();
+        assert_eq!(option.price(100.0, 0.25, 0.05, 0.2, OptionType::Call).unwrap_err(), FinanceError::InvalidPeriods);
+    }
+}
@@ -0,0 +1,565 @@
+//! Valoración por transformada de Fourier para cualquier modelo con función
+//! característica conocida.
+//!
+//! Inversión directa (Heston, 1993): para un strike suelto la call es
+//! C = ½(S₀e^{-qT} - Ke^{-rT}) + e^{-rT}/π ∫₀^∞ Re[e^{-iu ln K}(φ(u - i) - Kφ(u))/(iu)] du
+//! y la put se obtiene por paridad. La integral se evalúa con paneles de
+//! Gauss-Legendre hasta que su contribución es despreciable.
+//!
+//! Carr y Madan (1999): la call amortiguada e^{αk} C(k), k = ln K, es
+//! integrable y su transformada es
+//! ψ(v) = e^{-rT} φ(v - (α+1)i) / (α² + α - v² + i(2α + 1)v),
+//! de modo que C(k) = e^{-αk}/π ∫₀^∞ Re[e^{-ivk} ψ(v)] dv. Discretizando con
+//! la regla de Simpson en v_j = jη y k_u = k₀ + uλ, λη = 2π/N, la suma es
+//! una FFT que da los precios de toda una malla de log-strikes a la vez.
+//!
+//! Método COS de Fang y Oosterlee (2008): la densidad de y = ln(S_T/K) se
+//! desarrolla en serie de cosenos sobre [a, b] = x + c₁ ± L√(c₂ + √c₄),
+//! x = ln(S₀/K), con c_n los cumulantes de ln(S_T/S₀). Para la put,
+//! P = e^{-rT} Σ'_k Re[φ(kπ/(b-a)) e^{ikπ(x-a)/(b-a)}] V_k
+//! V_k = 2K/(b-a) [ψ_k(a, 0) - χ_k(a, 0)]
+//! con χ_k y ψ_k los coeficientes de coseno de e^y y de 1 en [a, 0]. La call
+//! se obtiene por paridad, que es más estable que truncar el pago de la call.
+//!
+//! Las funciones características se expresan para ln S_T, como en el resto
+//! de modelos del crate.
+
+use crate::common::{
+    FinanceError, FinanceResult,
+    DividendYield,
+    SpotPrice, StrikePrice, TimeToMaturity, InterestRate, Volatility,
+    OptionType, VolQuote
+};
+use crate::num::{fft, levenberg_marquardt, Complex, GaussLegendre};
+use super::black_scholes::{
+    option_greeks_dividend, option_price_dividend, implied_volatility_dividend,
+    validate_inputs, EPS_TIME
+};
+
+/// Paso de las diferencias finitas para los cumulantes.
+const CUMULANT_STEP: f64 = 1e-2;
+/// Puntos de Gauss-Legendre por panel en la integral de inversión.
+const QUADRATURE_POINTS: usize = 32;
+/// Anchura de cada panel de integración.
+const QUADRATURE_PANEL: f64 = 10.0;
+/// Máximo de paneles (u ≤ 2000).
+const QUADRATURE_MAX_PANELS: usize = 200;
+
+/// Modelo con función característica conocida
+pub trait CharacteristicFunction {
+    /// Función característica E[e^{iu ln S_T}] para u complejo, bajo la
+    /// medida neutral al riesgo.
+    fn characteristic_function(&self, u: Complex, s0: SpotPrice, t: TimeToMaturity, r: InterestRate, q: DividendYield) -> Complex;
+
+    /// Cumulantes (c₁, c₂, c₄) de ln(S_T/S₀).
+    ///
+    /// Por defecto se obtienen por diferencias finitas centradas de cinco
+    /// puntos de la función generadora de cumulantes K(v) = ln φ(-iv).
+    fn cumulants(&self, t: TimeToMaturity, r: InterestRate, q: DividendYield) -> (f64, f64, f64) {
+        let h = CUMULANT_STEP;
+        let k = |v: f64| self.characteristic_function(Complex::new(0.0, -v), 1.0, t, r, q).ln().re;
+        let (k0, k1, km1, k2, km2) = (k(0.0), k(h), k(-h), k(2.0 * h), k(-2.0 * h));
+        let c1 = (8.0 * (k1 - km1) - (k2 - km2)) / (12.0 * h);
+        let c2 = (16.0 * (k1 + km1) - (k2 + km2) - 30.0 * k0) / (12.0 * h * h);
+        let c4 = (k2 - 4.0 * k1 + 6.0 * k0 - 4.0 * km1 + km2) / h.powi(4);
+        (c1, c2, c4)
+    }
+}
+
+/// Modelo de Black-Scholes expresado por su función característica
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BlackScholesModel {
+    /// Volatilidad σ
+    pub sigma: Volatility,
+}
+
+/// Motor FFT de Carr-Madan
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CarrMadan {
+    /// Puntos de la FFT (potencia de dos)
+    pub points: usize,
+    /// Paso η en la variable de integración
+    pub spacing: f64,
+    /// Factor de amortiguamiento α
+    pub damping: f64,
+    /// Rendimiento por dividendo continuo del subyacente
+    pub dividend_yield: DividendYield,
+}
+
+/// Método COS de Fang-Oosterlee
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CosMethod {
+    /// Términos de la serie de cosenos
+    pub terms: usize,
+    /// Anchura L del intervalo de truncamiento, en unidades de √(c₂ + √c₄)
+    pub truncation: f64,
+    /// Rendimiento por dividendo continuo del subyacente
+    pub dividend_yield: DividendYield,
+}
+
+impl BlackScholesModel {
+    /// Crea el modelo.
+    ///
+    /// # Errores
+    /// * `InvalidInterestRate` - Si σ es negativa
+    pub fn new(sigma: Volatility) -> FinanceResult<Self> {
+        if !sigma.is_finite() || sigma < 0.0 {
+            return Err(FinanceError::InvalidInterestRate);
+        }
+        Ok(BlackScholesModel { sigma })
+    }
+}
+
+impl CharacteristicFunction for BlackScholesModel {
+    fn characteristic_function(&self, u: Complex, s0: SpotPrice, t: TimeToMaturity, r: InterestRate, q: DividendYield) -> Complex {
+        let variance = self.sigma * self.sigma;
+        (Complex::I * u * (s0.ln() + (r - q - 0.5 * variance) * t) - u * u * (0.5 * variance * t)).exp()
+    }
+
+    fn cumulants(&self, t: TimeToMaturity, r: InterestRate, q: DividendYield) -> (f64, f64, f64) {
+        let variance = self.sigma * self.sigma;
+        ((r - q - 0.5 * variance) * t, variance * t, 0.0)
+    }
+}
+
+impl CarrMadan {
+    /// Motor con `points` nodos, η = 0.25, α = 1.5 y sin dividendo.
+    ///
+    /// # Errores
+    /// * `InvalidPeriods` - Si `points` no es una potencia de dos mayor que 4
+    pub fn new(points: usize) -> FinanceResult<Self> {
+        if !points.is_power_of_two() || points < 8 {
+            return Err(FinanceError::InvalidPeriods);
+        }
+        Ok(CarrMadan { points, spacing: 0.25, damping: 1.5, dividend_yield: 0.0 })
+    }
+
+    /// Fija el paso η; la malla de log-strikes tiene paso 2π/(Nη).
+    ///
+    /// # Errores
+    /// * `InvalidInput` - Si η no es positivo
+    pub fn with_spacing(mut self, spacing: f64) -> FinanceResult<Self> {
+        if !spacing.is_finite() || spacing <= 0.0 {
+            return Err(FinanceError::InvalidInput);
+        }
+        self.spacing = spacing;
+        Ok(self)
+    }
+
+    /// Fija el amortiguamiento α; E[S_T^{α+1}] debe ser finito.
+    ///
+    /// # Errores
+    /// * `InvalidInput` - Si α no es positivo
+    pub fn with_damping(mut self, damping: f64) -> FinanceResult<Self> {
+        if !damping.is_finite() || damping <= 0.0 {
+            return Err(FinanceError::InvalidInput);
+        }
+        self.damping = damping;
+        Ok(self)
+    }
+
+    /// Cambia el rendimiento por dividendo continuo.
+    ///
+    /// # Errores
+    /// * `InvalidInterestRate` - Si no es finito
+    pub fn with_dividend_yield(mut self, q: DividendYield) -> FinanceResult<Self> {
+        if !q.is_finite() {
+            return Err(FinanceError::InvalidInterestRate);
+        }
+        self.dividend_yield = q;
+        Ok(self)
+    }
+
+    /// Precios de call en la malla nativa de strikes K_u = e^{k_u}, centrada
+    /// en el forward.
+    ///
+    /// # Errores
+    /// * `InvalidMonetaryValue` - Si el spot no es positivo
+    /// * `InvalidPeriods` - Si `t` no es positivo
+    pub fn grid<M: CharacteristicFunction + ?Sized>(&self, model: &M, s0: SpotPrice, t: TimeToMaturity, r: InterestRate) -> FinanceResult<Vec<(StrikePrice, f64)>> {
+        validate_inputs(s0, s0, t, r, 0.0)?;
+        if t < EPS_TIME {
+            return Err(FinanceError::InvalidPeriods);
+        }
+        let q = self.dividend_yield;
+        let (n, eta, alpha) = (self.points, self.spacing, self.damping);
+        let lambda = 2.0 * std::f64::consts::PI / (n as f64 * eta);
+        let k0 = s0.ln() + (r - q) * t - 0.5 * n as f64 * lambda;
+        let df = (-r * t).exp();
+
+        let mut data: Vec<Complex> = (0..n)
+            .map(|j| {
+                let v = j as f64 * eta;
+                let phi = model.characteristic_function(Complex::new(v, -(alpha + 1.0)), s0, t, r, q);
+                let psi = phi * df / Complex::new(alpha * alpha + alpha - v * v, (2.0 * alpha + 1.0) * v);
+                // Pesos de Simpson: η/3 (1, 4, 2, 4, ..., 2, 4)
+                let simpson = match j {
+                    0 => 1.0,
+                    j if j % 2 == 1 => 4.0,
+                    _ => 2.0,
+                } / 3.0;
+                (Complex::I * (-v * k0)).exp() * psi * (eta * simpson)
+            })
+            .collect();
+        fft(&mut data)?;
+        Ok(data.iter().enumerate()
+            .map(|(u, value)| {
+                let k = k0 + u as f64 * lambda;
+                (k.exp(), ((-alpha * k).exp() / std::f64::consts::PI * value.re).max(0.0))
+            })
+            .collect())
+    }
+
+    /// Precios en los strikes pedidos, interpolando la malla nativa con
+    /// polinomios cúbicos en log-strike. Las puts se obtienen por paridad.
+    ///
+    /// # Ejemplos
+    /// ```
+    /// use quant_finance::OptionType;
+    /// use quant_finance::options::{BlackScholesModel, CarrMadan, call_price};
+    ///
+    /// let model = BlackScholesModel::new(0.2).unwrap();
+    /// let engine = CarrMadan::new(4096).unwrap();
+    /// let prices = engine.prices(&model, 100.0, &[90.0, 100.0, 110.0], 1.0, 0.05, OptionType::Call).unwrap();
+    /// assert!((prices[1] - call_price(100.0, 100.0, 1.0, 0.05, 0.2).unwrap()).abs() < 1e-4);
+    /// ```
+    ///
+    /// # Errores
+    /// * Los de [`CarrMadan::grid`]
+    /// * `InvalidMonetaryValue` - Si algún strike no es positivo
+    /// * `InvalidInput` - Si algún strike cae fuera de la malla
+    pub fn prices<M: CharacteristicFunction + ?Sized>(&self, model: &M, s0: SpotPrice, strikes: &[StrikePrice], t: TimeToMaturity, r: InterestRate, kind: OptionType) -> FinanceResult<Vec<f64>> {
+        for &k in strikes {
+            validate_inputs(s0, k, t, r, 0.0)?;
+        }
+        if t < EPS_TIME {
+            return Ok(strikes.iter().map(|&k| kind.payoff(s0, k)).collect());
+        }
+        let grid = self.grid(model, s0, t, r)?;
+        let q = self.dividend_yield;
+        let k0 = grid[0].0.ln();
+        let lambda = grid[1].0.ln() - k0;
+        strikes.iter()
+            .map(|&k| {
+                let position = (k.ln() - k0) / lambda;
+                if position < 1.0 || position > (grid.len() - 3) as f64 {
+                    return Err(FinanceError::InvalidInput);
+                }
+                // Lagrange cúbico sobre los nodos i - 1, i, i + 1, i + 2
+                let i = position.floor() as usize;
+                let x = position - i as f64;
+                let weights = [
+                    -x * (x - 1.0) * (x - 2.0) / 6.0,
+                    (x + 1.0) * (x - 1.0) * (x - 2.0) / 2.0,
+                    -(x + 1.0) * x * (x - 2.0) / 2.0,
+                    (x + 1.0) * x * (x - 1.0) / 6.0,
+                ];
+                let call: f64 = weights.iter().zip(&grid[i - 1..=i + 2]).map(|(w, (_, c))| w * c).sum();
+                Ok(match kind {
+                    OptionType::Call => call.max(0.0),
+                    OptionType::Put => (call - s0 * (-q * t).exp() + k * (-r * t).exp()).max(0.0),
+                })
+            })
+            .collect()
+    }
+}
+
+impl CosMethod {
+    /// Método con `terms` términos, L = 10 y sin dividendo.
+    ///
+    /// # Errores
+    /// * `InvalidPeriods` - Si `terms` es cero
+    pub fn new(terms: usize) -> FinanceResult<Self> {
+        if terms == 0 {
+            return Err(FinanceError::InvalidPeriods);
+        }
+        Ok(CosMethod { terms, truncation: 10.0, dividend_yield: 0.0 })
+    }
+
+    /// Fija la anchura L del intervalo de truncamiento.
+    ///
+    /// # Errores
+    /// * `InvalidInput` - Si L no es positiva
+    pub fn with_truncation(mut self, truncation: f64) -> FinanceResult<Self> {
+        if !truncation.is_finite() || truncation <= 0.0 {
+            return Err(FinanceError::InvalidInput);
+        }
+        self.truncation = truncation;
+        Ok(self)
+    }
+
+    /// Cambia el rendimiento por dividendo continuo.
+    ///
+    /// # Errores
+    /// * `InvalidInterestRate` - Si no es finito
+    pub fn with_dividend_yield(mut self, q: DividendYield) -> FinanceResult<Self> {
+        if !q.is_finite() {
+            return Err(FinanceError::InvalidInterestRate);
+        }
+        self.dividend_yield = q;
+        Ok(self)
+    }
+
+    /// Precios europeos en todos los `strikes`; la función característica
+    /// se evalúa una sola vez para toda la malla.
+    ///
+    /// # Ejemplos
+    /// ```
+    /// use quant_finance::OptionType;
+    /// use quant_finance::options::{CosMethod, HestonModel};
+    ///
+    /// let model = HestonModel::new(0.04, 2.0, 0.04, 0.5, -0.7).unwrap();
+    /// let strikes = [80.0, 100.0, 120.0];
+    /// let cos = CosMethod::new(256).unwrap().prices(&model, 100.0, &strikes, 1.0, 0.03, OptionType::Put).unwrap();
+    /// let direct = model.price(100.0, 100.0, 1.0, 0.03, 0.0, OptionType::Put).unwrap();
+    /// assert!((cos[1] - direct).abs() < 1e-8);
+    /// ```
+    ///
+    /// # Errores
+    /// * `InvalidMonetaryValue` - Si el spot o algún strike no son positivos
+    /// * `InvalidPeriods` - Si `t` es negativo
+    /// * `NoConvergence` - Si los cumulantes no dan un intervalo válido
+    pub fn prices<M: CharacteristicFunction + ?Sized>(&self, model: &M, s0: SpotPrice, strikes: &[StrikePrice], t: TimeToMaturity, r: InterestRate, kind: OptionType) -> FinanceResult<Vec<f64>> {
+        for &k in strikes {
+            validate_inputs(s0, k, t, r, 0.0)?;
+        }
+        if t < EPS_TIME {
+            return Ok(strikes.iter().map(|&k| kind.payoff(s0, k)).collect());
+        }
+        let q = self.dividend_yield;
+        let (c1, c2, c4) = model.cumulants(t, r, q);
+        let half_width = self.truncation * (c2 + c4.abs().sqrt()).sqrt();
+        if !half_width.is_finite() || half_width <= 0.0 || !c1.is_finite() {
+            return Err(FinanceError::NoConvergence);
+        }
+        let width = 2.0 * half_width;
+        let (df, dq) = ((-r * t).exp(), (-q * t).exp());
+
+        // Con a = x + c₁ - L√(·), x - a = L√(·) - c₁ no depende del strike
+        let coefficients: Vec<Complex> = (0..self.terms)
+            .map(|k| {
+                let u = k as f64 * std::f64::consts::PI / width;
+                let phi = model.characteristic_function(Complex::from(u), 1.0, t, r, q);
+                let weight = if k == 0 { 0.5 } else { 1.0 };
+                phi * (Complex::I * (u * (half_width - c1))).exp() * weight
+            })
+            .collect();
+
+        Ok(strikes.iter()
+            .map(|&strike| {
+                let a = (s0 / strike).ln() + c1 - half_width;
+                let d = (a + width).min(0.0);
+                let put = if a >= 0.0 {
+                    0.0
+                } else {
+                    let series: f64 = coefficients.iter().enumerate()
+                        .map(|(k, coefficient)| {
+                            let (chi, psi) = cosine_coefficients(k, a, width, a, d);
+                            coefficient.re * (psi - chi)
+                        })
+                        .sum();
+                    (df * strike * 2.0 / width * series).max(0.0)
+                };
+                match kind {
+                    OptionType::Put => put,
+                    OptionType::Call => (put + s0 * dq - strike * df).max(0.0),
+                }
+            })
+            .collect())
+    }
+}
+
+/// Precio europeo de `model` con la fórmula de inversión directa.
+pub(crate) fn fourier_price<M: CharacteristicFunction + ?Sized>(model: &M, s0: SpotPrice, k: StrikePrice, t: TimeToMaturity, r: InterestRate, q: DividendYield, kind: OptionType) -> FinanceResult<f64> {
+    validate_inputs(s0, k, t, r, 0.0)?;
+    if !q.is_finite() {
+        return Err(FinanceError::InvalidInterestRate);
+    }
+    if t < EPS_TIME {
+        return Ok(kind.payoff(s0, k));
+    }
+    let (df, dq) = ((-r * t).exp(), (-q * t).exp());
+    let ln_k = k.ln();
+    let shifted = Complex::new(0.0, -1.0);
+    let cf = |u: Complex| model.characteristic_function(u, s0, t, r, q);
+    let integrand = |u: f64| {
+        let phi = cf(Complex::from(u));
+        let phi_shifted = cf(Complex::from(u) + shifted);
+        let kernel = (Complex::I * (-u * ln_k)).exp() / (Complex::I * u);
+        (kernel * (phi_shifted - phi * k)).re
+    };
+    let rule = GaussLegendre::new(QUADRATURE_POINTS)?;
+    let integral = rule.integrate_to_infinity(integrand, 0.0, QUADRATURE_PANEL, 1e-14 * s0, QUADRATURE_MAX_PANELS);
+    let call = 0.5 * (s0 * dq - k * df) + df * integral / std::f64::consts::PI;
+    if !call.is_finite() {
+        return Err(FinanceError::NoConvergence);
+    }
+    Ok(match kind {
+        OptionType::Call => call.max(0.0),
+        OptionType::Put => (call - s0 * dq + k * df).max(0.0),
+    })
+}
+
+/// Opción fuera del dinero respecto al forward: call si K ≥ F, put si no.
+pub(crate) fn out_of_the_money(s0: SpotPrice, k: StrikePrice, t: TimeToMaturity, r: InterestRate, q: DividendYield) -> OptionType {
+    if k >= s0 * ((r - q) * t).exp() { OptionType::Call } else { OptionType::Put }
+}
+
+/// Volatilidad implícita de Black-Scholes del precio de `model`, usando la
+/// opción fuera del dinero.
+pub(crate) fn fourier_implied_volatility<M: CharacteristicFunction + ?Sized>(model: &M, s0: SpotPrice, k: StrikePrice, t: TimeToMaturity, r: InterestRate, q: DividendYield) -> FinanceResult<Volatility> {
+    let kind = out_of_the_money(s0, k, t, r, q);
+    let price = fourier_price(model, s0, k, t, r, q, kind)?;
+    implied_volatility_dividend(price, s0, k, t, r, q, kind)
+}
+
+/// Calibra un modelo a cotizaciones de volatilidad implícita; devuelve el
+/// modelo, el RMSE en volatilidad y las iteraciones.
+///
+/// Levenberg-Marquardt sobre los errores de precio de la opción fuera del
+/// dinero divididos por su vega de mercado, que aproximan a primer orden
+/// los errores en volatilidad. `build` construye el modelo a partir del
+/// vector de parámetros, que parte de `x0` y se acota por `bounds`. Si la
+/// volatilidad implícita del modelo no puede invertirse en una cotización,
+/// el RMSE usa su residuo.
+pub(crate) fn calibrate_to_vols<M, B>(quotes: &[VolQuote], s0: SpotPrice, r: InterestRate, q: DividendYield, x0: &[f64], (lower, upper): (&[f64], &[f64]), build: B) -> FinanceResult<(M, f64, usize)>
+where
+    M: CharacteristicFunction,
+    B: Fn(&[f64]) -> FinanceResult<M>,
+{
+    if quotes.len() < x0.len() {
+        return Err(FinanceError::InvalidInput);
+    }
+    let mut targets = Vec::with_capacity(quotes.len());
+    for quote in quotes {
+        if !quote.volatility.is_finite() || quote.volatility <= 0.0 || quote.maturity < EPS_TIME {
+            return Err(FinanceError::InvalidInput);
+        }
+        let kind = out_of_the_money(s0, quote.strike, quote.maturity, r, q);
+        let price = option_price_dividend(s0, quote.strike, quote.maturity, r, q, quote.volatility, kind)?;
+        let vega = option_greeks_dividend(s0, quote.strike, quote.maturity, r, q, quote.volatility, kind)?.vega;
+        targets.push((kind, price, vega.max(1e-8)));
+    }
+
+    let residuals = |p: &[f64]| -> FinanceResult<Vec<f64>> {
+        let model = build(p)?;
+        quotes.iter().zip(&targets)
+            .map(|(quote, (kind, price, vega))| {
+                Ok((fourier_price(&model, s0, quote.strike, quote.maturity, r, q, *kind)? - price) / vega)
+            })
+            .collect()
+    };
+    let fit = levenberg_marquardt(residuals, x0, lower, upper, 200, 1e-12)?;
+    let model = build(&fit.x)?;
+
+    let squared: f64 = quotes.iter().zip(&fit.residuals)
+        .map(|(quote, residual)| {
+            fourier_implied_volatility(&model, s0, quote.strike, quote.maturity, r, q)
+                .map_or(residual * residual, |vol| (vol - quote.volatility).powi(2))
+        })
+        .sum();
+    Ok((model, (squared / quotes.len() as f64).sqrt(), fit.iterations))
+}
+
+/// (χ_k, ψ_k): coeficientes de coseno de e^y y de 1 sobre [c, d] ⊂ [a, a + width].
+fn cosine_coefficients(k: usize, a: f64, width: f64, c: f64, d: f64) -> (f64, f64) {
+    let w = k as f64 * std::f64::consts::PI / width;
+    let (angle_c, angle_d) = (w * (c - a), w * (d - a));
+    let (exp_c, exp_d) = (c.exp(), d.exp());
+    let chi = (angle_d.cos() * exp_d - angle_c.cos() * exp_c + w * (angle_d.sin() * exp_d - angle_c.sin() * exp_c)) / (1.0 + w * w);
+    let psi = if k == 0 { d - c } else { (angle_d.sin() - angle_c.sin()) / w };
+    (chi, psi)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::options::{call_price, put_price, HestonModel, BatesModel, LognormalJumps, MertonJumpDiffusion};
+
+    const STRIKES: [f64; 7] = [60.0, 80.0, 95.0, 100.0, 105.0, 120.0, 150.0];
+
+    #[test]
+    fn numerical_cumulants_match_merton() {
+        // ln(S_T/S₀) = difusión + Σ saltos: los cumulantes son aditivos
+        let (t, r, q, sigma) = (2.0, 0.05, 0.01, 0.2);
+        let jumps = LognormalJumps::new(0.8, -0.12, 0.18).unwrap();
+        let (lambda_t, mu, delta) = (jumps.intensity * t, jumps.mean, jumps.std_dev);
+        let (c1, c2, c4) = MertonJumpDiffusion::new(sigma, jumps).unwrap().cumulants(t, r, q);
+        let diffusion = BlackScholesModel::new(sigma).unwrap().cumulants(t, r, q);
+        assert!((c1 - (diffusion.0 + lambda_t * (mu - jumps.compensator()))).abs() < 1e-8);
+        assert!((c2 - (diffusion.1 + lambda_t * (mu * mu + delta * delta))).abs() < 1e-7);
+        let fourth = lambda_t * (mu.powi(4) + 6.0 * mu * mu * delta * delta + 3.0 * delta.powi(4));
+        assert!((c4 - fourth).abs() < 1e-5, "{c4} vs {fourth}");
+    }
+
+    #[test]
+    fn cos_matches_black_scholes() {
+        let (s0, t, r, sigma) = (100.0, 0.75, 0.05, 0.25);
+        let model = BlackScholesModel::new(sigma).unwrap();
+        let cos = CosMethod::new(128).unwrap();
+        let calls = cos.prices(&model, s0, &STRIKES, t, r, OptionType::Call).unwrap();
+        let puts = cos.prices(&model, s0, &STRIKES, t, r, OptionType::Put).unwrap();
+        for ((k, c), p) in STRIKES.iter().zip(&calls).zip(&puts) {
+            assert!((c - call_price(s0, *k, t, r, sigma).unwrap()).abs() < 1e-12, "{k}");
+            assert!((p - put_price(s0, *k, t, r, sigma).unwrap()).abs() < 1e-12, "{k}");
+        }
+        // Convergencia exponencial: con pocos términos ya es preciso
+        let coarse = CosMethod::new(64).unwrap().prices(&model, s0, &STRIKES, t, r, OptionType::Call).unwrap();
+        assert!(coarse.iter().zip(&calls).all(|(a, b)| (a - b).abs() < 1e-8));
+    }
+
+    #[test]
+    fn carr_madan_matches_black_scholes() {
+        let (s0, t, r, q, sigma) = (100.0, 0.5, 0.03, 0.01, 0.2);
+        let model = BlackScholesModel::new(sigma).unwrap();
+        let engine = CarrMadan::new(4096).unwrap().with_dividend_yield(q).unwrap();
+        let calls = engine.prices(&model, s0, &STRIKES, t, r, OptionType::Call).unwrap();
+        for (k, c) in STRIKES.iter().zip(&calls) {
+            let exact = call_price(s0 * (-q * t).exp(), *k, t, r, sigma).unwrap();
+            assert!((c - exact).abs() < 1e-4, "{k}: {c} vs {exact}");
+        }
+        // Los nodos de la malla no necesitan interpolación
+        let grid = engine.grid(&model, s0, t, r).unwrap();
+        let (k, c) = grid[engine.points / 2];
+        assert!((c - call_price(s0 * (-q * t).exp(), k, t, r, sigma).unwrap()).abs() < 1e-6);
+    }
+
+    #[test]
+    fn engines_agree_on_stochastic_volatility_and_jumps() {
+        let (s0, t, r, q) = (100.0, 1.0, 0.03, 0.01);
+        let heston = HestonModel::new(0.04, 1.5, 0.06, 0.6, -0.7).unwrap();
+        let jumps = LognormalJumps::new(0.8, -0.12, 0.18).unwrap();
+        let models: [&dyn CharacteristicFunction; 3] = [
+            &heston,
+            &BatesModel::new(heston, jumps),
+            &MertonJumpDiffusion::new(0.18, jumps).unwrap(),
+        ];
+        let cos = CosMethod::new(256).unwrap().with_dividend_yield(q).unwrap();
+        let fft = CarrMadan::new(4096).unwrap().with_dividend_yield(q).unwrap();
+        for model in models {
+            let a = cos.prices(model, s0, &STRIKES, t, r, OptionType::Put).unwrap();
+            let b = fft.prices(model, s0, &STRIKES, t, r, OptionType::Put).unwrap();
+            for ((k, x), y) in STRIKES.iter().zip(&a).zip(&b) {
+                assert!((x - y).abs() < 1e-4, "{k}: {x} vs {y}");
+            }
+        }
+        let direct = heston.price(s0, 95.0, t, r, q, OptionType::Put).unwrap();
+        let cos = cos.prices(&heston, s0, &[95.0], t, r, OptionType::Put).unwrap();
+        assert!((cos[0] - direct).abs() < 1e-8);
+    }
+
+    #[test]
+    fn invalid_inputs() {
+        assert_eq!(CarrMadan::new(1000).unwrap_err(), FinanceError::InvalidPeriods);
+        assert_eq!(CarrMadan::new(4096).unwrap().with_damping(0.0).unwrap_err(), FinanceError::InvalidInput);
+        assert_eq!(CosMethod::new(0).unwrap_err(), FinanceError::InvalidPeriods);
+        assert_eq!(CosMethod::new(64).unwrap().with_truncation(-1.0).unwrap_err(), FinanceError::InvalidInput);
+        assert_eq!(CosMethod::new(64).unwrap().with_dividend_yield(f64::NAN).unwrap_err(), FinanceError::InvalidInterestRate);
+        let model = BlackScholesModel::new(0.2).unwrap();
+        let cos = CosMethod::new(64).unwrap();
+        assert_eq!(cos.prices(&model, 100.0, &[-5.0], 1.0, 0.05, OptionType::Call).unwrap_err(), FinanceError::InvalidMonetaryValue);
+        assert_eq!(cos.prices(&model, 100.0, &[90.0], 0.0, 0.05, OptionType::Call).unwrap(), vec![10.0]);
+        let engine = CarrMadan::new(64).unwrap();
+        assert_eq!(engine.prices(&model, 100.0, &[1e-9], 1.0, 0.05, OptionType::Call).unwrap_err(), FinanceError::InvalidInput);
+    }
+}
@@ -0,0 +1,348 @@
+//! Modelo de Garman-Kohlhagen (1983) para opciones europeas sobre divisas.
+//!
+//! El subyacente es el tipo de cambio S (unidades de divisa doméstica por
+//! unidad de divisa extranjera) y el tipo extranjero r_f actúa como un
+//! rendimiento por dividendo continuo:
+//! C = S e^{-r_f T} N(d1) - K e^{-r_d T} N(d2)
+//! P = K e^{-r_d T} N(-d2) - S e^{-r_f T} N(-d1)
+//! d1 = [ln(S/K) + (r_d - r_f + σ^2/2) T] / (σ √T)
+//!
+//! Además del precio incluye las convenciones de cotización de prima y de
+//! delta habituales en el mercado FX (Wystup, "FX Options and Structured
+//! Products") y la inversión delta → strike necesaria para construir la
+//! sonrisa a partir de risk reversals y butterflies.
+
+use crate::common::{
+    FinanceError, FinanceResult,
+    SpotPrice, ForwardPrice, StrikePrice, TimeToMaturity, InterestRate, Volatility,
+    OptionType
+};
+use crate::num::brent;
+use super::black_scholes::{
+    Greeks, normal_cdf, normal_pdf, normal_inv_cdf,
+    d1_d2_dividend, option_price_dividend, option_greeks_dividend, implied_volatility_dividend
+};
+
+const STRIKE_TOL: f64 = 1e-12;
+const STRIKE_MAX_ITER: usize = 200;
+
+/// Datos de mercado de un par de divisas
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FxMarket {
+    /// Tipo de cambio spot (doméstica por unidad de extranjera)
+    pub spot: SpotPrice,
+    /// Tipo de interés continuo de la divisa doméstica (r_d)
+    pub domestic_rate: InterestRate,
+    /// Tipo de interés continuo de la divisa extranjera (r_f)
+    pub foreign_rate: InterestRate,
+}
+
+impl FxMarket {
+    /// Crea un mercado FX validando las entradas.
+    ///
+    /// # Errores
+    /// * `InvalidMonetaryValue` - Si el spot no es positivo y finito
+    /// * `InvalidInterestRate` - Si algún tipo no es finito
+    pub fn new(spot: SpotPrice, domestic_rate: InterestRate, foreign_rate: InterestRate) -> FinanceResult<Self> {
+        if !spot.is_finite() || spot <= 0.0 {
+            return Err(FinanceError::InvalidMonetaryValue);
+        }
+        if !domestic_rate.is_finite() || !foreign_rate.is_finite() {
+            return Err(FinanceError::InvalidInterestRate);
+        }
+        Ok(FxMarket { spot, domestic_rate, foreign_rate })
+    }
+
+    /// Forward a plazo T: F = S e^{(r_d - r_f) T}
+    pub fn forward(&self, t: TimeToMaturity) -> ForwardPrice {
+        self.spot * ((self.domestic_rate - self.foreign_rate) * t).exp()
+    }
+}
+
+/// Convención de cotización de la prima
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FxPremiumQuote {
+    /// Divisa doméstica por unidad de nominal extranjero: V
+    DomesticPips,
+    /// Divisa extranjera por unidad de nominal doméstico: V / (S K)
+    ForeignPips,
+    /// Fracción del nominal doméstico: V / K
+    PercentDomestic,
+    /// Fracción del nominal extranjero: V / S
+    PercentForeign,
+}
+
+/// Convención de delta
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FxDeltaConvention {
+    /// Δ = ω e^{-r_f T} N(ω d1)
+    Spot,
+    /// Δ = ω N(ω d1)
+    Forward,
+    /// Δ = ω (K/S) e^{-r_d T} N(ω d2)  (prima pagada en divisa extranjera)
+    SpotPremiumAdjusted,
+    /// Δ = ω (K/F) N(ω d2)
+    ForwardPremiumAdjusted,
+}
+
+impl FxDeltaConvention {
+    fn is_premium_adjusted(self) -> bool {
+        matches!(self, FxDeltaConvention::SpotPremiumAdjusted | FxDeltaConvention::ForwardPremiumAdjusted)
+    }
+
+    fn is_spot(self) -> bool {
+        matches!(self, FxDeltaConvention::Spot | FxDeltaConvention::SpotPremiumAdjusted)
+    }
+}
+
+/// Precio Garman-Kohlhagen en pips domésticos.
+pub fn fx_option_price(market: &FxMarket, k: StrikePrice, t: TimeToMaturity, sigma: Volatility, kind: OptionType) -> FinanceResult<f64> {
+    option_price_dividend(market.spot, k, t, market.domestic_rate, market.foreign_rate, sigma, kind)
+}
+
+/// Prima en la convención de cotización indicada.
+pub fn fx_premium(market: &FxMarket, k: StrikePrice, t: TimeToMaturity, sigma: Volatility, kind: OptionType, quote: FxPremiumQuote) -> FinanceResult<f64> {
+    let price = fx_option_price(market, k, t, sigma, kind)?;
+    Ok(match quote {
+        FxPremiumQuote::DomesticPips    => price,
+        FxPremiumQuote::ForeignPips     => price / (market.spot * k),
+        FxPremiumQuote::PercentDomestic => price / k,
+        FxPremiumQuote::PercentForeign  => price / market.spot,
+    })
+}
+
+/// Griegas en pips domésticos; `rho` es la sensibilidad al tipo doméstico.
+pub fn fx_greeks(market: &FxMarket, k: StrikePrice, t: TimeToMaturity, sigma: Volatility, kind: OptionType) -> FinanceResult<Greeks> {
+    option_greeks_dividend(market.spot, k, t, market.domestic_rate, market.foreign_rate, sigma, kind)
+}
+
+/// Volatilidad implícita a partir de un precio en pips domésticos.
+pub fn fx_implied_volatility(market: &FxMarket, market_price: f64, k: StrikePrice, t: TimeToMaturity, kind: OptionType) -> FinanceResult<Volatility> {
+    implied_volatility_dividend(market_price, market.spot, k, t, market.domestic_rate, market.foreign_rate, kind)
+}
+
+/// Delta en la convención indicada.
+///
+/// La delta ajustada por prima descuenta la prima pagada en divisa extranjera:
+/// Δ_spot,pa = Δ_spot - V / S.
+pub fn fx_delta(market: &FxMarket, k: StrikePrice, t: TimeToMaturity, sigma: Volatility, kind: OptionType, convention: FxDeltaConvention) -> FinanceResult<f64> {
+    let (d1, d2) = d1_d2_dividend(market.spot, k, t, market.domestic_rate, market.foreign_rate, sigma)?;
+    let omega = match kind {
+        OptionType::Call => 1.0,
+        OptionType::Put  => -1.0,
+    };
+    let forward_delta = if convention.is_premium_adjusted() {
+        omega * k / market.forward(t) * normal_cdf(omega * d2)
+    } else {
+        omega * normal_cdf(omega * d1)
+    };
+    Ok(if convention.is_spot() { (-market.foreign_rate * t).exp() * forward_delta } else { forward_delta })
+}
+
+/// Strike correspondiente a una delta dada (inversión delta → strike).
+///
+/// Sin ajuste por prima la inversión es cerrada:
+/// K = F exp(-ω N^{-1}(ω Δ_f) σ√T + σ²T/2)
+///
+/// Con ajuste por prima se resuelve numéricamente. La delta de una call
+/// ajustada por prima no es monótona en K, así que se busca en [K_min, K_max]
+/// (Clark, "Foreign Exchange Option Pricing"), donde K_max es el strike sin
+/// ajuste y K_min el máximo de la delta, que cumple σ√T N(d2) = φ(d2).
+///
+/// # Errores
+/// * `InvalidInput` - Si la delta no es alcanzable en la convención indicada
+pub fn fx_strike_from_delta(market: &FxMarket, delta: f64, t: TimeToMaturity, sigma: Volatility, kind: OptionType, convention: FxDeltaConvention) -> FinanceResult<StrikePrice> {
+    if !delta.is_finite() || !t.is_finite() || !sigma.is_finite() || t <= 0.0 || sigma <= 0.0 {
+        return Err(FinanceError::InvalidInput);
+    }
+    let omega = match kind {
+        OptionType::Call => 1.0,
+        OptionType::Put  => -1.0,
+    };
+    let forward_delta = if convention.is_spot() { delta * (market.foreign_rate * t).exp() } else { delta };
+    if !(omega * forward_delta > 0.0 && omega * forward_delta < 1.0) {
+        return Err(FinanceError::InvalidInput);
+    }
+
+    let forward = market.forward(t);
+    let sigma_sqrt_t = sigma * t.sqrt();
+    let d1 = omega * normal_inv_cdf(omega * forward_delta);
+    let unadjusted = forward * (-d1 * sigma_sqrt_t + 0.5 * sigma_sqrt_t * sigma_sqrt_t).exp();
+    if !convention.is_premium_adjusted() {
+        return Ok(unadjusted);
+    }
+
+    let target = |k: f64| -> f64 {
+        fx_delta(market, k, t, sigma, kind, FxDeltaConvention::ForwardPremiumAdjusted)
+            .map(|d| d - forward_delta)
+            .unwrap_or(f64::NAN)
+    };
+
+    // |Δ_pa| < |Δ| para calls y |Δ_pa| > |Δ| para puts: en ambos casos K_pa < K
+    let k_max = unadjusted;
+    let k_min = match kind {
+        OptionType::Call => {
+            let d2 = brent(|d2| sigma_sqrt_t * normal_cdf(d2) - normal_pdf(d2), -10.0, 10.0, STRIKE_TOL, STRIKE_MAX_ITER)?;
+            let k_min = forward * (-d2 * sigma_sqrt_t - 0.5 * sigma_sqrt_t * sigma_sqrt_t).exp();
+            if target(k_min) < 0.0 {
+                return Err(FinanceError::InvalidInput);
+            }
+            k_min
+        }
+        OptionType::Put => {
+            let mut k_min = k_max;
+            while target(k_min) < 0.0 {
+                k_min *= 0.5;
+                if k_min < f64::EPSILON * forward {
+                    return Err(FinanceError::InvalidInput);
+                }
+            }
+            k_min
+        }
+    };
+    brent(target, k_min, k_max, STRIKE_TOL * forward, STRIKE_MAX_ITER)
+}
+
+/// Strike ATM de straddle delta-neutral (call y put con deltas opuestas).
+///
+/// Sin ajuste por prima K = F e^{σ²T/2}; con ajuste K = F e^{-σ²T/2}.
+pub fn fx_atm_dns_strike(market: &FxMarket, t: TimeToMaturity, sigma: Volatility, convention: FxDeltaConvention) -> StrikePrice {
+    let half_variance = 0.5 * sigma * sigma * t;
+    if convention.is_premium_adjusted() {
+        market.forward(t) * (-half_variance).exp()
+    } else {
+        market.forward(t) * half_variance.exp()
+    }
+}
+
+/// Volatilidades de call y put a partir de las cotizaciones ATM, risk reversal
+/// y butterfly (convención "smile strangle") para una misma delta:
+/// σ_call = σ_ATM + BF + RR/2, σ_put = σ_ATM + BF - RR/2
+///
+/// # Errores
+/// * `InvalidInterestRate` - Si alguna volatilidad resultante no es positiva
+pub fn fx_smile_vols(atm_vol: Volatility, risk_reversal: Volatility, butterfly: Volatility) -> FinanceResult<(Volatility, Volatility)> {
+    let call_vol = atm_vol + butterfly + 0.5 * risk_reversal;
+    let put_vol = atm_vol + butterfly - 0.5 * risk_reversal;
+    if !call_vol.is_finite() || !put_vol.is_finite() || call_vol <= 0.0 || put_vol <= 0.0 {
+        return Err(FinanceError::InvalidInterestRate);
+    }
+    Ok((call_vol, put_vol))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONVENTIONS: [FxDeltaConvention; 4] = [
+        FxDeltaConvention::Spot,
+        FxDeltaConvention::Forward,
+        FxDeltaConvention::SpotPremiumAdjusted,
+        FxDeltaConvention::ForwardPremiumAdjusted,
+    ];
+
+    fn eurusd() -> FxMarket {
+        FxMarket::new(1.10, 0.05, 0.03).unwrap()
+    }
+
+    #[test]
+    fn put_call_parity() {
+        let m = eurusd();
+        let (k, t, sigma) = (1.12, 0.5, 0.09);
+        let c = fx_option_price(&m, k, t, sigma, OptionType::Call).unwrap();
+        let p = fx_option_price(&m, k, t, sigma, OptionType::Put).unwrap();
+        let rhs = m.spot * (-m.foreign_rate * t).exp() - k * (-m.domestic_rate * t).exp();
+        assert!((c - p - rhs).abs() < 1e-12);
+    }
+
+    #[test]
+    fn reference_value() {
+        // S=1.6, K=1.6, T=4/12, r_d=8%, r_f=11%, σ=14.1% (valores con la CDF exacta)
+        let m = FxMarket::new(1.6, 0.08, 0.11).unwrap();
+        let c = fx_option_price(&m, 1.6, 4.0 / 12.0, 0.141, OptionType::Call).unwrap();
+        let p = fx_option_price(&m, 1.6, 4.0 / 12.0, 0.141, OptionType::Put).unwrap();
+        assert!((c - 0.042958).abs() < 1e-6);
+        assert!((p - 0.058459).abs() < 1e-6);
+    }
+
+    #[test]
+    fn premium_quote_conversions() {
+        let m = eurusd();
+        let (k, t, sigma) = (1.08, 1.0, 0.1);
+        let v = fx_premium(&m, k, t, sigma, OptionType::Call, FxPremiumQuote::DomesticPips).unwrap();
+        let pct_f = fx_premium(&m, k, t, sigma, OptionType::Call, FxPremiumQuote::PercentForeign).unwrap();
+        let pct_d = fx_premium(&m, k, t, sigma, OptionType::Call, FxPremiumQuote::PercentDomestic).unwrap();
+        let pips_f = fx_premium(&m, k, t, sigma, OptionType::Call, FxPremiumQuote::ForeignPips).unwrap();
+        assert!((pct_f * m.spot - v).abs() < 1e-14);
+        assert!((pct_d * k - v).abs() < 1e-14);
+        assert!((pips_f * m.spot * k - v).abs() < 1e-14);
+    }
+
+    #[test]
+    fn premium_adjusted_delta_subtracts_premium() {
+        let m = eurusd();
+        let (k, t, sigma) = (1.15, 0.75, 0.12);
+        for kind in [OptionType::Call, OptionType::Put] {
+            let spot = fx_delta(&m, k, t, sigma, kind, FxDeltaConvention::Spot).unwrap();
+            let spot_pa = fx_delta(&m, k, t, sigma, kind, FxDeltaConvention::SpotPremiumAdjusted).unwrap();
+            let pct_f = fx_premium(&m, k, t, sigma, kind, FxPremiumQuote::PercentForeign).unwrap();
+            assert!((spot_pa - (spot - pct_f)).abs() < 1e-12);
+            let spot_greek = fx_greeks(&m, k, t, sigma, kind).unwrap().delta;
+            assert!((spot - spot_greek).abs() < 1e-14);
+        }
+    }
+
+    #[test]
+    fn strike_from_delta_round_trip() {
+        let m = eurusd();
+        let (t, sigma) = (1.0, 0.15);
+        for convention in CONVENTIONS {
+            for (kind, delta) in [(OptionType::Call, 0.25), (OptionType::Call, 0.1), (OptionType::Put, -0.25), (OptionType::Put, -0.1)] {
+                let k = fx_strike_from_delta(&m, delta, t, sigma, kind, convention).unwrap();
+                let back = fx_delta(&m, k, t, sigma, kind, convention).unwrap();
+                assert!((back - delta).abs() < 1e-6, "{convention:?} {kind:?} Δ={delta}");
+            }
+        }
+    }
+
+    #[test]
+    fn premium_adjusted_call_delta_is_capped() {
+        // Con vol alta la delta de una call ajustada por prima no alcanza valores cercanos a 1
+        let m = eurusd();
+        let err = fx_strike_from_delta(&m, 0.95, 2.0, 0.5, OptionType::Call, FxDeltaConvention::ForwardPremiumAdjusted).unwrap_err();
+        assert_eq!(err, FinanceError::InvalidInput);
+    }
+
+    #[test]
+    fn atm_dns_is_delta_neutral() {
+        let m = eurusd();
+        let (t, sigma) = (0.5, 0.11);
+        for convention in CONVENTIONS {
+            let k = fx_atm_dns_strike(&m, t, sigma, convention);
+            let call = fx_delta(&m, k, t, sigma, OptionType::Call, convention).unwrap();
+            let put = fx_delta(&m, k, t, sigma, OptionType::Put, convention).unwrap();
+            assert!((call + put).abs() < 1e-6, "{convention:?}");
+        }
+    }
+
+    #[test]
+    fn smile_from_market_quotes() {
+        let (call_vol, put_vol) = fx_smile_vols(0.10, -0.01, 0.003).unwrap();
+        assert!((call_vol - 0.098).abs() < 1e-12);
+        assert!((put_vol - 0.108).abs() < 1e-12);
+        assert!((call_vol - put_vol - (-0.01)).abs() < 1e-12);
+
+        let m = eurusd();
+        let k_call = fx_strike_from_delta(&m, 0.25, 1.0, call_vol, OptionType::Call, FxDeltaConvention::Spot).unwrap();
+        let k_put = fx_strike_from_delta(&m, -0.25, 1.0, put_vol, OptionType::Put, FxDeltaConvention::Spot).unwrap();
+        assert!(k_put < m.forward(1.0) && m.forward(1.0) < k_call);
+
+        assert_eq!(fx_smile_vols(0.05, 0.2, 0.0).unwrap_err(), FinanceError::InvalidInterestRate);
+    }
+
+    #[test]
+    fn invalid_market() {
+        assert_eq!(FxMarket::new(0.0, 0.01, 0.01).unwrap_err(), FinanceError::InvalidMonetaryValue);
+        assert_eq!(FxMarket::new(1.0, f64::NAN, 0.01).unwrap_err(), FinanceError::InvalidInterestRate);
+    }
+}
@@ -0,0 +1,326 @@
+//! Modelo de volatilidad estocástica de Heston (1993).
+//!
+//! Bajo la medida neutral al riesgo:
+//! dS = (r - q) S dt + √v S dW₁
+//! dv = κ(θ - v) dt + ξ √v dW₂,  d⟨W₁, W₂⟩ = ρ dt
+//!
+//! Valoración semianalítica: la función característica de ln S_T en la
+//! formulación "little trap" de Albrecher et al. (2007), que evita los
+//! saltos de rama del logaritmo complejo,
+//! β = κ - ρξiu, d = √(β² + ξ²(iu + u²)), g = (β - d)/(β + d)
+//! C = (r - q)iuT + κθ/ξ² [(β - d)T - 2 ln((1 - g e^{-dT})/(1 - g))]
+//! D = (β - d)/ξ² · (1 - e^{-dT})/(1 - g e^{-dT})
+//! φ(u) = exp(iu ln S₀ + C + D v₀),
+//! se invierte con la fórmula de [`super::fourier`].
+//!
+//! Simulación: esquema QE (quadratic-exponential) de Andersen (2008) para la
+//! varianza y discretización central (γ₁ = γ₂ = ½) para el logaritmo del
+//! precio.
+//!
+//! Calibración: mínimos cuadrados (Levenberg-Marquardt) sobre los errores de
+//! precio divididos por la vega de Black-Scholes de cada cotización, que
+//! aproximan a primer orden los errores en volatilidad implícita.
+
+use crate::common::{
+    FinanceError, FinanceResult,
+    DividendYield,
+    SpotPrice, StrikePrice, TimeToMaturity, InterestRate, Volatility,
+    OptionType, VolQuote
+};
+use crate::num::{Complex, Rng};
+use super::monte_carlo::{MonteCarlo, MonteCarloResult, PathGenerator};
+use super::fourier::{CharacteristicFunction, calibrate_to_vols, fourier_implied_volatility, fourier_price};
+
+/// Umbral de ψ para pasar del esquema cuadrático al exponencial en QE.
+const QE_PSI_CRITICAL: f64 = 1.5;
+/// Cotas de (v₀, κ, θ, ξ, ρ) durante la calibración.
+const CALIBRATION_LOWER: [f64; 5] = [1e-4, 1e-3, 1e-4, 1e-3, -0.999];
+const CALIBRATION_UPPER: [f64; 5] = [4.0, 20.0, 4.0, 5.0, 0.999];
+
+/// Parámetros del modelo de Heston
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HestonModel {
+    /// Varianza inicial v₀
+    pub v0: f64,
+    /// Velocidad de reversión a la media κ
+    pub kappa: f64,
+    /// Varianza de largo plazo θ
+    pub theta: f64,
+    /// Volatilidad de la varianza ξ
+    pub xi: f64,
+    /// Correlación ρ entre precio y varianza
+    pub rho: f64,
+}
+
+/// Resultado de una calibración de Heston
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HestonCalibration {
+    /// Modelo calibrado
+    pub model: HestonModel,
+    /// Raíz del error cuadrático medio en volatilidad implícita
+    pub rmse: f64,
+    /// Iteraciones de Levenberg-Marquardt
+    pub iterations: usize,
+}
+
+impl HestonModel {
+    /// Crea el modelo validando los parámetros.
+    ///
+    /// # Errores
+    /// * `InvalidInterestRate` - Si v₀ o θ son negativas o ξ no es positiva
+    /// * `InvalidInput` - Si κ no es positiva o |ρ| ≥ 1
+    pub fn new(v0: f64, kappa: f64, theta: f64, xi: f64, rho: f64) -> FinanceResult<Self> {
+        if !v0.is_finite() || !theta.is_finite() || !xi.is_finite() || v0 < 0.0 || theta < 0.0 || xi <= 0.0 {
+            return Err(FinanceError::InvalidInterestRate);
+        }
+        if !kappa.is_finite() || kappa <= 0.0 || !rho.is_finite() || rho.abs() >= 1.0 {
+            return Err(FinanceError::InvalidInput);
+        }
+        Ok(HestonModel { v0, kappa, theta, xi, rho })
+    }
+
+    /// `true` si se cumple la condición de Feller 2κθ ≥ ξ² (la varianza no
+    /// alcanza el cero).
+    pub fn feller_satisfied(&self) -> bool {
+        2.0 * self.kappa * self.theta >= self.xi * self.xi
+    }
+
+    /// Función característica E[e^{iu ln S_T}] para u complejo. Equivale a
+    /// [`CharacteristicFunction::characteristic_function`].
+    pub fn characteristic_function(&self, u: Complex, s0: SpotPrice, t: TimeToMaturity, r: InterestRate, q: DividendYield) -> Complex {
+        CharacteristicFunction::characteristic_function(self, u, s0, t, r, q)
+    }
+
+    /// Precio europeo semianalítico.
+    ///
+    /// # Ejemplos
+    /// ```
+    /// use quant_finance::OptionType;
+    /// use quant_finance::options::HestonModel;
+    ///
+    /// let model = HestonModel::new(0.04, 2.0, 0.04, 0.5, -0.7).unwrap();
+    /// let call = model.price(100.0, 100.0, 1.0, 0.03, 0.0, OptionType::Call).unwrap();
+    /// let put = model.price(100.0, 100.0, 1.0, 0.03, 0.0, OptionType::Put).unwrap();
+    /// assert!((call - put - (100.0 - 100.0 * (-0.03f64).exp())).abs() < 1e-10);
+    /// ```
+    ///
+    /// # Errores
+    /// * `InvalidMonetaryValue` - Si el spot o el strike no son positivos
+    /// * `InvalidPeriods` - Si `t` es negativo
+    /// * `InvalidInterestRate` - Si `q` no es finito
+    pub fn price(&self, s0: SpotPrice, k: StrikePrice, t: TimeToMaturity, r: InterestRate, q: DividendYield, kind: OptionType) -> FinanceResult<f64> {
+        fourier_price(self, s0, k, t, r, q, kind)
+    }
+
+    /// Volatilidad implícita de Black-Scholes del precio de Heston, usando la
+    /// opción fuera del dinero (call si K ≥ F, put si K < F).
+    ///
+    /// # Errores
+    /// Los de [`HestonModel::price`] y los de la inversión de Black-Scholes.
+    pub fn implied_volatility(&self, s0: SpotPrice, k: StrikePrice, t: TimeToMaturity, r: InterestRate, q: DividendYield) -> FinanceResult<Volatility> {
+        fourier_implied_volatility(self, s0, k, t, r, q)
+    }
+
+    /// Valora por Monte Carlo (esquema QE) el pago `payoff` de la trayectoria
+    /// S_0, ..., S_n, con la configuración de `engine` (trayectorias, pasos,
+    /// semilla, antitéticas y dividendo).
+    ///
+    /// Si `engine` tiene variable de control, se usa la call europea de ese
+    /// strike con su precio semianalítico de Heston como valor exacto.
+    ///
+    /// # Errores
+    /// * `InvalidMonetaryValue` - Si el spot no es positivo
+    /// * `InvalidPeriods` - Si `t` es negativo
+    pub fn monte_carlo<F: Fn(&[f64]) -> f64>(&self, engine: &MonteCarlo, s0: SpotPrice, t: TimeToMaturity, r: InterestRate, payoff: F) -> FinanceResult<MonteCarloResult> {
+        engine.price_with(self, s0, t, r, payoff)
+    }
+
+    /// Calibra el modelo a cotizaciones de volatilidad implícita partiendo de
+    /// `initial`.
+    ///
+    /// # Errores
+    /// * `InvalidInput` - Si hay menos cotizaciones que parámetros (5) o
+    ///   alguna cotización no es válida
+    pub fn calibrate(quotes: &[VolQuote], s0: SpotPrice, r: InterestRate, q: DividendYield, initial: HestonModel) -> FinanceResult<HestonCalibration> {
+        let x0 = [initial.v0, initial.kappa, initial.theta, initial.xi, initial.rho];
+        let bounds = (CALIBRATION_LOWER.as_slice(), CALIBRATION_UPPER.as_slice());
+        let (model, rmse, iterations) = calibrate_to_vols(quotes, s0, r, q, &x0, bounds, |p| HestonModel::new(p[0], p[1], p[2], p[3], p[4]))?;
+        Ok(HestonCalibration { model, rmse, iterations })
+    }
+}
+
+impl PathGenerator for HestonModel {
+    /// (Z_v, U, Z_x): normal y uniforme de la varianza, normal del precio
+    type Shock = (f64, f64, f64);
+    /// Varianza v
+    type State = f64;
+
+    fn initial_state(&self) -> f64 {
+        self.v0
+    }
+
+    fn sample(&self, rng: &mut Rng, _dt: f64) -> (f64, f64, f64) {
+        (rng.normal(), rng.uniform(), rng.normal())
+    }
+
+    fn antithetic(&self, (z_v, u, z_x): (f64, f64, f64)) -> (f64, f64, f64) {
+        (-z_v, 1.0 - u, -z_x)
+    }
+
+    /// Un paso QE de la varianza y el incremento central de ln S.
+    fn log_increment(&self, v: &mut f64, (z_v, u, z_x): (f64, f64, f64), dt: f64) -> f64 {
+        let HestonModel { kappa, theta, xi, rho, .. } = *self;
+        let decay = (-kappa * dt).exp();
+        let k0 = -rho * kappa * theta / xi * dt;
+        let k1 = 0.5 * dt * (kappa * rho / xi - 0.5) - rho / xi;
+        let k2 = 0.5 * dt * (kappa * rho / xi - 0.5) + rho / xi;
+        let k3 = 0.5 * dt * (1.0 - rho * rho);
+
+        let m = theta + (*v - theta) * decay;
+        let s2 = *v * xi * xi * decay * (1.0 - decay) / kappa + theta * xi * xi * (1.0 - decay).powi(2) / (2.0 * kappa);
+        let psi = s2 / (m * m);
+        // Con Δt = 0 (o v = θ = 0) la varianza no se mueve y ψ no está definida
+        let v_next = if s2 <= 0.0 || m <= 0.0 {
+            m
+        } else if psi <= QE_PSI_CRITICAL {
+            let inv = 2.0 / psi;
+            let b2 = inv - 1.0 + inv.sqrt() * (inv - 1.0).sqrt();
+            let a = m / (1.0 + b2);
+            a * (b2.sqrt() + z_v).powi(2)
+        } else {
+            let p = (psi - 1.0) / (psi + 1.0);
+            let beta = (1.0 - p) / m;
+            if u <= p { 0.0 } else { ((1.0 - p) / (1.0 - u)).ln() / beta }
+        };
+        let increment = k0 + k1 * *v + k2 * v_next + (k3 * (*v + v_next)).sqrt() * z_x;
+        *v = v_next;
+        increment
+    }
+
+    fn call_price(&self, s0: SpotPrice, k: StrikePrice, t: TimeToMaturity, r: InterestRate, q: DividendYield) -> FinanceResult<f64> {
+        self.price(s0, k, t, r, q, OptionType::Call)
+    }
+}
+
+impl CharacteristicFunction for HestonModel {
+    fn characteristic_function(&self, u: Complex, s0: SpotPrice, t: TimeToMaturity, r: InterestRate, q: DividendYield) -> Complex {
+        let HestonModel { v0, kappa, theta, xi, rho } = *self;
+        let iu = Complex::I * u;
+        let xi2 = xi * xi;
+        let beta = kappa - rho * xi * iu;
+        let d = (beta * beta + xi2 * (iu + u * u)).sqrt();
+        let g = (beta - d) / (beta + d);
+        let decay = (-d * t).exp();
+        let c = iu * ((r - q) * t)
+            + kappa * theta / xi2 * ((beta - d) * t - 2.0 * ((1.0 - g * decay) / (1.0 - g)).ln());
+        let big_d = (beta - d) / xi2 * (1.0 - decay) / (1.0 - g * decay);
+        (iu * s0.ln() + c + big_d * v0).exp()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::options::option_price_dividend;
+
+    fn model() -> HestonModel {
+        HestonModel::new(0.04, 1.5, 0.06, 0.6, -0.7).unwrap()
+    }
+
+    #[test]
+    fn small_vol_of_vol_reduces_to_black_scholes() {
+        // Con ξ → 0 la varianza es determinista y la varianza media es
+        // θ + (v₀ - θ)(1 - e^{-κT})/(κT)
+        let (s0, t, r, q) = (100.0, 1.5, 0.04, 0.01);
+        let m = HestonModel::new(0.09, 2.0, 0.04, 1e-4, 0.0).unwrap();
+        let average = m.theta + (m.v0 - m.theta) * (1.0 - (-m.kappa * t).exp()) / (m.kappa * t);
+        for k in [70.0, 100.0, 140.0] {
+            for kind in [OptionType::Call, OptionType::Put] {
+                let heston = m.price(s0, k, t, r, q, kind).unwrap();
+                let bs = option_price_dividend(s0, k, t, r, q, average.sqrt(), kind).unwrap();
+                assert!((heston - bs).abs() < 1e-7, "{k} {kind:?}: {heston} vs {bs}");
+            }
+        }
+    }
+
+    #[test]
+    fn put_call_parity_and_negative_skew() {
+        let (s0, t, r, q) = (100.0, 1.0, 0.03, 0.01);
+        let m = model();
+        for k in [60.0, 90.0, 100.0, 125.0, 180.0] {
+            let c = m.price(s0, k, t, r, q, OptionType::Call).unwrap();
+            let p = m.price(s0, k, t, r, q, OptionType::Put).unwrap();
+            assert!((c - p - (s0 * (-q * t).exp() - k * (-r * t).exp())).abs() < 1e-9);
+        }
+        // ρ < 0: la volatilidad implícita decrece con el strike
+        let vols: Vec<f64> = [80.0, 100.0, 120.0].iter()
+            .map(|&k| m.implied_volatility(s0, k, t, r, q).unwrap())
+            .collect();
+        assert!(vols[0] > vols[1] && vols[1] > vols[2], "{vols:?}");
+        // Con ρ = 0 la sonrisa es simétrica en log-moneyness alrededor del forward
+        let symmetric = HestonModel { rho: 0.0, ..m };
+        let f = s0 * ((r - q) * t).exp();
+        let low = symmetric.implied_volatility(s0, f / 1.2, t, r, q).unwrap();
+        let high = symmetric.implied_volatility(s0, f * 1.2, t, r, q).unwrap();
+        assert!((low - high).abs() < 2e-3, "{low} vs {high}");
+    }
+
+    #[test]
+    fn qe_monte_carlo_matches_semi_analytic() {
+        let (s0, k, t, r) = (100.0, 100.0, 1.0, 0.03);
+        let m = model();
+        let exact = m.price(s0, k, t, r, 0.0, OptionType::Put).unwrap();
+        let engine = MonteCarlo::new(40_000, 50).unwrap().with_antithetic(true);
+        let result = m.monte_carlo(&engine, s0, t, r, |path| (k - path[path.len() - 1]).max(0.0)).unwrap();
+        assert!((result.price - exact).abs() < 3.0 * result.std_error + 0.02, "{result:?} vs {exact}");
+
+        // Feller no se cumple: QE sigue siendo estable
+        let rough = HestonModel::new(0.04, 0.5, 0.04, 1.0, -0.9).unwrap();
+        assert!(!rough.feller_satisfied());
+        let exact = rough.price(s0, k, t, r, 0.0, OptionType::Call).unwrap();
+        let controlled = engine.with_control_variate(110.0).unwrap();
+        let result = rough.monte_carlo(&controlled, s0, t, r, |path| (path[path.len() - 1] - k).max(0.0)).unwrap();
+        assert!((result.price - exact).abs() < 3.0 * result.std_error + 0.02, "{result:?} vs {exact}");
+    }
+
+    #[test]
+    fn monte_carlo_at_expiry_pays_intrinsic() {
+        let engine = MonteCarlo::new(1_000, 10).unwrap().with_antithetic(true);
+        let call = |path: &[f64]| (path[path.len() - 1] - 90.0).max(0.0);
+        assert!((model().monte_carlo(&engine, 100.0, 0.0, 0.03, call).unwrap().price - 10.0).abs() < 1e-12);
+        let controlled = engine.with_control_variate(95.0).unwrap();
+        assert!((model().monte_carlo(&controlled, 100.0, 0.0, 0.03, call).unwrap().price - 10.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn calibration_recovers_parameters() {
+        let (s0, r, q) = (100.0, 0.02, 0.0);
+        let truth = model();
+        let mut quotes = Vec::new();
+        for t in [0.25, 0.5, 1.0, 2.0] {
+            for k in [80.0, 90.0, 100.0, 110.0, 120.0] {
+                let volatility = truth.implied_volatility(s0, k, t, r, q).unwrap();
+                quotes.push(VolQuote { strike: k, maturity: t, volatility });
+            }
+        }
+        let initial = HestonModel::new(0.02, 3.0, 0.03, 0.3, -0.3).unwrap();
+        let calibration = HestonModel::calibrate(&quotes, s0, r, q, initial).unwrap();
+        let fitted = calibration.model;
+        assert!(calibration.rmse < 1e-5, "{calibration:?}");
+        assert!((fitted.v0 - truth.v0).abs() < 1e-3);
+        assert!((fitted.rho - truth.rho).abs() < 1e-2);
+        assert!((fitted.xi - truth.xi).abs() < 2e-2);
+    }
+
+    #[test]
+    fn invalid_parameters() {
+        assert_eq!(HestonModel::new(-0.01, 1.0, 0.04, 0.5, 0.0).unwrap_err(), FinanceError::InvalidInterestRate);
+        assert_eq!(HestonModel::new(0.04, 1.0, 0.04, 0.0, 0.0).unwrap_err(), FinanceError::InvalidInterestRate);
+        assert_eq!(HestonModel::new(0.04, 0.0, 0.04, 0.5, 0.0).unwrap_err(), FinanceError::InvalidInput);
+        assert_eq!(HestonModel::new(0.04, 1.0, 0.04, 0.5, 1.0).unwrap_err(), FinanceError::InvalidInput);
+        let m = model();
+        assert_eq!(m.price(100.0, -1.0, 1.0, 0.05, 0.0, OptionType::Call).unwrap_err(), FinanceError::InvalidMonetaryValue);
+        assert_eq!(m.price(100.0, 90.0, 0.0, 0.05, 0.0, OptionType::Put).unwrap(), 0.0);
+        assert_eq!(HestonModel::calibrate(&[], 100.0, 0.05, 0.0, m).unwrap_err(), FinanceError::InvalidInput);
+    }
+}
@@ -0,0 +1,388 @@
+//! Modelos con saltos: Merton (1976) y Bates (1996).
+//!
+//! Saltos lognormales: el logaritmo del precio salta J ~ N(μ_J, δ²) en los
+//! instantes de un proceso de Poisson de intensidad λ, y la deriva se
+//! compensa con -λκ, κ = E[e^J - 1] = e^{μ_J + δ²/2} - 1, para que el
+//! precio descontado siga siendo martingala.
+//!
+//! Merton: difusión lognormal con volatilidad σ más saltos. Condicionando al
+//! número de saltos n hasta T, el precio es una media ponderada por Poisson
+//! de precios de Black-Scholes:
+//! V = Σ_n e^{-λT}(λT)^n/n! · BS(S_n, K, T, r, σ_n)
+//! S_n = S₀ e^{-qT} e^{n(μ_J + δ²/2) - λκT},  σ_n² = σ² + nδ²/T
+//!
+//! Bates: Heston más saltos. La función característica es la de Heston
+//! multiplicada por la de los saltos compensados,
+//! exp(λT(e^{iuμ_J - δ²u²/2} - 1 - iuκ)),
+//! y se valora con la misma fórmula de inversión que Heston.
+//!
+//! Simulación: en cada paso se muestrea el número de saltos N ~ Poisson(λΔt)
+//! y su suma Nμ_J + √N δ Z; la difusión es exacta en Merton y QE en Bates.
+
+use crate::common::{
+    FinanceError, FinanceResult,
+    DividendYield,
+    SpotPrice, StrikePrice, TimeToMaturity, InterestRate, Volatility,
+    OptionType
+};
+use crate::num::{Complex, Rng};
+use super::black_scholes::{option_price, validate_inputs, EPS_TIME};
+use super::heston::HestonModel;
+use super::monte_carlo::{MonteCarlo, MonteCarloResult, PathGenerator};
+use super::fourier::{CharacteristicFunction, fourier_price};
+
+/// Peso de Poisson acumulado a partir del cual se corta la serie de Merton.
+const SERIES_TOLERANCE: f64 = 1e-15;
+/// Máximo de términos de la serie de Merton más allá de λT.
+const SERIES_MAX_TERMS: usize = 1000;
+
+/// Saltos lognormales en el logaritmo del precio
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LognormalJumps {
+    /// Intensidad λ (saltos esperados por año)
+    pub intensity: f64,
+    /// Media μ_J del salto en logaritmo
+    pub mean: f64,
+    /// Desviación típica δ del salto en logaritmo
+    pub std_dev: f64,
+}
+
+/// Modelo de difusión con saltos de Merton
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MertonJumpDiffusion {
+    /// Volatilidad de la parte difusiva σ
+    pub sigma: Volatility,
+    /// Saltos del precio
+    pub jumps: LognormalJumps,
+}
+
+/// Modelo de Bates: Heston con saltos lognormales
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BatesModel {
+    /// Parte de volatilidad estocástica
+    pub heston: HestonModel,
+    /// Saltos del precio
+    pub jumps: LognormalJumps,
+}
+
+impl LognormalJumps {
+    /// Crea los saltos validando los parámetros.
+    ///
+    /// # Errores
+    /// * `InvalidInput` - Si λ es negativa o μ_J no es finita
+    /// * `InvalidInterestRate` - Si δ es negativa
+    pub fn new(intensity: f64, mean: f64, std_dev: f64) -> FinanceResult<Self> {
+        if !intensity.is_finite() || intensity < 0.0 || !mean.is_finite() {
+            return Err(FinanceError::InvalidInput);
+        }
+        if !std_dev.is_finite() || std_dev < 0.0 {
+            return Err(FinanceError::InvalidInterestRate);
+        }
+        Ok(LognormalJumps { intensity, mean, std_dev })
+    }
+
+    /// Salto relativo medio κ = E[e^J] - 1.
+    pub fn compensator(&self) -> f64 {
+        (self.mean + 0.5 * self.std_dev * self.std_dev).exp_m1()
+    }
+
+    /// Logaritmo de la función característica de los saltos compensados
+    /// acumulados hasta `t`.
+    pub fn log_characteristic(&self, u: Complex, t: TimeToMaturity) -> Complex {
+        let iu = Complex::I * u;
+        let jump = (iu * self.mean + u * u * (-0.5 * self.std_dev * self.std_dev)).exp();
+        (jump - 1.0 - iu * self.compensator()) * (self.intensity * t)
+    }
+
+    /// Suma de los saltos de un paso de longitud `dt`.
+    pub(crate) fn sample(&self, rng: &mut Rng, dt: f64) -> f64 {
+        match rng.poisson(self.intensity * dt) {
+            0 => 0.0,
+            n => n as f64 * self.mean + (n as f64).sqrt() * self.std_dev * rng.normal(),
+        }
+    }
+}
+
+impl MertonJumpDiffusion {
+    /// Crea el modelo.
+    ///
+    /// # Errores
+    /// * `InvalidInterestRate` - Si σ es negativa
+    pub fn new(sigma: Volatility, jumps: LognormalJumps) -> FinanceResult<Self> {
+        if !sigma.is_finite() || sigma < 0.0 {
+            return Err(FinanceError::InvalidInterestRate);
+        }
+        Ok(MertonJumpDiffusion { sigma, jumps })
+    }
+
+    /// Función característica E[e^{iu ln S_T}] para u complejo. Equivale a
+    /// [`CharacteristicFunction::characteristic_function`].
+    pub fn characteristic_function(&self, u: Complex, s0: SpotPrice, t: TimeToMaturity, r: InterestRate, q: DividendYield) -> Complex {
+        CharacteristicFunction::characteristic_function(self, u, s0, t, r, q)
+    }
+
+    /// Precio europeo por la serie de Merton.
+    ///
+    /// # Ejemplos
+    /// ```
+    /// use quant_finance::OptionType;
+    /// use quant_finance::options::{LognormalJumps, MertonJumpDiffusion, option_price};
+    ///
+    /// let jumps = LognormalJumps::new(1.0, -0.1, 0.15).unwrap();
+    /// let model = MertonJumpDiffusion::new(0.2, jumps).unwrap();
+    /// let merton = model.price(100.0, 80.0, 0.25, 0.05, 0.0, OptionType::Put).unwrap();
+    /// // Los saltos a la baja encarecen las puts fuera del dinero
+    /// assert!(merton > option_price(100.0, 80.0, 0.25, 0.05, 0.2, OptionType::Put).unwrap());
+    /// ```
+    ///
+    /// # Errores
+    /// * `InvalidMonetaryValue` - Si el spot o el strike no son positivos
+    /// * `InvalidPeriods` - Si `t` es negativo
+    /// * `InvalidInterestRate` - Si `q` no es finito
+    /// * `NoConvergence` - Si la serie no converge en λT + `SERIES_MAX_TERMS` términos
+    pub fn price(&self, s0: SpotPrice, k: StrikePrice, t: TimeToMaturity, r: InterestRate, q: DividendYield, kind: OptionType) -> FinanceResult<f64> {
+        validate_inputs(s0, k, t, r, self.sigma)?;
+        if !q.is_finite() {
+            return Err(FinanceError::InvalidInterestRate);
+        }
+        if t < EPS_TIME {
+            return Ok(kind.payoff(s0, k));
+        }
+        let LognormalJumps { intensity, mean, std_dev } = self.jumps;
+        let lambda_t = intensity * t;
+        let forward_spot = s0 * (-q * t - lambda_t * self.jumps.compensator()).exp();
+        let jump_growth = mean + 0.5 * std_dev * std_dev;
+
+        // Pesos en logaritmos: e^{-λT} se anula en coma flotante si λT ≳ 745
+        let ln_lambda_t = lambda_t.ln();
+        let mut ln_factorial = 0.0;
+        let mut cumulative = 0.0;
+        let mut price = 0.0;
+        for n in 0..SERIES_MAX_TERMS + lambda_t.ceil() as usize {
+            if n > 0 {
+                ln_factorial += (n as f64).ln();
+            }
+            let weight = if n == 0 { (-lambda_t).exp() } else { (n as f64 * ln_lambda_t - lambda_t - ln_factorial).exp() };
+            if weight > 0.0 {
+                let spot = forward_spot * (n as f64 * jump_growth).exp();
+                let sigma_n = (self.sigma * self.sigma + n as f64 * std_dev * std_dev / t).sqrt();
+                price += weight * option_price(spot, k, t, r, sigma_n, kind)?;
+            }
+            cumulative += weight;
+            if cumulative > 1.0 - SERIES_TOLERANCE || (n as f64 > lambda_t && weight < SERIES_TOLERANCE) {
+                return Ok(price);
+            }
+        }
+        Err(FinanceError::NoConvergence)
+    }
+
+    /// Valora por Monte Carlo el pago `payoff` de la trayectoria S_0, ..., S_n
+    /// con la configuración de `engine`. La difusión se simula de forma exacta
+    /// y los saltos de cada paso se comparten con la trayectoria antitética.
+    ///
+    /// Si `engine` tiene variable de control, se usa la call europea de ese
+    /// strike con su precio de Merton como valor exacto.
+    ///
+    /// # Errores
+    /// * `InvalidMonetaryValue` - Si el spot no es positivo
+    /// * `InvalidPeriods` - Si `t` es negativo
+    pub fn monte_carlo<F: Fn(&[f64]) -> f64>(&self, engine: &MonteCarlo, s0: SpotPrice, t: TimeToMaturity, r: InterestRate, payoff: F) -> FinanceResult<MonteCarloResult> {
+        engine.price_with(self, s0, t, r, payoff)
+    }
+}
+
+impl PathGenerator for MertonJumpDiffusion {
+    /// (Z, suma de los saltos del paso)
+    type Shock = (f64, f64);
+    type State = ();
+
+    fn initial_state(&self) {}
+
+    fn sample(&self, rng: &mut Rng, dt: f64) -> (f64, f64) {
+        let z = rng.normal();
+        (z, self.jumps.sample(rng, dt))
+    }
+
+    fn antithetic(&self, (z, jump): (f64, f64)) -> (f64, f64) {
+        (-z, jump)
+    }
+
+    fn log_increment(&self, _state: &mut (), (z, jump): (f64, f64), dt: f64) -> f64 {
+        let drift = -self.jumps.intensity * self.jumps.compensator() - 0.5 * self.sigma * self.sigma;
+        drift * dt + self.sigma * dt.sqrt() * z + jump
+    }
+
+    fn call_price(&self, s0: SpotPrice, k: StrikePrice, t: TimeToMaturity, r: InterestRate, q: DividendYield) -> FinanceResult<f64> {
+        self.price(s0, k, t, r, q, OptionType::Call)
+    }
+}
+
+impl CharacteristicFunction for MertonJumpDiffusion {
+    fn characteristic_function(&self, u: Complex, s0: SpotPrice, t: TimeToMaturity, r: InterestRate, q: DividendYield) -> Complex {
+        let iu = Complex::I * u;
+        let variance = self.sigma * self.sigma;
+        let diffusion = iu * (s0.ln() + (r - q - 0.5 * variance) * t) - u * u * (0.5 * variance * t);
+        (diffusion + self.jumps.log_characteristic(u, t)).exp()
+    }
+}
+
+impl BatesModel {
+    /// Combina un modelo de Heston con saltos lognormales.
+    pub fn new(heston: HestonModel, jumps: LognormalJumps) -> Self {
+        BatesModel { heston, jumps }
+    }
+
+    /// Función característica E[e^{iu ln S_T}] para u complejo. Equivale a
+    /// [`CharacteristicFunction::characteristic_function`].
+    pub fn characteristic_function(&self, u: Complex, s0: SpotPrice, t: TimeToMaturity, r: InterestRate, q: DividendYield) -> Complex {
+        CharacteristicFunction::characteristic_function(self, u, s0, t, r, q)
+    }
+
+    /// Precio europeo semianalítico.
+    ///
+    /// # Errores
+    /// Los mismos que [`HestonModel::price`].
+    pub fn price(&self, s0: SpotPrice, k: StrikePrice, t: TimeToMaturity, r: InterestRate, q: DividendYield, kind: OptionType) -> FinanceResult<f64> {
+        fourier_price(self, s0, k, t, r, q, kind)
+    }
+
+    /// Valora por Monte Carlo (esquema QE más saltos) el pago `payoff` de la
+    /// trayectoria S_0, ..., S_n, con la configuración de `engine`.
+    ///
+    /// # Errores
+    /// Los mismos que [`HestonModel::monte_carlo`].
+    pub fn monte_carlo<F: Fn(&[f64]) -> f64>(&self, engine: &MonteCarlo, s0: SpotPrice, t: TimeToMaturity, r: InterestRate, payoff: F) -> FinanceResult<MonteCarloResult> {
+        engine.price_with(self, s0, t, r, payoff)
+    }
+}
+
+impl PathGenerator for BatesModel {
+    /// (shocks de Heston, suma de los saltos del paso)
+    type Shock = (<HestonModel as PathGenerator>::Shock, f64);
+    /// Varianza v
+    type State = f64;
+
+    fn initial_state(&self) -> f64 {
+        self.heston.initial_state()
+    }
+
+    fn sample(&self, rng: &mut Rng, dt: f64) -> Self::Shock {
+        let diffusion = self.heston.sample(rng, dt);
+        (diffusion, self.jumps.sample(rng, dt))
+    }
+
+    fn antithetic(&self, (diffusion, jump): Self::Shock) -> Self::Shock {
+        (self.heston.antithetic(diffusion), jump)
+    }
+
+    fn log_increment(&self, v: &mut f64, (diffusion, jump): Self::Shock, dt: f64) -> f64 {
+        let compensation = self.jumps.intensity * self.jumps.compensator() * dt;
+        self.heston.log_increment(v, diffusion, dt) + jump - compensation
+    }
+
+    fn call_price(&self, s0: SpotPrice, k: StrikePrice, t: TimeToMaturity, r: InterestRate, q: DividendYield) -> FinanceResult<f64> {
+        self.price(s0, k, t, r, q, OptionType::Call)
+    }
+}
+
+impl CharacteristicFunction for BatesModel {
+    fn characteristic_function(&self, u: Complex, s0: SpotPrice, t: TimeToMaturity, r: InterestRate, q: DividendYield) -> Complex {
+        self.heston.characteristic_function(u, s0, t, r, q) * self.jumps.log_characteristic(u, t).exp()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn jumps() -> LognormalJumps {
+        LognormalJumps::new(0.8, -0.12, 0.18).unwrap()
+    }
+
+    #[test]
+    fn merton_series_matches_fourier_inversion() {
+        let (s0, t, r, q) = (100.0, 0.5, 0.04, 0.01);
+        let model = MertonJumpDiffusion::new(0.18, jumps()).unwrap();
+        for k in [70.0, 95.0, 100.0, 130.0] {
+            for kind in [OptionType::Call, OptionType::Put] {
+                let series = model.price(s0, k, t, r, q, kind).unwrap();
+                let fourier = fourier_price(&model, s0, k, t, r, q, kind).unwrap();
+                assert!((series - fourier).abs() < 1e-12, "{k} {kind:?}: {series} vs {fourier}");
+            }
+        }
+        // Paridad put-call
+        let c = model.price(s0, 105.0, t, r, q, OptionType::Call).unwrap();
+        let p = model.price(s0, 105.0, t, r, q, OptionType::Put).unwrap();
+        assert!((c - p - (s0 * (-q * t).exp() - 105.0 * (-r * t).exp())).abs() < 1e-10);
+
+        // Intensidad alta: e^{-λT} no es representable
+        let frequent = MertonJumpDiffusion::new(0.2, LognormalJumps::new(800.0, 0.0, 0.01).unwrap()).unwrap();
+        let series = frequent.price(s0, 100.0, 1.0, r, q, OptionType::Call).unwrap();
+        let fourier = fourier_price(&frequent, s0, 100.0, 1.0, r, q, OptionType::Call).unwrap();
+        assert!((series - fourier).abs() < 1e-10, "{series} vs {fourier}");
+    }
+
+    #[test]
+    fn without_jumps_reduces_to_black_scholes() {
+        let none = LognormalJumps::new(0.0, -0.2, 0.3).unwrap();
+        let model = MertonJumpDiffusion::new(0.25, none).unwrap();
+        let merton = model.price(100.0, 110.0, 1.0, 0.05, 0.0, OptionType::Call).unwrap();
+        let bs = option_price(100.0, 110.0, 1.0, 0.05, 0.25, OptionType::Call).unwrap();
+        assert!((merton - bs).abs() < 1e-14);
+
+        let heston = HestonModel::new(0.04, 1.5, 0.06, 0.6, -0.7).unwrap();
+        let bates = BatesModel::new(heston, none);
+        let (a, b) = (
+            bates.price(100.0, 90.0, 1.0, 0.03, 0.0, OptionType::Put).unwrap(),
+            heston.price(100.0, 90.0, 1.0, 0.03, 0.0, OptionType::Put).unwrap(),
+        );
+        assert!((a - b).abs() < 1e-14);
+    }
+
+    #[test]
+    fn bates_with_deterministic_variance_matches_merton() {
+        // ξ → 0 y v₀ = θ: la varianza es constante y Bates se reduce a Merton
+        let (s0, t, r, q) = (100.0, 0.75, 0.03, 0.02);
+        let heston = HestonModel::new(0.04, 2.0, 0.04, 1e-4, 0.0).unwrap();
+        let bates = BatesModel::new(heston, jumps());
+        let merton = MertonJumpDiffusion::new(0.2, jumps()).unwrap();
+        for k in [80.0, 100.0, 120.0] {
+            let a = bates.price(s0, k, t, r, q, OptionType::Call).unwrap();
+            let b = merton.price(s0, k, t, r, q, OptionType::Call).unwrap();
+            assert!((a - b).abs() < 1e-7, "{k}: {a} vs {b}");
+        }
+    }
+
+    #[test]
+    fn simulation_matches_closed_forms() {
+        let (s0, k, t, r) = (100.0, 95.0, 0.5, 0.04);
+        let engine = MonteCarlo::new(40_000, 20).unwrap().with_antithetic(true);
+
+        let merton = MertonJumpDiffusion::new(0.2, jumps()).unwrap();
+        let exact = merton.price(s0, k, t, r, 0.0, OptionType::Put).unwrap();
+        let result = merton.monte_carlo(&engine, s0, t, r, |path| (k - path[path.len() - 1]).max(0.0)).unwrap();
+        assert!((result.price - exact).abs() < 3.0 * result.std_error, "{result:?} vs {exact}");
+
+        let bates = BatesModel::new(HestonModel::new(0.04, 1.5, 0.06, 0.6, -0.7).unwrap(), jumps());
+        let exact = bates.price(s0, k, t, r, 0.0, OptionType::Call).unwrap();
+        let controlled = engine.with_control_variate(105.0).unwrap();
+        let result = bates.monte_carlo(&controlled, s0, t, r, |path| (path[path.len() - 1] - k).max(0.0)).unwrap();
+        assert!((result.price - exact).abs() < 3.0 * result.std_error + 0.02, "{result:?} vs {exact}");
+
+        // Al vencimiento la trayectoria se queda en S₀
+        let intrinsic = bates.monte_carlo(&engine, s0, 0.0, r, |path| (path[path.len() - 1] - 90.0).max(0.0)).unwrap();
+        assert!((intrinsic.price - 10.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn invalid_inputs() {
+        assert_eq!(LognormalJumps::new(-1.0, 0.0, 0.1).unwrap_err(), FinanceError::InvalidInput);
+        assert_eq!(LognormalJumps::new(1.0, f64::NAN, 0.1).unwrap_err(), FinanceError::InvalidInput);
+        assert_eq!(LognormalJumps::new(1.0, 0.0, -0.1).unwrap_err(), FinanceError::InvalidInterestRate);
+        assert_eq!(MertonJumpDiffusion::new(-0.2, jumps()).unwrap_err(), FinanceError::InvalidInterestRate);
+        let model = MertonJumpDiffusion::new(0.2, jumps()).unwrap();
+        assert_eq!(model.price(0.0, 100.0, 1.0, 0.05, 0.0, OptionType::Call).unwrap_err(), FinanceError::InvalidMonetaryValue);
+        assert_eq!(model.price(100.0, 100.0, -1.0, 0.05, 0.0, OptionType::Call).unwrap_err(), FinanceError::InvalidPeriods);
+        assert_eq!(model.price(100.0, 90.0, 0.0, 0.05, 0.0, OptionType::Call).unwrap(), 10.0);
+    }
+}
@@ -0,0 +1,385 @@
+//! Modelos de Lévy de colas gruesas: Variance Gamma y Normal Inverse Gaussian.
+//!
+//! Ambos son movimientos brownianos con deriva evaluados en un reloj
+//! aleatorio G_t (subordinación), X_t = b G_t + s W(G_t), y el precio es
+//! S_T = S₀ exp((r - q + ω)T + X_T), con ω la corrección que hace de
+//! S_T e^{-(r-q)T} una martingala.
+//!
+//! Variance Gamma (Madan, Carr y Chang, 1998): G_t gamma de media t y
+//! varianza νt, b = θ, s = σ.
+//! φ_X(u) = (1 - iuθν + σ²νu²/2)^{-T/ν},  ω = ln(1 - θν - σ²ν/2)/ν
+//!
+//! NIG (Barndorff-Nielsen, 1997): G_t inversa gaussiana de media δt/γ y
+//! forma (δt)², γ = √(α² - β²), b = β, s = 1.
+//! φ_X(u) = exp(δT(γ - √(α² - (β + iu)²))),  ω = δ(√(α² - (β+1)²) - γ)
+//!
+//! Los precios europeos se obtienen con la fórmula de inversión de Heston, la
+//! simulación muestrea directamente el subordinador en cada paso y la
+//! calibración ajusta los tres parámetros a errores de precio divididos por
+//! la vega de Black-Scholes, como en Heston.
+
+use crate::common::{
+    FinanceError, FinanceResult,
+    DividendYield,
+    SpotPrice, StrikePrice, TimeToMaturity, InterestRate, Volatility,
+    OptionType, VolQuote
+};
+use crate::num::{Complex, Rng};
+use super::fourier::{CharacteristicFunction, calibrate_to_vols, fourier_implied_volatility, fourier_price};
+use super::monte_carlo::{MonteCarlo, MonteCarloResult, PathGenerator};
+
+/// Cotas de (σ, ν, θ) en la calibración de Variance Gamma.
+const VG_LOWER: [f64; 3] = [1e-3, 1e-4, -2.0];
+const VG_UPPER: [f64; 3] = [2.0, 5.0, 2.0];
+/// Cotas de (α, β, δ) en la calibración de NIG.
+const NIG_LOWER: [f64; 3] = [0.1, -100.0, 1e-3];
+const NIG_UPPER: [f64; 3] = [100.0, 100.0, 10.0];
+
+/// Modelo Variance Gamma
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VarianceGamma {
+    /// Volatilidad σ del browniano subordinado
+    pub sigma: Volatility,
+    /// Varianza ν del reloj gamma por unidad de tiempo (curtosis)
+    pub nu: f64,
+    /// Deriva θ del browniano subordinado (asimetría)
+    pub theta: f64,
+}
+
+/// Modelo Normal Inverse Gaussian
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NormalInverseGaussian {
+    /// Pesadez de colas α
+    pub alpha: f64,
+    /// Asimetría β, |β| < α
+    pub beta: f64,
+    /// Escala δ
+    pub delta: f64,
+}
+
+/// Resultado de una calibración de un modelo de Lévy
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LevyCalibration<M> {
+    /// Modelo calibrado
+    pub model: M,
+    /// Raíz del error cuadrático medio en volatilidad implícita
+    pub rmse: f64,
+    /// Iteraciones de Levenberg-Marquardt
+    pub iterations: usize,
+}
+
+/// Modelo de Lévy obtenido por subordinación de un browniano con deriva.
+trait Subordinated: CharacteristicFunction {
+    /// Corrección de martingala ω.
+    fn martingale_correction(&self) -> f64;
+
+    /// (b, s) en X = b G + s √G Z.
+    fn drift_and_scale(&self) -> (f64, f64);
+
+    /// Incremento del reloj G en un paso de longitud `dt`.
+    fn time_change(&self, rng: &mut Rng, dt: f64) -> f64;
+}
+
+impl VarianceGamma {
+    /// Crea el modelo validando los parámetros.
+    ///
+    /// # Errores
+    /// * `InvalidInterestRate` - Si σ no es positiva
+    /// * `InvalidInput` - Si ν no es positiva, θ no es finita o
+    ///   1 - θν - σ²ν/2 ≤ 0 (E[S_T] no sería finito)
+    pub fn new(sigma: Volatility, nu: f64, theta: f64) -> FinanceResult<Self> {
+        if !sigma.is_finite() || sigma <= 0.0 {
+            return Err(FinanceError::InvalidInterestRate);
+        }
+        if !nu.is_finite() || nu <= 0.0 || !theta.is_finite() || 1.0 - theta * nu - 0.5 * sigma * sigma * nu <= 0.0 {
+            return Err(FinanceError::InvalidInput);
+        }
+        Ok(VarianceGamma { sigma, nu, theta })
+    }
+
+    /// Precio europeo semianalítico.
+    ///
+    /// # Ejemplos
+    /// ```
+    /// use quant_finance::OptionType;
+    /// use quant_finance::options::{VarianceGamma, option_price};
+    ///
+    /// let model = VarianceGamma::new(0.2, 0.3, -0.15).unwrap();
+    /// let put = model.price(100.0, 80.0, 0.5, 0.03, 0.0, OptionType::Put).unwrap();
+    /// // Cola izquierda más gruesa que la lognormal con la misma volatilidad
+    /// let total_vol = (0.2f64.powi(2) + 0.15f64.powi(2) * 0.3).sqrt();
+    /// assert!(put > option_price(100.0, 80.0, 0.5, 0.03, total_vol, OptionType::Put).unwrap());
+    /// ```
+    ///
+    /// # Errores
+    /// Los mismos que [`HestonModel::price`](super::HestonModel::price).
+    pub fn price(&self, s0: SpotPrice, k: StrikePrice, t: TimeToMaturity, r: InterestRate, q: DividendYield, kind: OptionType) -> FinanceResult<f64> {
+        fourier_price(self, s0, k, t, r, q, kind)
+    }
+
+    /// Volatilidad implícita de Black-Scholes, usando la opción fuera del
+    /// dinero.
+    pub fn implied_volatility(&self, s0: SpotPrice, k: StrikePrice, t: TimeToMaturity, r: InterestRate, q: DividendYield) -> FinanceResult<Volatility> {
+        fourier_implied_volatility(self, s0, k, t, r, q)
+    }
+
+    /// Valora por Monte Carlo el pago `payoff` de la trayectoria S_0, ..., S_n
+    /// simulando el reloj gamma en cada paso.
+    ///
+    /// # Errores
+    /// * `InvalidMonetaryValue` - Si el spot no es positivo
+    /// * `InvalidPeriods` - Si `t` es negativo
+    pub fn monte_carlo<F: Fn(&[f64]) -> f64>(&self, engine: &MonteCarlo, s0: SpotPrice, t: TimeToMaturity, r: InterestRate, payoff: F) -> FinanceResult<MonteCarloResult> {
+        engine.price_with(self, s0, t, r, payoff)
+    }
+
+    /// Calibra (σ, ν, θ) a cotizaciones de volatilidad implícita, p. ej. una
+    /// sección de strikes, partiendo de `initial`.
+    ///
+    /// # Errores
+    /// * `InvalidInput` - Si hay menos de tres cotizaciones o alguna no es válida
+    pub fn calibrate(quotes: &[VolQuote], s0: SpotPrice, r: InterestRate, q: DividendYield, initial: VarianceGamma) -> FinanceResult<LevyCalibration<Self>> {
+        let (model, rmse, iterations) = calibrate_to_vols(quotes, s0, r, q, &[initial.sigma, initial.nu, initial.theta], (&VG_LOWER, &VG_UPPER), |p| VarianceGamma::new(p[0], p[1], p[2]))?;
+        Ok(LevyCalibration { model, rmse, iterations })
+    }
+}
+
+impl CharacteristicFunction for VarianceGamma {
+    fn characteristic_function(&self, u: Complex, s0: SpotPrice, t: TimeToMaturity, r: InterestRate, q: DividendYield) -> Complex {
+        let VarianceGamma { sigma, nu, theta } = *self;
+        let iu = Complex::I * u;
+        let base = 1.0 - iu * (theta * nu) + u * u * (0.5 * sigma * sigma * nu);
+        (iu * (s0.ln() + (r - q + self.martingale_correction()) * t) - base.ln() * (t / nu)).exp()
+    }
+}
+
+impl Subordinated for VarianceGamma {
+    fn martingale_correction(&self) -> f64 {
+        (1.0 - self.theta * self.nu - 0.5 * self.sigma * self.sigma * self.nu).ln() / self.nu
+    }
+
+    fn drift_and_scale(&self) -> (f64, f64) {
+        (self.theta, self.sigma)
+    }
+
+    fn time_change(&self, rng: &mut Rng, dt: f64) -> f64 {
+        self.nu * rng.gamma(dt / self.nu)
+    }
+}
+
+impl NormalInverseGaussian {
+    /// Crea el modelo validando los parámetros.
+    ///
+    /// # Errores
+    /// * `InvalidInput` - Si α o δ no son positivos, |β| ≥ α o
+    ///   |β + 1| ≥ α (E[S_T] no sería finito)
+    pub fn new(alpha: f64, beta: f64, delta: f64) -> FinanceResult<Self> {
+        if !alpha.is_finite() || !beta.is_finite() || !delta.is_finite() || alpha <= 0.0 || delta <= 0.0 {
+            return Err(FinanceError::InvalidInput);
+        }
+        if beta.abs() >= alpha || (beta + 1.0).abs() >= alpha {
+            return Err(FinanceError::InvalidInput);
+        }
+        Ok(NormalInverseGaussian { alpha, beta, delta })
+    }
+
+    /// γ = √(α² - β²)
+    fn gamma(&self) -> f64 {
+        (self.alpha * self.alpha - self.beta * self.beta).sqrt()
+    }
+
+    /// Precio europeo semianalítico.
+    ///
+    /// # Errores
+    /// Los mismos que [`HestonModel::price`](super::HestonModel::price).
+    pub fn price(&self, s0: SpotPrice, k: StrikePrice, t: TimeToMaturity, r: InterestRate, q: DividendYield, kind: OptionType) -> FinanceResult<f64> {
+        fourier_price(self, s0, k, t, r, q, kind)
+    }
+
+    /// Volatilidad implícita de Black-Scholes, usando la opción fuera del
+    /// dinero.
+    pub fn implied_volatility(&self, s0: SpotPrice, k: StrikePrice, t: TimeToMaturity, r: InterestRate, q: DividendYield) -> FinanceResult<Volatility> {
+        fourier_implied_volatility(self, s0, k, t, r, q)
+    }
+
+    /// Valora por Monte Carlo el pago `payoff` de la trayectoria S_0, ..., S_n
+    /// simulando el reloj inverso gaussiano en cada paso.
+    ///
+    /// # Errores
+    /// * `InvalidMonetaryValue` - Si el spot no es positivo
+    /// * `InvalidPeriods` - Si `t` es negativo
+    pub fn monte_carlo<F: Fn(&[f64]) -> f64>(&self, engine: &MonteCarlo, s0: SpotPrice, t: TimeToMaturity, r: InterestRate, payoff: F) -> FinanceResult<MonteCarloResult> {
+        engine.price_with(self, s0, t, r, payoff)
+    }
+
+    /// Calibra (α, β, δ) a cotizaciones de volatilidad implícita, p. ej. una
+    /// sección de strikes, partiendo de `initial`.
+    ///
+    /// # Errores
+    /// * `InvalidInput` - Si hay menos de tres cotizaciones o alguna no es válida
+    pub fn calibrate(quotes: &[VolQuote], s0: SpotPrice, r: InterestRate, q: DividendYield, initial: NormalInverseGaussian) -> FinanceResult<LevyCalibration<Self>> {
+        let (model, rmse, iterations) = calibrate_to_vols(quotes, s0, r, q, &[initial.alpha, initial.beta, initial.delta], (&NIG_LOWER, &NIG_UPPER), |p| NormalInverseGaussian::new(p[0], p[1], p[2]))?;
+        Ok(LevyCalibration { model, rmse, iterations })
+    }
+}
+
+impl CharacteristicFunction for NormalInverseGaussian {
+    fn characteristic_function(&self, u: Complex, s0: SpotPrice, t: TimeToMaturity, r: InterestRate, q: DividendYield) -> Complex {
+        let iu = Complex::I * u;
+        let shifted = self.beta + iu;
+        let root = (self.alpha * self.alpha - shifted * shifted).sqrt();
+        (iu * (s0.ln() + (r - q + self.martingale_correction()) * t) + (self.gamma() - root) * (self.delta * t)).exp()
+    }
+}
+
+impl Subordinated for NormalInverseGaussian {
+    fn martingale_correction(&self) -> f64 {
+        self.delta * ((self.alpha * self.alpha - (self.beta + 1.0).powi(2)).sqrt() - self.gamma())
+    }
+
+    fn drift_and_scale(&self) -> (f64, f64) {
+        (self.beta, 1.0)
+    }
+
+    fn time_change(&self, rng: &mut Rng, dt: f64) -> f64 {
+        // La inversa gaussiana degenera (0/0) con Δt = 0
+        if dt <= 0.0 {
+            return 0.0;
+        }
+        let scale = self.delta * dt;
+        rng.inverse_gaussian(scale / self.gamma(), scale * scale)
+    }
+}
+
+/// Simulación por subordinación: en cada paso X = ωΔt + b G + s √G Z con G
+/// el incremento del reloj; la antitética usa -Z con el mismo G.
+impl<M: Subordinated> PathGenerator for M {
+    /// (G, Z) por paso
+    type Shock = (f64, f64);
+    type State = ();
+
+    fn initial_state(&self) {}
+
+    fn sample(&self, rng: &mut Rng, dt: f64) -> (f64, f64) {
+        let g = self.time_change(rng, dt);
+        (g, rng.normal())
+    }
+
+    fn antithetic(&self, (g, z): (f64, f64)) -> (f64, f64) {
+        (g, -z)
+    }
+
+    fn log_increment(&self, _state: &mut (), (g, z): (f64, f64), dt: f64) -> f64 {
+        let (b, s) = self.drift_and_scale();
+        self.martingale_correction() * dt + b * g + s * g.sqrt() * z
+    }
+
+    fn call_price(&self, s0: SpotPrice, k: StrikePrice, t: TimeToMaturity, r: InterestRate, q: DividendYield) -> FinanceResult<f64> {
+        fourier_price(self, s0, k, t, r, q, OptionType::Call)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::options::{CosMethod, option_price_dividend};
+
+    fn vg() -> VarianceGamma {
+        VarianceGamma::new(0.18, 0.25, -0.14).unwrap()
+    }
+
+    fn nig() -> NormalInverseGaussian {
+        NormalInverseGaussian::new(8.0, -3.0, 0.4).unwrap()
+    }
+
+    #[test]
+    fn martingale_and_black_scholes_limits() {
+        let (s0, t, r, q): (f64, f64, f64, f64) = (100.0, 0.8, 0.04, 0.01);
+        let forward = s0 * ((r - q) * t).exp();
+        let minus_i = Complex::new(0.0, -1.0);
+        for model in [&vg() as &dyn CharacteristicFunction, &nig()] {
+            let expectation = model.characteristic_function(minus_i, s0, t, r, q);
+            assert!((expectation.re - forward).abs() < 1e-10 && expectation.im.abs() < 1e-10);
+        }
+        // ν → 0: VG tiende a Black-Scholes con σ; α → ∞ con δ/α = σ²: NIG también
+        let sigma: f64 = 0.25;
+        let bs = option_price_dividend(s0, 110.0, t, r, q, sigma, OptionType::Call).unwrap();
+        let vg = VarianceGamma::new(sigma, 1e-5, 0.0).unwrap().price(s0, 110.0, t, r, q, OptionType::Call).unwrap();
+        let nig = NormalInverseGaussian::new(1e4, 0.0, 1e4 * sigma * sigma).unwrap().price(s0, 110.0, t, r, q, OptionType::Call).unwrap();
+        // Los límites son de orden ν y 1/α: con ν = 1e-5 VG se separa ~1e-5
+        assert!((vg - bs).abs() < 5e-5, "{vg} vs {bs}");
+        assert!((nig - bs).abs() < 5e-5, "{nig} vs {bs}");
+    }
+
+    #[test]
+    fn fat_tails_and_skew() {
+        let (s0, t, r, q) = (100.0, 0.5, 0.03, 0.0);
+        for (name, vols) in [
+            ("vg", [70.0, 100.0, 130.0].map(|k| vg().implied_volatility(s0, k, t, r, q).unwrap())),
+            ("nig", [70.0, 100.0, 130.0].map(|k| nig().implied_volatility(s0, k, t, r, q).unwrap())),
+        ] {
+            // Asimetría negativa: las puts fuera del dinero son las más caras
+            assert!(vols[0] > vols[1] && vols[0] > vols[2], "{name}: {vols:?}");
+        }
+        // Inversión directa y COS coinciden
+        let cos = CosMethod::new(512).unwrap();
+        for k in [80.0, 100.0, 120.0] {
+            let direct = nig().price(s0, k, t, r, q, OptionType::Call).unwrap();
+            let series = cos.prices(&nig(), s0, &[k], t, r, OptionType::Call).unwrap()[0];
+            assert!((direct - series).abs() < 1e-6, "{k}: {direct} vs {series}");
+        }
+    }
+
+    #[test]
+    fn subordinated_simulation_matches_fourier() {
+        let (s0, k, t, r) = (100.0, 100.0, 0.5, 0.03);
+        let engine = MonteCarlo::new(40_000, 10).unwrap().with_antithetic(true);
+        let exact = vg().price(s0, k, t, r, 0.0, OptionType::Put).unwrap();
+        let result = vg().monte_carlo(&engine, s0, t, r, |path| (k - path[path.len() - 1]).max(0.0)).unwrap();
+        assert!((result.price - exact).abs() < 3.0 * result.std_error, "{result:?} vs {exact}");
+
+        let controlled = engine.with_control_variate(105.0).unwrap();
+        let exact = nig().price(s0, k, t, r, 0.0, OptionType::Call).unwrap();
+        let result = nig().monte_carlo(&controlled, s0, t, r, |path| (path[path.len() - 1] - k).max(0.0)).unwrap();
+        assert!((result.price - exact).abs() < 3.0 * result.std_error, "{result:?} vs {exact}");
+
+        // Al vencimiento ambos relojes están parados y se paga el intrínseco
+        let call = |path: &[f64]| (path[path.len() - 1] - 90.0).max(0.0);
+        assert!((vg().monte_carlo(&engine, s0, 0.0, r, call).unwrap().price - 10.0).abs() < 1e-12);
+        assert!((nig().monte_carlo(&engine, s0, 0.0, r, call).unwrap().price - 10.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn calibration_recovers_parameters() {
+        let (s0, r, q, t) = (100.0, 0.02, 0.0, 0.5);
+        let strikes = [75.0, 85.0, 92.0, 100.0, 108.0, 115.0, 125.0];
+        let slice = |vol: &dyn Fn(f64) -> f64| -> Vec<VolQuote> {
+            strikes.iter().map(|&strike| VolQuote { strike, maturity: t, volatility: vol(strike) }).collect()
+        };
+
+        let quotes = slice(&|k| vg().implied_volatility(s0, k, t, r, q).unwrap());
+        let start = VarianceGamma::new(0.25, 0.1, 0.0).unwrap();
+        let fit = VarianceGamma::calibrate(&quotes, s0, r, q, start).unwrap();
+        assert!(fit.rmse < 1e-5, "{fit:?}");
+        assert!((fit.model.sigma - vg().sigma).abs() < 1e-3 && (fit.model.theta - vg().theta).abs() < 1e-2);
+
+        let quotes = slice(&|k| nig().implied_volatility(s0, k, t, r, q).unwrap());
+        let start = NormalInverseGaussian::new(5.0, 0.0, 0.3).unwrap();
+        let fit = NormalInverseGaussian::calibrate(&quotes, s0, r, q, start).unwrap();
+        assert!(fit.rmse < 1e-5, "{fit:?}");
+        assert!((fit.model.delta - nig().delta).abs() < 1e-2);
+    }
+
+    #[test]
+    fn invalid_parameters() {
+        assert_eq!(VarianceGamma::new(0.0, 0.2, 0.0).unwrap_err(), FinanceError::InvalidInterestRate);
+        assert_eq!(VarianceGamma::new(0.2, 0.0, 0.0).unwrap_err(), FinanceError::InvalidInput);
+        // 1 - θν - σ²ν/2 ≤ 0
+        assert_eq!(VarianceGamma::new(0.2, 2.0, 0.5).unwrap_err(), FinanceError::InvalidInput);
+        assert_eq!(NormalInverseGaussian::new(2.0, 2.0, 0.5).unwrap_err(), FinanceError::InvalidInput);
+        assert_eq!(NormalInverseGaussian::new(2.0, 1.5, 0.5).unwrap_err(), FinanceError::InvalidInput);
+        assert_eq!(NormalInverseGaussian::new(2.0, 0.0, 0.0).unwrap_err(), FinanceError::InvalidInput);
+        assert_eq!(vg().price(-1.0, 100.0, 1.0, 0.05, 0.0, OptionType::Call).unwrap_err(), FinanceError::InvalidMonetaryValue);
+        assert_eq!(VarianceGamma::calibrate(&[], 100.0, 0.05, 0.0, vg()).unwrap_err(), FinanceError::InvalidInput);
+    }
+}
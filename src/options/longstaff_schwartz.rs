@@ -0,0 +1,441 @@
+//! Monte Carlo de mínimos cuadrados (Longstaff-Schwartz, 2001) para opciones
+//! con ejercicio americano o bermuda bajo GBM.
+//!
+//! Las trayectorias se simulan de forma exacta en una malla uniforme de
+//! `steps` pasos. En cada fecha de ejercicio, y hacia atrás, el valor de
+//! continuación se estima regresando el flujo descontado futuro de las
+//! trayectorias dentro del dinero sobre una base de funciones de x = S/K;
+//! se ejerce cuando el pago inmediato supera la continuación estimada.
+//!
+//! - Cota inferior: la regla de ejercicio estimada se aplica a un segundo
+//!   conjunto de trayectorias independientes, de modo que el estimador es
+//!   el valor de una estrategia admisible (sesgado a la baja).
+//! - Cota superior: dual de Andersen-Broadie (2004). La martingala se
+//!   construye con el valor de la propia estrategia, estimado en cada fecha
+//!   con subtrayectorias anidadas, y la cota es
+//!   π₀ + E[max_k (Z_k - π_k)], con Z_k el pago descontado en la fecha k.
+//!
+//! Las fechas bermudas se asignan al paso de la malla más cercano.
+
+use crate::common::{
+    FinanceError, FinanceResult,
+    DividendYield,
+    SpotPrice, StrikePrice, TimeToMaturity, InterestRate, Volatility,
+    OptionType, ExerciseStyle
+};
+use crate::num::{Rng, least_squares};
+use super::binomial::{exercise_steps, validate_exercise};
+use super::black_scholes::{validate_inputs, EPS_TIME};
+use super::monte_carlo::MonteCarloResult;
+
+/// Semilla por defecto del generador.
+const DEFAULT_SEED: u64 = 0x15A_0001;
+
+/// Base de funciones para la regresión del valor de continuación, evaluada
+/// en x = S/K. Ambas incluyen el término constante.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RegressionBasis {
+    /// 1, x, ..., x^n
+    Monomial(usize),
+    /// 1, e^{-x/2} L_0(x), ..., e^{-x/2} L_n(x), con L_j los polinomios de
+    /// Laguerre (la elección del artículo original)
+    Laguerre(usize),
+}
+
+impl RegressionBasis {
+    /// Número de funciones de la base
+    pub fn len(self) -> usize {
+        match self {
+            RegressionBasis::Monomial(n) => n + 1,
+            RegressionBasis::Laguerre(n) => n + 2,
+        }
+    }
+
+    /// `true` si la base solo tiene el término constante
+    pub fn is_empty(self) -> bool {
+        self.len() == 1
+    }
+
+    /// Valores de las funciones de la base en `x`
+    pub fn evaluate(self, x: f64) -> Vec<f64> {
+        match self {
+            RegressionBasis::Monomial(n) => {
+                let mut values = Vec::with_capacity(n + 1);
+                let mut power = 1.0;
+                for _ in 0..=n {
+                    values.push(power);
+                    power *= x;
+                }
+                values
+            }
+            RegressionBasis::Laguerre(n) => {
+                // (j + 1) L_{j+1} = (2j + 1 - x) L_j - j L_{j-1}
+                let weight = (-0.5 * x).exp();
+                let mut values = Vec::with_capacity(n + 2);
+                values.push(1.0);
+                let (mut prev, mut current) = (0.0, 1.0);
+                for j in 0..=n {
+                    values.push(weight * current);
+                    let jf = j as f64;
+                    let next = ((2.0 * jf + 1.0 - x) * current - jf * prev) / (jf + 1.0);
+                    prev = current;
+                    current = next;
+                }
+                values
+            }
+        }
+    }
+}
+
+/// Configuración del motor de Longstaff-Schwartz
+#[derive(Debug, Clone, PartialEq)]
+pub struct LongstaffSchwartz {
+    /// Trayectorias de la regresión y, por separado, de la cota inferior
+    pub paths: usize,
+    /// Número de pasos temporales por trayectoria
+    pub steps: usize,
+    /// Semilla del generador
+    pub seed: u64,
+    /// Base de la regresión
+    pub basis: RegressionBasis,
+    /// Estilo de ejercicio (americano: en cada paso de la malla)
+    pub exercise: ExerciseStyle,
+    /// Rendimiento por dividendo continuo del subyacente
+    pub dividend_yield: DividendYield,
+    /// Trayectorias exteriores y subtrayectorias por fecha del dual de
+    /// Andersen-Broadie, si se calcula la cota superior
+    pub dual: Option<(usize, usize)>,
+}
+
+/// Cotas del precio obtenidas por Longstaff-Schwartz
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LongstaffSchwartzResult {
+    /// Valor de la regla de ejercicio estimada sobre trayectorias independientes
+    pub lower_bound: MonteCarloResult,
+    /// Cota dual de Andersen-Broadie, si se ha pedido
+    pub upper_bound: Option<MonteCarloResult>,
+}
+
+/// Regla de ejercicio estimada: coeficientes de la regresión por paso.
+struct ExercisePolicy {
+    basis: RegressionBasis,
+    k: StrikePrice,
+    kind: OptionType,
+    /// Factor de descuento e^{-r t_i} de cada paso
+    discount: Vec<f64>,
+    exercisable: Vec<bool>,
+    coefficients: Vec<Option<Vec<f64>>>,
+}
+
+impl ExercisePolicy {
+    /// Pago inmediato descontado a hoy en el paso `i`
+    fn discounted_payoff(&self, i: usize, s: SpotPrice) -> f64 {
+        self.discount[i] * self.kind.payoff(s, self.k)
+    }
+
+    /// `true` si la regla ejerce en el paso intermedio `i` con subyacente `s`
+    fn exercises(&self, i: usize, s: SpotPrice) -> bool {
+        let Some(beta) = self.coefficients[i].as_ref().filter(|_| self.exercisable[i]) else {
+            return false;
+        };
+        let exercise = self.discounted_payoff(i, s);
+        let continuation: f64 = self.basis.evaluate(s / self.k).iter().zip(beta).map(|(f, b)| f * b).sum();
+        exercise > 0.0 && exercise >= continuation
+    }
+
+    /// Pago descontado de seguir la regla desde el paso `from` (incluido)
+    /// a lo largo de `path`, indexada por paso.
+    fn cash_flow(&self, path: &[f64], from: usize) -> f64 {
+        let n = path.len() - 1;
+        (from..n)
+            .find(|&i| self.exercises(i, path[i]))
+            .map_or_else(|| self.discounted_payoff(n, path[n]), |i| self.discounted_payoff(i, path[i]))
+    }
+}
+
+impl LongstaffSchwartz {
+    /// Crea un motor americano con base de Laguerre de grado 2, semilla por
+    /// defecto y sin cota superior.
+    ///
+    /// # Errores
+    /// * `InvalidPeriods` - Si `paths < 2` o `steps == 0`
+    pub fn new(paths: usize, steps: usize) -> FinanceResult<Self> {
+        if paths < 2 || steps == 0 {
+            return Err(FinanceError::InvalidPeriods);
+        }
+        Ok(LongstaffSchwartz {
+            paths,
+            steps,
+            seed: DEFAULT_SEED,
+            basis: RegressionBasis::Laguerre(2),
+            exercise: ExerciseStyle::American,
+            dividend_yield: 0.0,
+            dual: None,
+        })
+    }
+
+    /// Cambia la semilla del generador.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Cambia la base de la regresión.
+    pub fn with_basis(mut self, basis: RegressionBasis) -> Self {
+        self.basis = basis;
+        self
+    }
+
+    /// Cambia el estilo de ejercicio.
+    pub fn with_exercise(mut self, exercise: ExerciseStyle) -> FinanceResult<Self> {
+        validate_exercise(&exercise)?;
+        self.exercise = exercise;
+        Ok(self)
+    }
+
+    /// Cambia el rendimiento por dividendo continuo.
+    ///
+    /// # Errores
+    /// * `InvalidInterestRate` - Si no es finito
+    pub fn with_dividend_yield(mut self, q: DividendYield) -> FinanceResult<Self> {
+        if !q.is_finite() {
+            return Err(FinanceError::InvalidInterestRate);
+        }
+        self.dividend_yield = q;
+        Ok(self)
+    }
+
+    /// Calcula también la cota superior dual con `outer` trayectorias y
+    /// `inner` subtrayectorias en cada fecha de ejercicio.
+    ///
+    /// # Errores
+    /// * `InvalidPeriods` - Si `outer < 2` o `inner == 0`
+    pub fn with_dual(mut self, outer: usize, inner: usize) -> FinanceResult<Self> {
+        if outer < 2 || inner == 0 {
+            return Err(FinanceError::InvalidPeriods);
+        }
+        self.dual = Some((outer, inner));
+        Ok(self)
+    }
+
+    /// Cotas inferior y (opcionalmente) superior del precio.
+    ///
+    /// # Ejemplos
+    /// ```
+    /// use quant_finance::OptionType;
+    /// use quant_finance::options::LongstaffSchwartz;
+    ///
+    /// // Put americana del artículo original: S=36, K=40, T=1, r=6%, σ=20%
+    /// let engine = LongstaffSchwartz::new(20_000, 50).unwrap();
+    /// let result = engine.price(36.0, 40.0, 1.0, 0.06, 0.2, OptionType::Put).unwrap();
+    /// assert!((result.lower_bound.price - 4.472).abs() < 0.05);
+    /// ```
+    ///
+    /// # Errores
+    /// * `InvalidMonetaryValue` - Si el spot o el strike no son positivos
+    /// * `InvalidPeriods` - Si `t` es negativo
+    /// * `InvalidInterestRate` - Si `sigma` es negativa
+    pub fn price(&self, s0: SpotPrice, k: StrikePrice, t: TimeToMaturity, r: InterestRate, sigma: Volatility, kind: OptionType) -> FinanceResult<LongstaffSchwartzResult> {
+        validate_inputs(s0, k, t, r, sigma)?;
+        if t < EPS_TIME {
+            let intrinsic = MonteCarloResult::from_samples(&[kind.payoff(s0, k)])?;
+            return Ok(LongstaffSchwartzResult { lower_bound: intrinsic, upper_bound: self.dual.map(|_| intrinsic) });
+        }
+
+        let n = self.steps;
+        let dt = t / n as f64;
+        let drift = (r - self.dividend_yield - 0.5 * sigma * sigma) * dt;
+        let diffusion = sigma * dt.sqrt();
+        let mut rng = Rng::new(self.seed);
+        let simulate = |path: &mut [f64], from: usize, rng: &mut Rng| {
+            for i in from..path.len() - 1 {
+                path[i + 1] = path[i] * (drift + diffusion * rng.normal()).exp();
+            }
+        };
+
+        let mut policy = ExercisePolicy {
+            basis: self.basis,
+            k,
+            kind,
+            discount: (0..=n).map(|i| (-r * dt * i as f64).exp()).collect(),
+            exercisable: exercise_steps(&self.exercise, t, n),
+            coefficients: vec![None; n + 1],
+        };
+
+        // Regresión hacia atrás sobre las trayectorias de entrenamiento
+        let mut training = vec![vec![0.0; n + 1]; self.paths];
+        for path in training.iter_mut() {
+            path[0] = s0;
+            simulate(path, 0, &mut rng);
+        }
+        let mut cash_flows: Vec<f64> = training.iter().map(|path| policy.discounted_payoff(n, path[n])).collect();
+        for i in (1..n).rev().filter(|&i| policy.exercisable[i]) {
+            let in_the_money: Vec<usize> = (0..self.paths).filter(|&p| kind.payoff(training[p][i], k) > 0.0).collect();
+            let design: Vec<Vec<f64>> = in_the_money.iter().map(|&p| self.basis.evaluate(training[p][i] / k)).collect();
+            let targets: Vec<f64> = in_the_money.iter().map(|&p| cash_flows[p]).collect();
+            // Con pocas trayectorias dentro del dinero no se ejerce en esa fecha
+            policy.coefficients[i] = least_squares(&design, &targets).ok();
+            for &p in &in_the_money {
+                if policy.exercises(i, training[p][i]) {
+                    cash_flows[p] = policy.discounted_payoff(i, training[p][i]);
+                }
+            }
+        }
+        drop(training);
+
+        // Cota inferior sobre trayectorias nuevas
+        let mut path = vec![0.0; n + 1];
+        path[0] = s0;
+        let lower_samples: Vec<f64> = (0..self.paths).map(|_| {
+            simulate(&mut path, 0, &mut rng);
+            policy.cash_flow(&path, 1)
+        }).collect();
+        let continuation = MonteCarloResult::from_samples(&lower_samples)?;
+        let immediate = kind.payoff(s0, k);
+        let lower_bound = if policy.exercisable[0] && immediate > continuation.price {
+            MonteCarloResult::from_samples(&[immediate])?
+        } else {
+            continuation
+        };
+
+        let upper_bound = match self.dual {
+            None => None,
+            Some((outer, inner)) => {
+                let mut sub_path = vec![0.0; n + 1];
+                let mut upper_samples = Vec::with_capacity(outer);
+                for _ in 0..outer {
+                    simulate(&mut path, 0, &mut rng);
+                    // π_0 = valor estimado de la estrategia; E_0[L_1] = continuación
+                    let mut martingale = lower_bound.price;
+                    let mut expected_next = continuation.price;
+                    let mut max_gap = if policy.exercisable[0] { immediate - martingale } else { f64::NEG_INFINITY };
+                    for i in (1..=n).filter(|&i| i == n || policy.exercisable[i]) {
+                        let exercise = policy.discounted_payoff(i, path[i]);
+                        let (value, next) = if i == n {
+                            (exercise, 0.0)
+                        } else {
+                            sub_path[i] = path[i];
+                            let mut sum = 0.0;
+                            for _ in 0..inner {
+                                simulate(&mut sub_path, i, &mut rng);
+                                sum += policy.cash_flow(&sub_path, i + 1);
+                            }
+                            let next = sum / inner as f64;
+                            (if policy.exercises(i, path[i]) { exercise } else { next }, next)
+                        };
+                        martingale += value - expected_next;
+                        expected_next = next;
+                        max_gap = max_gap.max(exercise - martingale);
+                    }
+                    upper_samples.push(lower_bound.price + max_gap);
+                }
+                Some(MonteCarloResult::from_samples(&upper_samples)?)
+            }
+        };
+
+        Ok(LongstaffSchwartzResult { lower_bound, upper_bound })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::options::{put_price, call_price, BinomialTree};
+
+    #[test]
+    fn american_put_matches_binomial() {
+        let (s0, k, t, r, sigma) = (36.0, 40.0, 1.0, 0.06, 0.2);
+        let european = put_price(s0, k, t, r, sigma).unwrap();
+        let tree = BinomialTree::new(2000).unwrap().with_exercise(ExerciseStyle::American).unwrap();
+        let american = tree.price(s0, k, t, r, sigma, OptionType::Put).unwrap();
+        let result = LongstaffSchwartz::new(40_000, 50).unwrap().price(s0, k, t, r, sigma, OptionType::Put).unwrap();
+        let lower = result.lower_bound;
+        assert!(lower.price > european + 0.5);
+        // 50 fechas de ejercicio quedan algo por debajo del americano continuo
+        assert!(lower.price < american + 3.0 * lower.std_error);
+        assert!((lower.price - american).abs() < 0.03, "{lower:?} vs {american}");
+    }
+
+    #[test]
+    fn dual_bounds_bracket_bermudan_tree() {
+        let (s0, k, t, r, sigma) = (40.0, 40.0, 1.0, 0.06, 0.2);
+        let dates: Vec<f64> = (1..10).map(|i| i as f64 / 10.0).collect();
+        let tree = BinomialTree::new(2000).unwrap().with_exercise(ExerciseStyle::Bermudan(dates.clone())).unwrap();
+        let bermudan = tree.price(s0, k, t, r, sigma, OptionType::Put).unwrap();
+
+        let engine = LongstaffSchwartz::new(20_000, 10).unwrap()
+            .with_exercise(ExerciseStyle::Bermudan(dates)).unwrap()
+            .with_dual(300, 300).unwrap();
+        let result = engine.price(s0, k, t, r, sigma, OptionType::Put).unwrap();
+        let (lower, upper) = (result.lower_bound, result.upper_bound.unwrap());
+        assert!(lower.price - 3.0 * lower.std_error < bermudan, "{lower:?} vs {bermudan}");
+        assert!(upper.price + 3.0 * upper.std_error > bermudan, "{upper:?} vs {bermudan}");
+        assert!(upper.price >= lower.price);
+        assert!(upper.price - lower.price < 0.05);
+    }
+
+    #[test]
+    fn bases_give_consistent_prices() {
+        let (s0, k, t, r, sigma) = (100.0, 100.0, 0.5, 0.05, 0.3);
+        let engine = LongstaffSchwartz::new(20_000, 25).unwrap();
+        let laguerre = engine.price(s0, k, t, r, sigma, OptionType::Put).unwrap().lower_bound;
+        let monomial = engine.clone().with_basis(RegressionBasis::Monomial(3))
+            .price(s0, k, t, r, sigma, OptionType::Put).unwrap().lower_bound;
+        let tolerance = 3.0 * (laguerre.std_error.powi(2) + monomial.std_error.powi(2)).sqrt();
+        assert!((laguerre.price - monomial.price).abs() < tolerance.max(0.02));
+    }
+
+    #[test]
+    fn european_exercise_and_calls_without_dividends() {
+        let (s0, k, t, r, sigma) = (100.0, 105.0, 1.0, 0.05, 0.2);
+        let european = LongstaffSchwartz::new(40_000, 10).unwrap().with_exercise(ExerciseStyle::European).unwrap();
+        let put = european.price(s0, k, t, r, sigma, OptionType::Put).unwrap().lower_bound;
+        assert!((put.price - put_price(s0, k, t, r, sigma).unwrap()).abs() < 3.0 * put.std_error);
+
+        // Sin dividendos nunca conviene ejercer una call antes del vencimiento
+        let american = LongstaffSchwartz::new(40_000, 10).unwrap();
+        let call = american.price(s0, k, t, r, sigma, OptionType::Call).unwrap().lower_bound;
+        assert!((call.price - call_price(s0, k, t, r, sigma).unwrap()).abs() < 3.0 * call.std_error);
+    }
+
+    #[test]
+    fn deep_in_the_money_exercises_immediately() {
+        let result = LongstaffSchwartz::new(2_000, 10).unwrap().price(10.0, 40.0, 1.0, 0.06, 0.2, OptionType::Put).unwrap();
+        assert_eq!(result.lower_bound.price, 30.0);
+        assert_eq!(result.lower_bound.std_error, 0.0);
+    }
+
+    #[test]
+    fn expiry_reports_upper_bound_only_with_dual() {
+        let engine = LongstaffSchwartz::new(100, 10).unwrap();
+        let plain = engine.price(90.0, 100.0, 0.0, 0.05, 0.2, OptionType::Put).unwrap();
+        assert_eq!(plain.lower_bound.price, 10.0);
+        assert!(plain.upper_bound.is_none());
+        let dual = engine.with_dual(10, 10).unwrap().price(90.0, 100.0, 0.0, 0.05, 0.2, OptionType::Put).unwrap();
+        assert_eq!(dual.upper_bound.unwrap().price, 10.0);
+    }
+
+    #[test]
+    fn laguerre_basis_values() {
+        // L_0 = 1, L_1 = 1 - x, L_2 = 1 - 2x + x²/2
+        let x: f64 = 0.8;
+        let values = RegressionBasis::Laguerre(2).evaluate(x);
+        let w = (-0.5 * x).exp();
+        let expected = [1.0, w, w * (1.0 - x), w * (1.0 - 2.0 * x + 0.5 * x * x)];
+        assert_eq!(values.len(), RegressionBasis::Laguerre(2).len());
+        for (v, e) in values.iter().zip(expected) {
+            assert!((v - e).abs() < 1e-14);
+        }
+        assert_eq!(RegressionBasis::Monomial(2).evaluate(2.0), vec![1.0, 2.0, 4.0]);
+    }
+
+    #[test]
+    fn invalid_configuration() {
+        assert_eq!(LongstaffSchwartz::new(1, 10).unwrap_err(), FinanceError::InvalidPeriods);
+        assert_eq!(LongstaffSchwartz::new(100, 0).unwrap_err(), FinanceError::InvalidPeriods);
+        let engine = LongstaffSchwartz::new(100, 10).unwrap();
+        assert_eq!(engine.clone().with_dual(1, 10).unwrap_err(), FinanceError::InvalidPeriods);
+        assert_eq!(engine.clone().with_exercise(ExerciseStyle::Bermudan(vec![-1.0])).unwrap_err(), FinanceError::InvalidPeriods);
+        assert_eq!(engine.price(100.0, 0.0, 1.0, 0.05, 0.2, OptionType::Put).unwrap_err(), FinanceError::InvalidMonetaryValue);
+    }
+}
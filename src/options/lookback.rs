@@ -0,0 +1,286 @@
+//! Opciones lookback europeas con observación continua.
+//!
+//! - Strike flotante (Goldman, Sosin y Gatto, 1979): la call paga S_T - min S
+//!   y la put max S - S_T.
+//! - Strike fijo (Conze y Viswanathan, 1991): la call paga max(max S - K, 0) y
+//!   la put max(K - min S, 0).
+//!
+//! Con b = r - q y m/M el mínimo/máximo observado hasta hoy, todas se escriben
+//! como una vanilla de Merton más un término de ajuste por el extremo:
+//! C_flot = c(S, m) + A_min(m)             P_flot = p(S, M) + A_max(M)
+//! C_fijo = e^{-rT}(X - K) + c(S, X) + A_max(X),  X = max(K, M)
+//! P_fijo = e^{-rT}(K - X) + p(S, X) + A_min(X),  X = min(K, m)
+//! A_min(X) = S e^{-rT} σ²/(2b) [(S/X)^{-2b/σ²} N(-x1 + 2b√T/σ) - e^{bT} N(-x1)]
+//! A_max(X) = S e^{-rT} σ²/(2b) [e^{bT} N(x1) - (S/X)^{-2b/σ²} N(x1 - 2b√T/σ)]
+//! x1 = [ln(S/X) + (b + σ²/2)T]/(σ√T)
+//!
+//! Con b = 0 los ajustes pasan al límite
+//! A_min = S e^{-rT} σ√T [φ(x1) - x1 N(-x1)],  A_max = S e^{-rT} σ√T [φ(x1) + x1 N(x1)].
+
+use crate::common::{
+    FinanceError, FinanceResult,
+    DividendYield,
+    SpotPrice, StrikePrice, TimeToMaturity, InterestRate, Volatility,
+    OptionType
+};
+use super::black_scholes::{
+    normal_cdf, normal_pdf, option_price_dividend, validate_inputs, EPS_TIME, EPS_VOL
+};
+
+/// Coste de mantenimiento por debajo del cual se usa el límite b = 0.
+const EPS_CARRY: f64 = 1e-9;
+
+/// Tipo de strike de una lookback
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LookbackStrike {
+    /// Strike igual al extremo observado (mínimo en la call, máximo en la put)
+    Floating,
+    /// Strike fijo sobre el extremo observado (máximo en la call, mínimo en la put)
+    Fixed(StrikePrice),
+}
+
+/// Opción lookback europea
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LookbackOption {
+    /// Tipo de strike
+    pub strike: LookbackStrike,
+    /// Mínimo y máximo observados hasta hoy; `None` si la observación empieza hoy
+    pub running_extremes: Option<(SpotPrice, SpotPrice)>,
+    /// Rendimiento por dividendo continuo del subyacente
+    pub dividend_yield: DividendYield,
+}
+
+impl LookbackOption {
+    /// Lookback de strike flotante que empieza a observar hoy.
+    pub fn floating() -> Self {
+        LookbackOption { strike: LookbackStrike::Floating, running_extremes: None, dividend_yield: 0.0 }
+    }
+
+    /// Lookback de strike fijo que empieza a observar hoy.
+    ///
+    /// # Errores
+    /// * `InvalidMonetaryValue` - Si el strike no es positivo
+    pub fn fixed(k: StrikePrice) -> FinanceResult<Self> {
+        if !k.is_finite() || k <= 0.0 {
+            return Err(FinanceError::InvalidMonetaryValue);
+        }
+        Ok(LookbackOption { strike: LookbackStrike::Fixed(k), running_extremes: None, dividend_yield: 0.0 })
+    }
+
+    /// Mínimo y máximo del subyacente observados hasta hoy.
+    ///
+    /// # Errores
+    /// * `InvalidMonetaryValue` - Si no son positivos o `min > max`
+    pub fn with_running_extremes(mut self, min: SpotPrice, max: SpotPrice) -> FinanceResult<Self> {
+        if !min.is_finite() || !max.is_finite() || min <= 0.0 || min > max {
+            return Err(FinanceError::InvalidMonetaryValue);
+        }
+        self.running_extremes = Some((min, max));
+        Ok(self)
+    }
+
+    /// Cambia el rendimiento por dividendo continuo.
+    ///
+    /// # Errores
+    /// * `InvalidInterestRate` - Si no es finito
+    pub fn with_dividend_yield(mut self, q: DividendYield) -> FinanceResult<Self> {
+        if !q.is_finite() {
+            return Err(FinanceError::InvalidInterestRate);
+        }
+        self.dividend_yield = q;
+        Ok(self)
+    }
+
+    /// Precio de la lookback.
+    ///
+    /// # Errores
+    /// * `InvalidInput` - Si el spot está fuera de los extremos observados
+    /// * `InvalidInterestRate` - Si `sigma` es nula o negativa
+    pub fn price(&self, s0: SpotPrice, t: TimeToMaturity, r: InterestRate, sigma: Volatility, kind: OptionType) -> FinanceResult<f64> {
+        let k = match self.strike {
+            LookbackStrike::Floating => s0,
+            LookbackStrike::Fixed(k) => k,
+        };
+        validate_inputs(s0, k, t, r, sigma)?;
+        let (min, max) = self.running_extremes.unwrap_or((s0, s0));
+        if s0 < min || s0 > max {
+            return Err(FinanceError::InvalidInput);
+        }
+
+        let q = self.dividend_yield;
+        let df = (-r * t).exp();
+        if t < EPS_TIME {
+            return Ok(match (self.strike, kind) {
+                (LookbackStrike::Floating, OptionType::Call) => s0 - min,
+                (LookbackStrike::Floating, OptionType::Put) => max - s0,
+                (LookbackStrike::Fixed(k), OptionType::Call) => (max - k).max(0.0),
+                (LookbackStrike::Fixed(k), OptionType::Put) => (k - min).max(0.0),
+            });
+        }
+        if sigma < EPS_VOL {
+            return Err(FinanceError::InvalidInterestRate);
+        }
+
+        let adjustment = Adjustment { s: s0, t, r, b: r - q, sigma };
+        match (self.strike, kind) {
+            (LookbackStrike::Floating, OptionType::Call) => {
+                Ok(option_price_dividend(s0, min, t, r, q, sigma, kind)? + adjustment.at_min(min))
+            }
+            (LookbackStrike::Floating, OptionType::Put) => {
+                Ok(option_price_dividend(s0, max, t, r, q, sigma, kind)? + adjustment.at_max(max))
+            }
+            (LookbackStrike::Fixed(k), OptionType::Call) => {
+                let x = k.max(max);
+                Ok(df * (x - k) + option_price_dividend(s0, x, t, r, q, sigma, kind)? + adjustment.at_max(x))
+            }
+            (LookbackStrike::Fixed(k), OptionType::Put) => {
+                let x = k.min(min);
+                Ok(df * (k - x) + option_price_dividend(s0, x, t, r, q, sigma, kind)? + adjustment.at_min(x))
+            }
+        }
+    }
+}
+
+/// Términos de ajuste por el extremo A_min y A_max.
+struct Adjustment {
+    s: SpotPrice,
+    t: TimeToMaturity,
+    r: InterestRate,
+    b: f64,
+    sigma: Volatility,
+}
+
+impl Adjustment {
+    fn x1(&self, x: SpotPrice) -> f64 {
+        ((self.s / x).ln() + (self.b + 0.5 * self.sigma * self.sigma) * self.t) / (self.sigma * self.t.sqrt())
+    }
+
+    /// A_min(X): ajuste cuando el payoff depende del mínimo
+    fn at_min(&self, x: SpotPrice) -> f64 {
+        let x1 = self.x1(x);
+        let sigma_sqrt_t = self.sigma * self.t.sqrt();
+        let scale = self.s * (-self.r * self.t).exp();
+        if self.b.abs() < EPS_CARRY {
+            return scale * sigma_sqrt_t * (normal_pdf(x1) - x1 * normal_cdf(-x1));
+        }
+        let v2 = self.sigma * self.sigma;
+        let reflected = (self.s / x).powf(-2.0 * self.b / v2) * normal_cdf(-x1 + 2.0 * self.b * self.t.sqrt() / self.sigma);
+        scale * v2 / (2.0 * self.b) * (reflected - (self.b * self.t).exp() * normal_cdf(-x1))
+    }
+
+    /// A_max(X): ajuste cuando el payoff depende del máximo
+    fn at_max(&self, x: SpotPrice) -> f64 {
+        let x1 = self.x1(x);
+        let sigma_sqrt_t = self.sigma * self.t.sqrt();
+        let scale = self.s * (-self.r * self.t).exp();
+        if self.b.abs() < EPS_CARRY {
+            return scale * sigma_sqrt_t * (normal_pdf(x1) + x1 * normal_cdf(x1));
+        }
+        let v2 = self.sigma * self.sigma;
+        let reflected = (self.s / x).powf(-2.0 * self.b / v2) * normal_cdf(x1 - 2.0 * self.b * self.t.sqrt() / self.sigma);
+        scale * v2 / (2.0 * self.b) * ((self.b * self.t).exp() * normal_cdf(x1) - reflected)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::num::Rng;
+    use crate::options::BGK_BETA;
+
+    /// Monte Carlo con observación discreta y corrección de Broadie-Glasserman-Kou
+    /// de los extremos; devuelve (precio, error estándar).
+    fn monte_carlo(option: &LookbackOption, s0: f64, t: f64, r: f64, sigma: f64, kind: OptionType) -> (f64, f64) {
+        let (paths, steps) = (10_000, 200);
+        let dt = t / steps as f64;
+        let drift = (r - option.dividend_yield - 0.5 * sigma * sigma) * dt;
+        let shift = (BGK_BETA * sigma * dt.sqrt()).exp();
+        let (min0, max0) = option.running_extremes.unwrap_or((s0, s0));
+        let mut rng = Rng::new(7);
+        let (mut sum, mut sum_sq) = (0.0, 0.0);
+        for _ in 0..paths {
+            let (mut s, mut lo, mut hi) = (s0, s0, s0);
+            for _ in 0..steps {
+                s *= (drift + sigma * dt.sqrt() * rng.normal()).exp();
+                lo = lo.min(s);
+                hi = hi.max(s);
+            }
+            let (lo, hi) = ((lo / shift).min(min0), (hi * shift).max(max0));
+            let payoff = match (option.strike, kind) {
+                (LookbackStrike::Floating, OptionType::Call) => s - lo,
+                (LookbackStrike::Floating, OptionType::Put) => hi - s,
+                (LookbackStrike::Fixed(k), OptionType::Call) => (hi - k).max(0.0),
+                (LookbackStrike::Fixed(k), OptionType::Put) => (k - lo).max(0.0),
+            };
+            sum += payoff;
+            sum_sq += payoff * payoff;
+        }
+        let mean = sum / paths as f64;
+        let std_err = ((sum_sq / paths as f64 - mean * mean) / paths as f64).sqrt();
+        ((-r * t).exp() * mean, (-r * t).exp() * std_err)
+    }
+
+    #[test]
+    fn haug_reference_value() {
+        // Haug (2007): flotante S=120, min=100, T=0.5, r=10%, b=4%, σ=30% → 25.3533
+        let floating = LookbackOption::floating()
+            .with_running_extremes(100.0, 120.0).unwrap()
+            .with_dividend_yield(0.06).unwrap();
+        let c = floating.price(120.0, 0.5, 0.10, 0.30, OptionType::Call).unwrap();
+        assert!((c - 25.3533).abs() < 1e-3, "{c}");
+    }
+
+    #[test]
+    fn matches_monte_carlo() {
+        let (s0, t, r, sigma) = (100.0, 1.0, 0.05, 0.3);
+        let options = [
+            LookbackOption::floating(),
+            LookbackOption::floating().with_running_extremes(90.0, 115.0).unwrap().with_dividend_yield(0.02).unwrap(),
+            LookbackOption::fixed(110.0).unwrap(),
+            LookbackOption::fixed(95.0).unwrap().with_running_extremes(92.0, 104.0).unwrap(),
+            LookbackOption::fixed(100.0).unwrap().with_dividend_yield(0.05).unwrap(),
+        ];
+        for option in options {
+            for kind in [OptionType::Call, OptionType::Put] {
+                let price = option.price(s0, t, r, sigma, kind).unwrap();
+                let (mc, std_err) = monte_carlo(&option, s0, t, r, sigma, kind);
+                assert!((price - mc).abs() < 4.0 * std_err + 0.05, "{option:?} {kind:?}: {price} vs {mc} ± {std_err}");
+            }
+        }
+    }
+
+    #[test]
+    fn zero_carry_limit_is_continuous() {
+        let (s0, t, r, sigma) = (100.0, 0.75, 0.04, 0.25);
+        for option in [LookbackOption::floating(), LookbackOption::fixed(100.0).unwrap()] {
+            for kind in [OptionType::Call, OptionType::Put] {
+                let at_zero = option.with_dividend_yield(r).unwrap().price(s0, t, r, sigma, kind).unwrap();
+                let near_zero = option.with_dividend_yield(r - 1e-6).unwrap().price(s0, t, r, sigma, kind).unwrap();
+                assert!((at_zero - near_zero).abs() < 1e-4, "{option:?} {kind:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn fixed_strike_parity_with_floating() {
+        // Con K = M, la call de strike fijo paga M_T - M, y la put flotante M_T - S_T:
+        // C_fijo(K = M) = P_flot + S e^{-qT} - M e^{-rT}
+        let (s0, t, r, q, sigma) = (100.0, 1.0, 0.05, 0.01, 0.2);
+        let max = 110.0;
+        let fixed = LookbackOption::fixed(max).unwrap().with_running_extremes(95.0, max).unwrap().with_dividend_yield(q).unwrap();
+        let floating = LookbackOption::floating().with_running_extremes(95.0, max).unwrap().with_dividend_yield(q).unwrap();
+        let c = fixed.price(s0, t, r, sigma, OptionType::Call).unwrap();
+        let p = floating.price(s0, t, r, sigma, OptionType::Put).unwrap();
+        assert!((c - (p + s0 * (-q * t).exp() - max * (-r * t).exp())).abs() < 1e-10);
+    }
+
+    #[test]
+    fn invalid_configuration() {
+        assert_eq!(LookbackOption::fixed(-1.0).unwrap_err(), FinanceError::InvalidMonetaryValue);
+        assert_eq!(LookbackOption::floating().with_running_extremes(110.0, 100.0).unwrap_err(), FinanceError::InvalidMonetaryValue);
+        let option = LookbackOption::floating().with_running_extremes(90.0, 110.0).unwrap();
+        assert_eq!(option.price(120.0, 1.0, 0.05, 0.2, OptionType::Call).unwrap_err(), FinanceError::InvalidInput);
+        assert_eq!(option.price(100.0, 1.0, 0.05, 0.0, OptionType::Call).unwrap_err(), FinanceError::InvalidInterestRate);
+        assert_eq!(option.price(100.0, 0.0, 0.05, 0.2, OptionType::Call).unwrap(), 10.0);
+    }
+}
@@ -1,13 +1,158 @@
-//! Valoración de opciones europeas
+//! Valoración de opciones
 //!
 //! Este módulo agrupa implementaciones relacionadas con opciones,
-//! empezando por el modelo de Black-Scholes-Merton (sin dividendos).
+//! empezando por el modelo de Black-Scholes-Merton (sin dividendos y con
+//! rendimiento por dividendo continuo) y los árboles binomiales y
+//! trinomiales para ejercicio europeo, americano y bermuda, y las
+//! aproximaciones analíticas de opciones americanas. También incluye
+//! opciones exóticas con fórmula cerrada (barreras, asiáticas, lookback, digitales),
+//! opciones sobre opciones (compuestas, chooser, forward-start) y opciones
+//! sobre dos activos (intercambio y spreads), y un motor de Monte Carlo para
+//! pagos dependientes de la trayectoria, con ejercicio anticipado por
+//! regresión (Longstaff-Schwartz), además de un solver de diferencias finitas
+//! para la EDP de Black-Scholes, el modelo de volatilidad estocástica de
+//! Heston, los modelos con saltos de Merton y Bates, los modelos de Lévy
+//! Variance Gamma y NIG, el modelo SABR para sonrisas de tipos y motores de
+//! Fourier (Carr-Madan y COS) para cualquier modelo con función
+//! característica conocida, y una superficie de volatilidad implícita con
+//! interpolación en varianza total y alas planas o SVI.
 
 pub mod black_scholes;
+pub mod dividends;
+pub mod binomial;
+pub mod trinomial;
+pub mod black76;
+pub mod bachelier;
+pub mod garman_kohlhagen;
+pub mod american;
+pub mod barrier;
+pub mod asian;
+pub mod lookback;
+pub mod digital;
+pub mod two_asset;
+pub mod compound;
+pub mod chooser;
+pub mod forward_start;
+pub mod monte_carlo;
+pub mod longstaff_schwartz;
+pub mod finite_difference;
+pub mod heston;
+pub mod jump_diffusion;
+pub mod fourier;
+pub mod levy;
+pub mod sabr;
+pub mod vol_surface;
 
 pub use black_scholes::{
     call_price,
     put_price,
     option_price,
+    option_greeks,
+    implied_volatility,
+    implied_volatility_dividend,
     d1_d2,
-};
\ No newline at end of file
+    call_price_dividend,
+    put_price_dividend,
+    option_price_dividend,
+    option_greeks_dividend,
+    d1_d2_dividend,
+    bivariate_normal_cdf,
+    Greeks,
+};
+
+pub use dividends::{
+    CashDividend,
+    dividends_present_value,
+    escrowed_spot,
+    escrowed_option_price,
+};
+
+pub use binomial::{BinomialTree, BinomialModel, LatticeGreeks};
+
+pub use trinomial::{TrinomialTree, TrinomialModel, LatticeBarrier};
+
+pub use black76::{
+    black76_call_price,
+    black76_put_price,
+    black76_price,
+    black76_greeks,
+    black76_implied_volatility,
+    black76_d1_d2,
+};
+
+pub use bachelier::{
+    bachelier_call_price,
+    bachelier_put_price,
+    bachelier_price,
+    bachelier_greeks,
+    bachelier_implied_volatility,
+    bachelier_d,
+    lognormal_to_normal_vol,
+    normal_to_lognormal_vol,
+};
+
+pub use garman_kohlhagen::{
+    FxMarket,
+    FxPremiumQuote,
+    FxDeltaConvention,
+    fx_option_price,
+    fx_premium,
+    fx_greeks,
+    fx_implied_volatility,
+    fx_delta,
+    fx_strike_from_delta,
+    fx_atm_dns_strike,
+    fx_smile_vols,
+};
+
+pub use american::{
+    AmericanApproximation,
+    barone_adesi_whaley,
+    bjerksund_stensland,
+};
+
+pub use barrier::{BarrierOption, BarrierMonitoring, BGK_BETA};
+
+pub use asian::{AsianOption, AverageSchedule};
+
+pub use lookback::{LookbackOption, LookbackStrike};
+
+pub use digital::{
+    cash_or_nothing_price,
+    cash_or_nothing_greeks,
+    asset_or_nothing_price,
+    asset_or_nothing_greeks,
+    gap_price,
+    gap_greeks,
+};
+
+pub use two_asset::{
+    TwoAssetMarket,
+    exchange_option_price,
+    kirk_spread_price,
+    bjerksund_stensland_spread_price,
+};
+
+pub use compound::CompoundOption;
+
+pub use chooser::ChooserOption;
+
+pub use forward_start::ForwardStartOption;
+
+pub use monte_carlo::{MonteCarlo, MonteCarloResult, PathGenerator};
+
+pub use longstaff_schwartz::{LongstaffSchwartz, LongstaffSchwartzResult, RegressionBasis};
+
+pub use finite_difference::{FiniteDifference, FdScheme, EarlyExerciseMethod, FdSurface};
+
+pub use heston::{HestonModel, HestonCalibration};
+
+pub use jump_diffusion::{LognormalJumps, MertonJumpDiffusion, BatesModel};
+
+pub use fourier::{CharacteristicFunction, BlackScholesModel, CarrMadan, CosMethod};
+
+pub use levy::{VarianceGamma, NormalInverseGaussian, LevyCalibration};
+
+pub use sabr::{SabrModel, SabrFormula, SabrCalibration};
+
+pub use vol_surface::{VolSurface, StrikeAxis, SmileInterpolation, WingExtrapolation, SviParameters};
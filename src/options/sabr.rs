@@ -0,0 +1,370 @@
+//! Modelo SABR de Hagan, Kumar, Lesniewski y Woodward (2002).
+//!
+//! Dinámica del forward bajo su medida forward:
+//! dF = α F^β dW₁,  dα = ν α dW₂,  d⟨W₁, W₂⟩ = ρ dt
+//!
+//! Volatilidad lognormal (Black-76) de Hagan, con F_m = √(FK), l = ln(F/K):
+//! σ_B = α / [F_m^{1-β} (1 + (1-β)² l²/24 + (1-β)⁴ l⁴/1920)] · z/x(z) · [1 + ε T]
+//! z = ν/α F_m^{1-β} l,  x(z) = ln[(√(1 - 2ρz + z²) + z - ρ)/(1 - ρ)]
+//! ε = (1-β)² α²/(24 F_m^{2-2β}) + ρβνα/(4 F_m^{1-β}) + (2 - 3ρ²) ν²/24
+//!
+//! Corrección de Obłój (2008): el término principal pasa a ser ν l / x(z) con
+//! z = ν (F^{1-β} - K^{1-β}) / (α(1-β)) (z = ν l/α si β = 1), que es exacto
+//! en el límite T → 0 y se comporta mejor lejos del dinero.
+//!
+//! Volatilidad normal (Bachelier) de Hagan:
+//! σ_N = α (1-β)(F - K)/(F^{1-β} - K^{1-β}) · ζ/x(ζ) · [1 + ε_N T]
+//! ζ = ν (F - K)/(α F_m^β)
+//! ε_N = -β(2-β) α²/(24 F_m^{2-2β}) + ρβνα/(4 F_m^{1-β}) + (2 - 3ρ²) ν²/24
+//!
+//! Los precios se obtienen llevando la volatilidad a Black-76 o a Bachelier.
+//! La calibración ajusta (α, ρ, ν) con β fijo a una sección de strikes de
+//! un mismo vencimiento.
+
+use crate::common::{
+    FinanceError, FinanceResult,
+    ForwardPrice, StrikePrice, TimeToMaturity, InterestRate, Volatility,
+    OptionType, VolQuote
+};
+use crate::num::levenberg_marquardt;
+use super::black_scholes::validate_inputs;
+use super::black76::black76_price;
+use super::bachelier::bachelier_price;
+
+/// Por debajo de este |z| se usa el desarrollo de z/x(z) en serie.
+const SMALL_Z: f64 = 1e-6;
+/// Por debajo de este |ln(F/K)| se toman los límites en el dinero.
+const SMALL_LOG_MONEYNESS: f64 = 1e-10;
+/// Cotas de ρ y ν durante la calibración.
+const CALIBRATION_RHO: f64 = 0.9999;
+const CALIBRATION_MAX_NU: f64 = 10.0;
+
+/// Parámetros del modelo SABR
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SabrModel {
+    /// Volatilidad inicial α
+    pub alpha: f64,
+    /// Elasticidad β ∈ [0, 1]
+    pub beta: f64,
+    /// Correlación ρ entre forward y volatilidad
+    pub rho: f64,
+    /// Volatilidad de la volatilidad ν
+    pub nu: f64,
+}
+
+/// Aproximación usada para la volatilidad implícita
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SabrFormula {
+    /// Volatilidad lognormal de Hagan (2002)
+    Hagan,
+    /// Volatilidad lognormal con la corrección de Obłój (2008)
+    Obloj,
+    /// Volatilidad normal (Bachelier) de Hagan (2002)
+    HaganNormal,
+}
+
+/// Resultado de una calibración SABR
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SabrCalibration {
+    /// Modelo calibrado (con el β fijado)
+    pub model: SabrModel,
+    /// Raíz del error cuadrático medio en volatilidad
+    pub rmse: f64,
+    /// Iteraciones de Levenberg-Marquardt
+    pub iterations: usize,
+}
+
+impl SabrFormula {
+    /// `true` si la volatilidad es normal (absoluta) en lugar de lognormal.
+    pub fn is_normal(&self) -> bool {
+        matches!(self, SabrFormula::HaganNormal)
+    }
+}
+
+impl SabrModel {
+    /// Crea el modelo validando los parámetros.
+    ///
+    /// # Errores
+    /// * `InvalidInterestRate` - Si α no es positiva o ν es negativa
+    /// * `InvalidInput` - Si β está fuera de [0, 1] o |ρ| ≥ 1
+    pub fn new(alpha: f64, beta: f64, rho: f64, nu: f64) -> FinanceResult<Self> {
+        if !alpha.is_finite() || !nu.is_finite() || alpha <= 0.0 || nu < 0.0 {
+            return Err(FinanceError::InvalidInterestRate);
+        }
+        if !(0.0..=1.0).contains(&beta) || !rho.is_finite() || rho.abs() >= 1.0 {
+            return Err(FinanceError::InvalidInput);
+        }
+        Ok(SabrModel { alpha, beta, rho, nu })
+    }
+
+    /// Volatilidad lognormal de Hagan.
+    ///
+    /// # Ejemplos
+    /// ```
+    /// use quant_finance::options::SabrModel;
+    ///
+    /// let model = SabrModel::new(0.2, 1.0, 0.0, 0.0).unwrap();
+    /// // Con ν = 0 y β = 1 el modelo es Black-76 con σ = α
+    /// assert!((model.lognormal_volatility(100.0, 120.0, 2.0).unwrap() - 0.2).abs() < 1e-15);
+    /// ```
+    ///
+    /// # Errores
+    /// * `InvalidMonetaryValue` - Si F o K no son positivos
+    /// * `InvalidPeriods` - Si `t` es negativo
+    pub fn lognormal_volatility(&self, f: ForwardPrice, k: StrikePrice, t: TimeToMaturity) -> FinanceResult<Volatility> {
+        validate_inputs(f, k, t, 0.0, 0.0)?;
+        let SabrModel { alpha, beta, rho, nu } = *self;
+        let one_minus_beta = 1.0 - beta;
+        let log_fk = (f / k).ln();
+        let fk_power = (f * k).powf(0.5 * one_minus_beta);
+        let expansion = 1.0 + one_minus_beta.powi(2) * log_fk.powi(2) / 24.0 + one_minus_beta.powi(4) * log_fk.powi(4) / 1920.0;
+        let z = nu / alpha * fk_power * log_fk;
+        let correction = 1.0
+            + (one_minus_beta.powi(2) * alpha * alpha / (24.0 * fk_power * fk_power)
+                + rho * beta * nu * alpha / (4.0 * fk_power)
+                + (2.0 - 3.0 * rho * rho) * nu * nu / 24.0) * t;
+        Ok(alpha / (fk_power * expansion) * z_over_x(z, rho) * correction)
+    }
+
+    /// Volatilidad lognormal con la corrección de Obłój.
+    ///
+    /// # Errores
+    /// Los mismos que [`SabrModel::lognormal_volatility`].
+    pub fn obloj_volatility(&self, f: ForwardPrice, k: StrikePrice, t: TimeToMaturity) -> FinanceResult<Volatility> {
+        validate_inputs(f, k, t, 0.0, 0.0)?;
+        let SabrModel { alpha, beta, rho, nu } = *self;
+        let log_fk = (f / k).ln();
+        let fk_power = (f * k).powf(0.5 * (1.0 - beta));
+        // ν l / x(z) = α · l/D · z/x(z), con D = (F^{1-β} - K^{1-β})/(1-β)
+        let distance = power_distance(f, k, beta);
+        let leading = if log_fk.abs() < SMALL_LOG_MONEYNESS { 1.0 / fk_power } else { log_fk / distance };
+        let z = nu / alpha * distance;
+        let correction = 1.0
+            + ((1.0 - beta).powi(2) * alpha * alpha / (24.0 * fk_power * fk_power)
+                + rho * beta * nu * alpha / (4.0 * fk_power)
+                + (2.0 - 3.0 * rho * rho) * nu * nu / 24.0) * t;
+        Ok(alpha * leading * z_over_x(z, rho) * correction)
+    }
+
+    /// Volatilidad normal (Bachelier) de Hagan.
+    ///
+    /// # Errores
+    /// Los mismos que [`SabrModel::lognormal_volatility`].
+    pub fn normal_volatility(&self, f: ForwardPrice, k: StrikePrice, t: TimeToMaturity) -> FinanceResult<Volatility> {
+        validate_inputs(f, k, t, 0.0, 0.0)?;
+        let SabrModel { alpha, beta, rho, nu } = *self;
+        let f_mid = (f * k).sqrt();
+        let mid_power = f_mid.powf(1.0 - beta);
+        // (F - K)/D → F_m^β en el dinero
+        let leading = if (f / k).ln().abs() < SMALL_LOG_MONEYNESS { f_mid.powf(beta) } else { (f - k) / power_distance(f, k, beta) };
+        let zeta = nu * (f - k) / (alpha * f_mid.powf(beta));
+        let correction = 1.0
+            + (-beta * (2.0 - beta) * alpha * alpha / (24.0 * mid_power * mid_power)
+                + rho * beta * nu * alpha / (4.0 * mid_power)
+                + (2.0 - 3.0 * rho * rho) * nu * nu / 24.0) * t;
+        Ok(alpha * leading * z_over_x(zeta, rho) * correction)
+    }
+
+    /// Volatilidad implícita según `formula` (lognormal o normal).
+    pub fn volatility(&self, f: ForwardPrice, k: StrikePrice, t: TimeToMaturity, formula: SabrFormula) -> FinanceResult<Volatility> {
+        match formula {
+            SabrFormula::Hagan => self.lognormal_volatility(f, k, t),
+            SabrFormula::Obloj => self.obloj_volatility(f, k, t),
+            SabrFormula::HaganNormal => self.normal_volatility(f, k, t),
+        }
+    }
+
+    /// Precio europeo: la volatilidad de `formula` se lleva a Black-76
+    /// (lognormal) o a Bachelier (normal).
+    ///
+    /// # Ejemplos
+    /// ```
+    /// use quant_finance::OptionType;
+    /// use quant_finance::options::{SabrModel, SabrFormula};
+    ///
+    /// let model = SabrModel::new(0.03, 0.5, -0.3, 0.4).unwrap();
+    /// let (f, t, r) = (0.04, 5.0, 0.03);
+    /// let lognormal = model.price(f, 0.05, t, r, SabrFormula::Obloj, OptionType::Call).unwrap();
+    /// let normal = model.price(f, 0.05, t, r, SabrFormula::HaganNormal, OptionType::Call).unwrap();
+    /// assert!((lognormal - normal).abs() < 1e-5);
+    /// ```
+    ///
+    /// # Errores
+    /// Los de la fórmula de volatilidad y los del pricer correspondiente.
+    pub fn price(&self, f: ForwardPrice, k: StrikePrice, t: TimeToMaturity, r: InterestRate, formula: SabrFormula, kind: OptionType) -> FinanceResult<f64> {
+        let sigma = self.volatility(f, k, t, formula)?;
+        if formula.is_normal() {
+            bachelier_price(f, k, t, r, sigma, kind)
+        } else {
+            black76_price(f, k, t, r, sigma, kind)
+        }
+    }
+
+    /// Calibra (α, ρ, ν) con β fijo a las volatilidades de una sección de
+    /// strikes, expresadas en la convención de `formula`.
+    ///
+    /// Todas las cotizaciones deben compartir el vencimiento, ya que el
+    /// forward `f` es único. El punto de partida toma α de la cotización
+    /// más cercana al dinero, ρ = 0 y ν = 0.5.
+    ///
+    /// # Errores
+    /// * `InvalidInput` - Si hay menos de tres cotizaciones, difieren los
+    ///   vencimientos, alguna volatilidad no es positiva o β está fuera de [0, 1]
+    /// * `InvalidMonetaryValue` - Si F o algún strike no son positivos
+    pub fn calibrate(quotes: &[VolQuote], f: ForwardPrice, beta: f64, formula: SabrFormula) -> FinanceResult<SabrCalibration> {
+        if quotes.len() < 3 || !(0.0..=1.0).contains(&beta) {
+            return Err(FinanceError::InvalidInput);
+        }
+        let t = quotes[0].maturity;
+        for quote in quotes {
+            validate_inputs(f, quote.strike, quote.maturity, 0.0, 0.0)?;
+            if quote.maturity != t || !quote.volatility.is_finite() || quote.volatility <= 0.0 {
+                return Err(FinanceError::InvalidInput);
+            }
+        }
+
+        // α inicial a partir del término principal en el dinero
+        let atm = quotes.iter()
+            .min_by(|a, b| (a.strike / f).ln().abs().total_cmp(&(b.strike / f).ln().abs()))
+            .map_or(0.0, |quote| quote.volatility);
+        let alpha0 = if formula.is_normal() { atm / f.powf(beta) } else { atm * f.powf(1.0 - beta) };
+
+        let residuals = |p: &[f64]| -> FinanceResult<Vec<f64>> {
+            let model = SabrModel::new(p[0], beta, p[1], p[2])?;
+            quotes.iter()
+                .map(|quote| Ok(model.volatility(f, quote.strike, t, formula)? - quote.volatility))
+                .collect()
+        };
+        let lower = [alpha0 * 1e-3, -CALIBRATION_RHO, 0.0];
+        let upper = [alpha0 * 1e3, CALIBRATION_RHO, CALIBRATION_MAX_NU];
+        let fit = levenberg_marquardt(residuals, &[alpha0, 0.0, 0.5], &lower, &upper, 200, 1e-14)?;
+        Ok(SabrCalibration {
+            model: SabrModel::new(fit.x[0], beta, fit.x[1], fit.x[2])?,
+            rmse: (fit.cost / quotes.len() as f64).sqrt(),
+            iterations: fit.iterations,
+        })
+    }
+}
+
+/// z/x(z), con su desarrollo 1 - ρz/2 + (2 - 3ρ²)z²/12 cerca de cero.
+fn z_over_x(z: f64, rho: f64) -> f64 {
+    if z.abs() < SMALL_Z {
+        return 1.0 - 0.5 * rho * z + (2.0 - 3.0 * rho * rho) * z * z / 12.0;
+    }
+    let x = (((1.0 - 2.0 * rho * z + z * z).sqrt() + z - rho) / (1.0 - rho)).ln();
+    z / x
+}
+
+/// (F^{1-β} - K^{1-β})/(1-β), o ln(F/K) si β = 1.
+fn power_distance(f: ForwardPrice, k: StrikePrice, beta: f64) -> f64 {
+    if beta == 1.0 {
+        (f / k).ln()
+    } else {
+        (f.powf(1.0 - beta) - k.powf(1.0 - beta)) / (1.0 - beta)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::options::lognormal_to_normal_vol;
+
+    fn model() -> SabrModel {
+        SabrModel::new(0.035, 0.5, -0.25, 0.45).unwrap()
+    }
+
+    #[test]
+    fn degenerate_cases() {
+        // ν = 0, β = 0: modelo normal con σ_N = α (salvo el término en T, nulo)
+        let normal = SabrModel::new(0.01, 0.0, 0.0, 0.0).unwrap();
+        for k in [0.01, 0.03, 0.06] {
+            assert!((normal.normal_volatility(0.03, k, 5.0).unwrap() - 0.01).abs() < 1e-15);
+        }
+        // ν = 0, β = 1: Black-76 con σ = α para ambas fórmulas lognormales
+        let lognormal = SabrModel::new(0.25, 1.0, 0.5, 0.0).unwrap();
+        for k in [50.0, 100.0, 180.0] {
+            assert!((lognormal.lognormal_volatility(100.0, k, 3.0).unwrap() - 0.25).abs() < 1e-15);
+            assert!((lognormal.obloj_volatility(100.0, k, 3.0).unwrap() - 0.25).abs() < 1e-15);
+        }
+        // Serie de z/x(z) continua con la fórmula cerrada
+        assert!((z_over_x(0.99 * SMALL_Z, -0.4) - z_over_x(1.01 * SMALL_Z, -0.4)).abs() < 1e-8);
+    }
+
+    #[test]
+    fn formulas_agree_at_the_money_and_nearby() {
+        let m = model();
+        let (f, t) = (0.04, 2.0);
+        let hagan = m.lognormal_volatility(f, f, t).unwrap();
+        assert!((hagan - m.obloj_volatility(f, f, t).unwrap()).abs() < 1e-15);
+        // Límite continuo al acercarse al dinero
+        let near = m.lognormal_volatility(f, f * (1.0 + 1e-9), t).unwrap();
+        assert!((near - hagan).abs() < 1e-9);
+        let near = m.normal_volatility(f, f * (1.0 + 1e-9), t).unwrap();
+        assert!((near - m.normal_volatility(f, f, t).unwrap()).abs() < 1e-11);
+        // Las tres aproximaciones difieren en términos de orden superior
+        for k in [0.02, 0.03, 0.04, 0.05, 0.07] {
+            let hagan = m.lognormal_volatility(f, k, t).unwrap();
+            let obloj = m.obloj_volatility(f, k, t).unwrap();
+            let normal = lognormal_to_normal_vol(f, k, t, hagan).unwrap();
+            let hagan_normal = m.normal_volatility(f, k, t).unwrap();
+            assert!((hagan / obloj - 1.0).abs() < 1e-2, "{k}: {hagan} vs {obloj}");
+            assert!((normal / hagan_normal - 1.0).abs() < 1e-2, "{k}: {normal} vs {hagan_normal}");
+        }
+    }
+
+    #[test]
+    fn smile_shape() {
+        let (f, t) = (0.04, 1.0);
+        // ρ < 0: sesgo negativo; ρ = 0 con β = 1: sonrisa simétrica en ln(K/F)
+        let m = model();
+        let vols: Vec<f64> = [0.03, 0.04, 0.05].iter()
+            .map(|&k| m.obloj_volatility(f, k, t).unwrap())
+            .collect();
+        assert!(vols[0] > vols[1] && vols[1] > vols[2], "{vols:?}");
+        let symmetric = SabrModel::new(0.2, 1.0, 0.0, 0.6).unwrap();
+        let low = symmetric.lognormal_volatility(100.0, 100.0 / 1.3, t).unwrap();
+        let high = symmetric.lognormal_volatility(100.0, 100.0 * 1.3, t).unwrap();
+        assert!((low - high).abs() < 1e-14);
+        assert!(low > symmetric.lognormal_volatility(100.0, 100.0, t).unwrap());
+        // Paridad put-call con el precio de Black-76
+        let df = (-0.02f64).exp();
+        let c = m.price(f, 0.05, t, 0.02, SabrFormula::Hagan, OptionType::Call).unwrap();
+        let p = m.price(f, 0.05, t, 0.02, SabrFormula::Hagan, OptionType::Put).unwrap();
+        assert!((c - p - df * (f - 0.05)).abs() < 1e-15);
+    }
+
+    #[test]
+    fn calibration_recovers_parameters() {
+        let (f, t) = (0.04, 2.0);
+        let truth = model();
+        for formula in [SabrFormula::Hagan, SabrFormula::Obloj, SabrFormula::HaganNormal] {
+            let quotes: Vec<VolQuote> = [0.015, 0.02, 0.03, 0.035, 0.04, 0.045, 0.05, 0.06, 0.08].iter()
+                .map(|&strike| VolQuote { strike, maturity: t, volatility: truth.volatility(f, strike, t, formula).unwrap() })
+                .collect();
+            let calibration = SabrModel::calibrate(&quotes, f, truth.beta, formula).unwrap();
+            let fitted = calibration.model;
+            assert!(calibration.rmse < 1e-8, "{formula:?}: {calibration:?}");
+            assert!((fitted.alpha - truth.alpha).abs() < 1e-6);
+            assert!((fitted.rho - truth.rho).abs() < 1e-4);
+            assert!((fitted.nu - truth.nu).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn invalid_inputs() {
+        assert_eq!(SabrModel::new(0.0, 0.5, 0.0, 0.3).unwrap_err(), FinanceError::InvalidInterestRate);
+        assert_eq!(SabrModel::new(0.2, 0.5, 0.0, -0.1).unwrap_err(), FinanceError::InvalidInterestRate);
+        assert_eq!(SabrModel::new(0.2, 1.5, 0.0, 0.3).unwrap_err(), FinanceError::InvalidInput);
+        assert_eq!(SabrModel::new(0.2, 0.5, -1.0, 0.3).unwrap_err(), FinanceError::InvalidInput);
+        let m = model();
+        assert_eq!(m.lognormal_volatility(0.04, -0.01, 1.0).unwrap_err(), FinanceError::InvalidMonetaryValue);
+        assert_eq!(m.normal_volatility(0.04, 0.03, -1.0).unwrap_err(), FinanceError::InvalidPeriods);
+        let quotes = [
+            VolQuote { strike: 0.03, maturity: 1.0, volatility: 0.3 },
+            VolQuote { strike: 0.04, maturity: 1.0, volatility: 0.25 },
+            VolQuote { strike: 0.05, maturity: 2.0, volatility: 0.22 },
+        ];
+        assert_eq!(SabrModel::calibrate(&quotes, 0.04, 0.5, SabrFormula::Hagan).unwrap_err(), FinanceError::InvalidInput);
+        assert_eq!(SabrModel::calibrate(&quotes[..2], 0.04, 0.5, SabrFormula::Hagan).unwrap_err(), FinanceError::InvalidInput);
+    }
+}
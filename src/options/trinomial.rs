@@ -0,0 +1,395 @@
+//! Árboles trinomiales recombinantes para ejercicio europeo, americano y
+//! bermuda, con barreras sobre los nodos.
+//!
+//! Con Δx = λσ√Δt, cada nodo sube a S e^{Δx}, se queda en S o baja a S e^{-Δx}.
+//! - Kamrad-Ritchken (1991), momentos en logaritmos, con ν = r - σ²/2:
+//!   p_u = 1/(2λ²) + ν√Δt/(2λσ), p_m = 1 - 1/λ², p_d = 1/(2λ²) - ν√Δt/(2λσ)
+//! - Boyle (1988), momentos en precios, con M = e^{rΔt}, V = M²(e^{σ²Δt} - 1):
+//!   p_u = [(V + M² - M) u - (M - 1)] / [(u - 1)(u² - 1)]
+//!   p_d = [(V + M² - M) u² - u³ (M - 1)] / [(u - 1)(u² - 1)]
+//!
+//! Barreras: siguiendo a Ritchken (1995) se ajusta λ ≥ λ₀ de forma que la
+//! barrera caiga exactamente sobre una capa de nodos, lo que elimina el error
+//! de posición de la barrera. Las knock-out se valoran directamente (con el
+//! rebate pagado al tocar) y las knock-in europeas por paridad in-out.
+
+use crate::common::{
+    FinanceError, FinanceResult,
+    MonetaryValue, SpotPrice, StrikePrice, TimeToMaturity, InterestRate, Volatility,
+    OptionType, ExerciseStyle, BarrierType
+};
+use super::black_scholes::{validate_inputs, EPS_TIME, EPS_VOL};
+use super::binomial::{LatticeGreeks, exercise_steps, validate_exercise};
+
+/// Parametrización del árbol trinomial
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TrinomialModel {
+    Boyle,
+    KamradRitchken,
+}
+
+/// Barrera de observación continua aplicada sobre los nodos
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LatticeBarrier {
+    /// Tipo de barrera
+    pub barrier_type: BarrierType,
+    /// Nivel de la barrera (H)
+    pub level: SpotPrice,
+    /// Rebate: pagado al tocar en las knock-out y al vencimiento si no se
+    /// toca en las knock-in
+    pub rebate: MonetaryValue,
+}
+
+/// Configuración de un árbol trinomial
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrinomialTree {
+    /// Parametrización de las probabilidades
+    pub model: TrinomialModel,
+    /// Número de pasos temporales
+    pub steps: usize,
+    /// Parámetro de dispersión λ (Δx = λσ√Δt)
+    pub lambda: f64,
+    /// Estilo de ejercicio
+    pub exercise: ExerciseStyle,
+    /// Barrera opcional
+    pub barrier: Option<LatticeBarrier>,
+}
+
+impl TrinomialTree {
+    /// λ por defecto: √(3/2), que da p_m = 1/3 en Kamrad-Ritchken.
+    pub const DEFAULT_LAMBDA: f64 = 1.224_744_871_391_589;
+
+    /// Crea un árbol Kamrad-Ritchken europeo con `steps` pasos.
+    ///
+    /// # Errores
+    /// * `InvalidPeriods` - Si `steps` es cero
+    pub fn new(steps: usize) -> FinanceResult<Self> {
+        if steps == 0 {
+            return Err(FinanceError::InvalidPeriods);
+        }
+        Ok(TrinomialTree {
+            model: TrinomialModel::KamradRitchken,
+            steps,
+            lambda: Self::DEFAULT_LAMBDA,
+            exercise: ExerciseStyle::European,
+            barrier: None,
+        })
+    }
+
+    /// Cambia la parametrización del árbol.
+    pub fn with_model(mut self, model: TrinomialModel) -> Self {
+        self.model = model;
+        self
+    }
+
+    /// Cambia el parámetro de dispersión λ.
+    ///
+    /// # Errores
+    /// * `InvalidInput` - Si λ < 1 (la probabilidad central sería negativa)
+    pub fn with_lambda(mut self, lambda: f64) -> FinanceResult<Self> {
+        if !lambda.is_finite() || lambda < 1.0 {
+            return Err(FinanceError::InvalidInput);
+        }
+        self.lambda = lambda;
+        Ok(self)
+    }
+
+    /// Cambia el estilo de ejercicio.
+    pub fn with_exercise(mut self, exercise: ExerciseStyle) -> FinanceResult<Self> {
+        validate_exercise(&exercise)?;
+        self.exercise = exercise;
+        Ok(self)
+    }
+
+    /// Añade una barrera sobre los nodos.
+    ///
+    /// # Errores
+    /// * `InvalidMonetaryValue` - Si el nivel no es positivo o el rebate es negativo
+    pub fn with_barrier(mut self, barrier: LatticeBarrier) -> FinanceResult<Self> {
+        if !barrier.level.is_finite() || barrier.level <= 0.0 || !barrier.rebate.is_finite() || barrier.rebate < 0.0 {
+            return Err(FinanceError::InvalidMonetaryValue);
+        }
+        self.barrier = Some(barrier);
+        Ok(self)
+    }
+
+    /// Precio por inducción hacia atrás.
+    ///
+    /// # Errores
+    /// * `InvalidInterestRate` - Si `sigma` es nula o negativa
+    /// * `InvalidInput` - Si alguna probabilidad sale de [0, 1], si la barrera
+    ///   está a menos de un paso del spot, o si se pide una knock-in no europea
+    pub fn price(&self, s0: SpotPrice, k: StrikePrice, t: TimeToMaturity, r: InterestRate, sigma: Volatility, kind: OptionType) -> FinanceResult<f64> {
+        validate_inputs(s0, k, t, r, sigma)?;
+        if t < EPS_TIME {
+            return Ok(self.expiry_value(s0, k, kind));
+        }
+        Ok(self.value_levels(s0, k, t, r, sigma, kind)?.0[0])
+    }
+
+    /// Precio, delta, gamma y theta a partir de los nodos del paso 1
+    /// (el nodo central coincide con S0).
+    ///
+    /// # Errores
+    /// * `InvalidPeriods` - Si `t < EPS_TIME`
+    pub fn greeks(&self, s0: SpotPrice, k: StrikePrice, t: TimeToMaturity, r: InterestRate, sigma: Volatility, kind: OptionType) -> FinanceResult<LatticeGreeks> {
+        validate_inputs(s0, k, t, r, sigma)?;
+        if t < EPS_TIME {
+            return Err(FinanceError::InvalidPeriods);
+        }
+        let (v0, v1, dx, dt) = self.value_levels(s0, k, t, r, sigma, kind)?;
+        let (s_down, s_up) = (s0 * (-dx).exp(), s0 * dx.exp());
+
+        let delta = (v1[2] - v1[0]) / (s_up - s_down);
+        let delta_up = (v1[2] - v1[1]) / (s_up - s0);
+        let delta_down = (v1[1] - v1[0]) / (s0 - s_down);
+        let gamma = (delta_up - delta_down) / (0.5 * (s_up - s_down));
+        let theta = (v1[1] - v0[0]) / dt;
+
+        Ok(LatticeGreeks { price: v0[0], delta, gamma, theta })
+    }
+
+    /// Valor cuando ya no queda tiempo.
+    fn expiry_value(&self, s0: SpotPrice, k: StrikePrice, kind: OptionType) -> f64 {
+        match self.barrier {
+            Some(b) if b.barrier_type.is_breached(s0, b.level) => {
+                if b.barrier_type.is_knock_in() { kind.payoff(s0, k) } else { b.rebate }
+            }
+            Some(b) if b.barrier_type.is_knock_in() => b.rebate,
+            _ => kind.payoff(s0, k),
+        }
+    }
+
+    /// Valores en los pasos 0 y 1 junto con (Δx, Δt), aplicando paridad in-out
+    /// para las knock-in.
+    fn value_levels(&self, s0: SpotPrice, k: StrikePrice, t: TimeToMaturity, r: InterestRate, sigma: Volatility, kind: OptionType) -> FinanceResult<(Vec<f64>, Vec<f64>, f64, f64)> {
+        if sigma < EPS_VOL {
+            return Err(FinanceError::InvalidInterestRate);
+        }
+        let payoff = |s: f64| kind.payoff(s, k);
+
+        let barrier = match self.barrier {
+            None => return self.roll_back(s0, t, r, sigma, &payoff, None),
+            Some(b) => b,
+        };
+
+        if !barrier.barrier_type.is_knock_in() {
+            return self.roll_back(s0, t, r, sigma, &payoff, Some(barrier));
+        }
+        if self.exercise != ExerciseStyle::European {
+            return Err(FinanceError::InvalidInput);
+        }
+
+        // Paridad in-out: KI = vanilla - KO(sin rebate) + rebate × no-touch
+        let out_type = if barrier.barrier_type.is_down() { BarrierType::DownAndOut } else { BarrierType::UpAndOut };
+        let knock_out = LatticeBarrier { barrier_type: out_type, rebate: 0.0, ..barrier };
+        let vanilla = self.roll_back(s0, t, r, sigma, &payoff, None)?;
+        let (out0, out1, dx, dt) = self.roll_back(s0, t, r, sigma, &payoff, Some(knock_out))?;
+        let (touch0, touch1, _, _) = self.roll_back(s0, t, r, sigma, &|_| barrier.rebate, Some(knock_out))?;
+
+        let combine = |v: &[f64], o: &[f64], n: &[f64]| -> Vec<f64> {
+            v.iter().zip(o).zip(n).map(|((v, o), n)| v - o + n).collect()
+        };
+        Ok((combine(&vanilla.0, &out0, &touch0), combine(&vanilla.1, &out1, &touch1), dx, dt))
+    }
+
+    /// Inducción hacia atrás con un payoff arbitrario y barrera knock-out opcional.
+    ///
+    /// El mismo payoff se usa al vencimiento y en las fechas de ejercicio.
+    fn roll_back<P: Fn(f64) -> f64>(&self, s0: SpotPrice, t: TimeToMaturity, r: InterestRate, sigma: Volatility, payoff: &P, knock_out: Option<LatticeBarrier>) -> FinanceResult<(Vec<f64>, Vec<f64>, f64, f64)> {
+        let n = self.steps;
+        let dt = t / n as f64;
+        let sigma_sqrt_dt = sigma * dt.sqrt();
+
+        let lambda = match self.barrier {
+            Some(b) => aligned_lambda(self.lambda, s0, b.level, sigma_sqrt_dt)?,
+            None => self.lambda,
+        };
+        let dx = lambda * sigma_sqrt_dt;
+        let (p_u, p_m, p_d) = self.probabilities(lambda, dx, dt, r, sigma);
+        if [p_u, p_m, p_d].iter().any(|p| !(0.0..=1.0).contains(p)) {
+            return Err(FinanceError::InvalidInput);
+        }
+        let disc = (-r * dt).exp();
+
+        let node = |j: i64| s0 * (j as f64 * dx).exp();
+        let knocked = |s: f64| knock_out.is_some_and(|b| b.barrier_type.is_breached(s, b.level));
+        let rebate = knock_out.map_or(0.0, |b| b.rebate);
+        let exercisable = exercise_steps(&self.exercise, t, n);
+
+        // Índice j + i: nodos j = -i..=i en el paso i
+        let mut values: Vec<f64> = (-(n as i64)..=n as i64)
+            .map(|j| if knocked(node(j)) { rebate } else { payoff(node(j)) })
+            .collect();
+        let mut level_one = Vec::new();
+
+        for i in (0..n).rev() {
+            let width = 2 * i + 1;
+            for idx in 0..width {
+                let j = idx as i64 - i as i64;
+                let s = node(j);
+                let mut v = disc * (p_d * values[idx] + p_m * values[idx + 1] + p_u * values[idx + 2]);
+                if exercisable[i] {
+                    v = v.max(payoff(s));
+                }
+                values[idx] = if knocked(s) { rebate } else { v };
+            }
+            values.truncate(width);
+            if i == 1 {
+                level_one = values.clone();
+            }
+        }
+        if n == 1 {
+            level_one = (-1..=1).map(|j| if knocked(node(j)) { rebate } else { payoff(node(j)) }).collect();
+        }
+        Ok((values, level_one, dx, dt))
+    }
+
+    /// Probabilidades (p_u, p_m, p_d) del modelo.
+    fn probabilities(&self, lambda: f64, dx: f64, dt: f64, r: InterestRate, sigma: Volatility) -> (f64, f64, f64) {
+        match self.model {
+            TrinomialModel::KamradRitchken => {
+                let drift = (r - 0.5 * sigma * sigma) * dt.sqrt() / (2.0 * lambda * sigma);
+                let base = 1.0 / (2.0 * lambda * lambda);
+                (base + drift, 1.0 - 2.0 * base, base - drift)
+            }
+            TrinomialModel::Boyle => {
+                let u = dx.exp();
+                let m = (r * dt).exp();
+                let v = m * m * ((sigma * sigma * dt).exp() - 1.0);
+                let denom = (u - 1.0) * (u * u - 1.0);
+                let p_u = ((v + m * m - m) * u - (m - 1.0)) / denom;
+                let p_d = ((v + m * m - m) * u * u - u * u * u * (m - 1.0)) / denom;
+                (p_u, 1.0 - p_u - p_d, p_d)
+            }
+        }
+    }
+}
+
+/// λ ≥ λ₀ tal que ln(S0/H) es un múltiplo entero de λσ√Δt (Ritchken 1995).
+fn aligned_lambda(lambda: f64, s0: SpotPrice, level: SpotPrice, sigma_sqrt_dt: f64) -> FinanceResult<f64> {
+    let distance = (s0 / level).ln().abs();
+    if distance == 0.0 {
+        return Ok(lambda);
+    }
+    let layers = (distance / (lambda * sigma_sqrt_dt)).floor();
+    if layers < 1.0 {
+        return Err(FinanceError::InvalidInput);
+    }
+    Ok(distance / (layers * sigma_sqrt_dt))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::options::{call_price, put_price, option_greeks, BinomialTree};
+
+    const MODELS: [TrinomialModel; 2] = [TrinomialModel::Boyle, TrinomialModel::KamradRitchken];
+
+    /// Down-and-out call con K ≥ H (Merton 1973): C - (H/S)^{2r/σ² - 1} C(H²/S)
+    fn down_and_out_call(s0: f64, k: f64, h: f64, t: f64, r: f64, sigma: f64) -> f64 {
+        let power = 2.0 * r / (sigma * sigma) - 1.0;
+        call_price(s0, k, t, r, sigma).unwrap() - (h / s0).powf(power) * call_price(h * h / s0, k, t, r, sigma).unwrap()
+    }
+
+    #[test]
+    fn converges_to_black_scholes() {
+        let (s0, k, t, r, sigma) = (100.0, 95.0, 0.75, 0.04, 0.3);
+        for model in MODELS {
+            let tree = TrinomialTree::new(500).unwrap().with_model(model);
+            let c = tree.price(s0, k, t, r, sigma, OptionType::Call).unwrap();
+            let p = tree.price(s0, k, t, r, sigma, OptionType::Put).unwrap();
+            assert!((c - call_price(s0, k, t, r, sigma).unwrap()).abs() < 5e-3, "{model:?}");
+            assert!((p - put_price(s0, k, t, r, sigma).unwrap()).abs() < 5e-3, "{model:?}");
+        }
+    }
+
+    #[test]
+    fn american_put_matches_binomial() {
+        let (s0, k, t, r, sigma) = (50.0, 50.0, 5.0 / 12.0, 0.10, 0.40);
+        let binomial = BinomialTree::new(2000).unwrap()
+            .with_exercise(ExerciseStyle::American).unwrap()
+            .price(s0, k, t, r, sigma, OptionType::Put).unwrap();
+        for model in MODELS {
+            let tree = TrinomialTree::new(1000).unwrap().with_model(model).with_exercise(ExerciseStyle::American).unwrap();
+            let p = tree.price(s0, k, t, r, sigma, OptionType::Put).unwrap();
+            assert!((p - 4.2842).abs() < 2e-3, "{model:?}: {p}");
+            assert!((p - binomial).abs() < 2e-3, "{model:?}");
+        }
+    }
+
+    #[test]
+    fn bermudan_lies_between_european_and_american() {
+        let (s0, k, t, r, sigma) = (100.0, 110.0, 1.0, 0.06, 0.2);
+        let base = TrinomialTree::new(400).unwrap();
+        let p_eu = base.price(s0, k, t, r, sigma, OptionType::Put).unwrap();
+        let p_be = base.clone().with_exercise(ExerciseStyle::Bermudan(vec![0.25, 0.5, 0.75])).unwrap()
+            .price(s0, k, t, r, sigma, OptionType::Put).unwrap();
+        let p_am = base.with_exercise(ExerciseStyle::American).unwrap()
+            .price(s0, k, t, r, sigma, OptionType::Put).unwrap();
+        assert!(p_eu < p_be && p_be < p_am);
+    }
+
+    #[test]
+    fn down_and_out_call_matches_closed_form() {
+        let (s0, k, h, t, r, sigma) = (100.0, 100.0, 90.0, 1.0, 0.05, 0.25);
+        let expected = down_and_out_call(s0, k, h, t, r, sigma);
+        let barrier = LatticeBarrier { barrier_type: BarrierType::DownAndOut, level: h, rebate: 0.0 };
+        for model in MODELS {
+            let tree = TrinomialTree::new(600).unwrap().with_model(model).with_barrier(barrier).unwrap();
+            let c = tree.price(s0, k, t, r, sigma, OptionType::Call).unwrap();
+            assert!((c - expected).abs() < 0.02, "{model:?}: {c} vs {expected}");
+        }
+    }
+
+    #[test]
+    fn in_out_parity() {
+        let (s0, k, t, r, sigma) = (100.0, 105.0, 1.0, 0.03, 0.2);
+        let tree = TrinomialTree::new(300).unwrap();
+        for (out_type, in_type, h) in [(BarrierType::DownAndOut, BarrierType::DownAndIn, 85.0), (BarrierType::UpAndOut, BarrierType::UpAndIn, 125.0)] {
+            for kind in [OptionType::Call, OptionType::Put] {
+                let vanilla = tree.price(s0, k, t, r, sigma, kind).unwrap();
+                let out = tree.clone().with_barrier(LatticeBarrier { barrier_type: out_type, level: h, rebate: 0.0 }).unwrap()
+                    .price(s0, k, t, r, sigma, kind).unwrap();
+                let knock_in = tree.clone().with_barrier(LatticeBarrier { barrier_type: in_type, level: h, rebate: 0.0 }).unwrap()
+                    .price(s0, k, t, r, sigma, kind).unwrap();
+                assert!((out + knock_in - vanilla).abs() < 5e-3, "{in_type:?} {kind:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn knock_out_rebate_adds_value() {
+        let (s0, k, t, r, sigma) = (100.0, 100.0, 1.0, 0.05, 0.3);
+        let no_rebate = LatticeBarrier { barrier_type: BarrierType::UpAndOut, level: 130.0, rebate: 0.0 };
+        let with_rebate = LatticeBarrier { rebate: 5.0, ..no_rebate };
+        let tree = TrinomialTree::new(300).unwrap();
+        let c0 = tree.clone().with_barrier(no_rebate).unwrap().price(s0, k, t, r, sigma, OptionType::Call).unwrap();
+        let c1 = tree.with_barrier(with_rebate).unwrap().price(s0, k, t, r, sigma, OptionType::Call).unwrap();
+        assert!(c1 > c0 && c1 - c0 < 5.0);
+    }
+
+    #[test]
+    fn tree_greeks_match_black_scholes() {
+        let (s0, k, t, r, sigma) = (100.0, 100.0, 1.0, 0.05, 0.2);
+        for kind in [OptionType::Call, OptionType::Put] {
+            let bs = option_greeks(s0, k, t, r, sigma, kind).unwrap();
+            let g = TrinomialTree::new(800).unwrap().greeks(s0, k, t, r, sigma, kind).unwrap();
+            assert!((g.delta - bs.delta).abs() < 2e-3);
+            assert!((g.gamma - bs.gamma).abs() < 2e-4);
+            assert!((g.theta - bs.theta).abs() < 2e-2);
+        }
+    }
+
+    #[test]
+    fn invalid_configuration() {
+        assert_eq!(TrinomialTree::new(0).unwrap_err(), FinanceError::InvalidPeriods);
+        assert_eq!(TrinomialTree::new(10).unwrap().with_lambda(0.9).unwrap_err(), FinanceError::InvalidInput);
+        let bad = LatticeBarrier { barrier_type: BarrierType::DownAndOut, level: -1.0, rebate: 0.0 };
+        assert_eq!(TrinomialTree::new(10).unwrap().with_barrier(bad).unwrap_err(), FinanceError::InvalidMonetaryValue);
+        // Knock-in americana: no hay paridad in-out
+        let knock_in = LatticeBarrier { barrier_type: BarrierType::DownAndIn, level: 80.0, rebate: 0.0 };
+        let tree = TrinomialTree::new(50).unwrap().with_exercise(ExerciseStyle::American).unwrap().with_barrier(knock_in).unwrap();
+        assert_eq!(tree.price(100.0, 100.0, 1.0, 0.05, 0.2, OptionType::Put).unwrap_err(), FinanceError::InvalidInput);
+    }
+}
@@ -0,0 +1,303 @@
+//! Opciones sobre dos activos lognormales correlacionados.
+//!
+//! Con F_i = S_i e^{(r - q_i)T} y df = e^{-rT}:
+//!
+//! - Intercambio (Margrabe, 1978), paga max(S1_T - S2_T, 0):
+//!   V = S1 e^{-q1 T} N(d1) - S2 e^{-q2 T} N(d2)
+//!   σ² = σ1² + σ2² - 2ρσ1σ2, d1 = [ln(F1/F2) + σ²T/2]/(σ√T), d2 = d1 - σ√T
+//! - Spread (call max(S1_T - S2_T - K, 0)), Kirk (1995): F2 + K se trata como
+//!   lognormal con b = F2/(F2 + K) y σ_K² = σ1² - 2bρσ1σ2 + b²σ2²:
+//!   C = df [F1 N(d1) - (F2 + K) N(d2)], d1 = [ln(F1/(F2 + K)) + σ_K²T/2]/(σ_K√T)
+//! - Spread, Bjerksund y Stensland (2011): misma volatilidad σ_K pero con
+//!   tres términos de probabilidad distintos,
+//!   C = df [F1 N(d1) - F2 N(d2) - K N(d3)]
+//!   d1 = [ln(F1/a) + (σ1²/2 - bρσ1σ2 + b²σ2²/2)T]/(σ_K√T)
+//!   d2 = [ln(F1/a) + (-σ1²/2 + ρσ1σ2 + (b²/2 - b)σ2²)T]/(σ_K√T)
+//!   d3 = [ln(F1/a) + (-σ1²/2 + b²σ2²/2)T]/(σ_K√T),  a = F2 + K
+//!
+//! Las puts de spread se obtienen por paridad: P = C - df (F1 - F2 - K).
+//! Con K = 0 ambas aproximaciones coinciden con Margrabe. Para spreads sobre
+//! futuros (crack, spark) basta con q_i = r, de modo que F_i = S_i.
+
+use crate::common::{
+    FinanceError, FinanceResult,
+    SpotPrice, ForwardPrice, StrikePrice, TimeToMaturity, InterestRate, Volatility,
+    DividendYield, OptionType
+};
+use super::black_scholes::{normal_cdf, EPS_TIME, EPS_VOL};
+
+/// Datos de mercado de dos activos correlacionados
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TwoAssetMarket {
+    /// Precio spot del primer activo (S1)
+    pub spot1: SpotPrice,
+    /// Precio spot del segundo activo (S2)
+    pub spot2: SpotPrice,
+    /// Volatilidad del primer activo (σ1)
+    pub sigma1: Volatility,
+    /// Volatilidad del segundo activo (σ2)
+    pub sigma2: Volatility,
+    /// Correlación entre los rendimientos logarítmicos (ρ)
+    pub rho: f64,
+    /// Rendimiento por dividendo continuo del primer activo (q1)
+    pub dividend_yield1: DividendYield,
+    /// Rendimiento por dividendo continuo del segundo activo (q2)
+    pub dividend_yield2: DividendYield,
+}
+
+impl TwoAssetMarket {
+    /// Crea un mercado de dos activos sin dividendos.
+    ///
+    /// # Errores
+    /// * `InvalidMonetaryValue` - Si algún spot no es positivo y finito
+    /// * `InvalidInterestRate` - Si alguna volatilidad es negativa o no finita
+    /// * `InvalidInput` - Si la correlación está fuera de [-1, 1]
+    pub fn new(spot1: SpotPrice, spot2: SpotPrice, sigma1: Volatility, sigma2: Volatility, rho: f64) -> FinanceResult<Self> {
+        if !spot1.is_finite() || !spot2.is_finite() || spot1 <= 0.0 || spot2 <= 0.0 {
+            return Err(FinanceError::InvalidMonetaryValue);
+        }
+        if !sigma1.is_finite() || !sigma2.is_finite() || sigma1 < 0.0 || sigma2 < 0.0 {
+            return Err(FinanceError::InvalidInterestRate);
+        }
+        if !(-1.0..=1.0).contains(&rho) {
+            return Err(FinanceError::InvalidInput);
+        }
+        Ok(TwoAssetMarket { spot1, spot2, sigma1, sigma2, rho, dividend_yield1: 0.0, dividend_yield2: 0.0 })
+    }
+
+    /// Cambia los rendimientos por dividendo continuos.
+    ///
+    /// # Errores
+    /// * `InvalidInterestRate` - Si alguno no es finito
+    pub fn with_dividend_yields(mut self, q1: DividendYield, q2: DividendYield) -> FinanceResult<Self> {
+        if !q1.is_finite() || !q2.is_finite() {
+            return Err(FinanceError::InvalidInterestRate);
+        }
+        self.dividend_yield1 = q1;
+        self.dividend_yield2 = q2;
+        Ok(self)
+    }
+
+    /// Forwards a plazo T: F_i = S_i e^{(r - q_i) T}
+    pub fn forwards(&self, t: TimeToMaturity, r: InterestRate) -> (ForwardPrice, ForwardPrice) {
+        (
+            self.spot1 * ((r - self.dividend_yield1) * t).exp(),
+            self.spot2 * ((r - self.dividend_yield2) * t).exp(),
+        )
+    }
+}
+
+/// Opción de Margrabe a recibir el activo 1 entregando el activo 2.
+///
+/// # Errores
+/// * `InvalidPeriods` - Si `t` es negativo
+/// * `InvalidInterestRate` - Si `r` no es finito
+pub fn exchange_option_price(market: &TwoAssetMarket, t: TimeToMaturity, r: InterestRate) -> FinanceResult<f64> {
+    validate_time_rate(t, r)?;
+    let (f1, f2) = market.forwards(t, r);
+    let df = (-r * t).exp();
+    let sigma = (market.sigma1.powi(2) + market.sigma2.powi(2) - 2.0 * market.rho * market.sigma1 * market.sigma2).max(0.0).sqrt();
+    Ok(df * black(f1, f2, sigma, t))
+}
+
+/// Spread con la aproximación de Kirk.
+///
+/// # Errores
+/// * `InvalidPeriods` - Si `t` es negativo
+/// * `InvalidInterestRate` - Si `r` no es finito
+/// * `InvalidInput` - Si F2 + K no es positivo
+pub fn kirk_spread_price(market: &TwoAssetMarket, k: StrikePrice, t: TimeToMaturity, r: InterestRate, kind: OptionType) -> FinanceResult<f64> {
+    let spread = SpreadTerms::new(market, k, t, r)?;
+    let call = spread.df * black(spread.f1, spread.a, spread.sigma, t);
+    Ok(spread.with_kind(call, kind))
+}
+
+/// Spread con la aproximación de Bjerksund-Stensland (2011).
+///
+/// # Errores
+/// * `InvalidPeriods` - Si `t` es negativo
+/// * `InvalidInterestRate` - Si `r` no es finito
+/// * `InvalidInput` - Si F2 + K no es positivo
+pub fn bjerksund_stensland_spread_price(market: &TwoAssetMarket, k: StrikePrice, t: TimeToMaturity, r: InterestRate, kind: OptionType) -> FinanceResult<f64> {
+    let spread = SpreadTerms::new(market, k, t, r)?;
+    if t < EPS_TIME || spread.sigma < EPS_VOL {
+        let call = spread.df * black(spread.f1, spread.a, spread.sigma, t);
+        return Ok(spread.with_kind(call, kind));
+    }
+
+    let (s1, s2, rho, b) = (market.sigma1, market.sigma2, market.rho, spread.b);
+    let sigma_sqrt_t = spread.sigma * t.sqrt();
+    let ln = (spread.f1 / spread.a).ln();
+    let d1 = (ln + (0.5 * s1 * s1 - b * rho * s1 * s2 + 0.5 * b * b * s2 * s2) * t) / sigma_sqrt_t;
+    let d2 = (ln + (-0.5 * s1 * s1 + rho * s1 * s2 + (0.5 * b * b - b) * s2 * s2) * t) / sigma_sqrt_t;
+    let d3 = (ln + (-0.5 * s1 * s1 + 0.5 * b * b * s2 * s2) * t) / sigma_sqrt_t;
+    let f2 = spread.a - k;
+    let call = spread.df * (spread.f1 * normal_cdf(d1) - f2 * normal_cdf(d2) - k * normal_cdf(d3));
+    Ok(spread.with_kind(call, kind))
+}
+
+/// Forwards, descuento y volatilidad efectiva comunes a Kirk y Bjerksund-Stensland.
+struct SpreadTerms {
+    f1: ForwardPrice,
+    /// a = F2 + K
+    a: f64,
+    /// b = F2 / (F2 + K)
+    b: f64,
+    sigma: Volatility,
+    df: f64,
+}
+
+impl SpreadTerms {
+    fn new(market: &TwoAssetMarket, k: StrikePrice, t: TimeToMaturity, r: InterestRate) -> FinanceResult<Self> {
+        validate_time_rate(t, r)?;
+        if !k.is_finite() {
+            return Err(FinanceError::InvalidMonetaryValue);
+        }
+        let (f1, f2) = market.forwards(t, r);
+        let a = f2 + k;
+        if a <= 0.0 {
+            return Err(FinanceError::InvalidInput);
+        }
+        let b = f2 / a;
+        let (s1, s2) = (market.sigma1, market.sigma2);
+        let sigma = (s1 * s1 - 2.0 * b * market.rho * s1 * s2 + b * b * s2 * s2).max(0.0).sqrt();
+        Ok(SpreadTerms { f1, a, b, sigma, df: (-r * t).exp() })
+    }
+
+    /// Precio de la put por paridad a partir de la call
+    fn with_kind(&self, call: f64, kind: OptionType) -> f64 {
+        match kind {
+            OptionType::Call => call,
+            OptionType::Put => call - self.df * (self.f1 - self.a),
+        }
+    }
+}
+
+fn validate_time_rate(t: TimeToMaturity, r: InterestRate) -> FinanceResult<()> {
+    if !t.is_finite() || t < 0.0 {
+        return Err(FinanceError::InvalidPeriods);
+    }
+    if !r.is_finite() {
+        return Err(FinanceError::InvalidInterestRate);
+    }
+    Ok(())
+}
+
+/// Black sin descontar: F N(d1) - K N(d2), con el límite intrínseco si σ√T → 0.
+fn black(f: ForwardPrice, k: f64, sigma: Volatility, t: TimeToMaturity) -> f64 {
+    let sigma_sqrt_t = sigma * t.sqrt();
+    if t < EPS_TIME || sigma < EPS_VOL {
+        return (f - k).max(0.0);
+    }
+    let d1 = ((f / k).ln() + 0.5 * sigma_sqrt_t * sigma_sqrt_t) / sigma_sqrt_t;
+    f * normal_cdf(d1) - k * normal_cdf(d1 - sigma_sqrt_t)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::options::call_price_dividend;
+
+    /// Spread exacto por integración sobre el segundo factor: condicionado a
+    /// Z2 = z, S1 es lognormal y el precio es un Black-Scholes.
+    fn spread_by_quadrature(market: &TwoAssetMarket, k: f64, t: f64, r: f64) -> f64 {
+        let (f1, f2) = market.forwards(t, r);
+        let (s1, s2, rho) = (market.sigma1, market.sigma2, market.rho);
+        let sqrt_t = t.sqrt();
+        let n = 4000;
+        let (lo, hi) = (-9.0, 9.0);
+        let h = (hi - lo) / n as f64;
+        let mut sum = 0.0;
+        for i in 0..=n {
+            let z = lo + i as f64 * h;
+            let weight = if i == 0 || i == n { 0.5 } else { 1.0 };
+            let s2_t = f2 * (s2 * sqrt_t * z - 0.5 * s2 * s2 * t).exp();
+            let f1_z = f1 * (rho * s1 * sqrt_t * z - 0.5 * rho * rho * s1 * s1 * t).exp();
+            let conditional = black(f1_z, s2_t + k, s1 * (1.0 - rho * rho).sqrt(), t);
+            sum += weight * conditional * (-0.5 * z * z).exp() / (2.0 * std::f64::consts::PI).sqrt();
+        }
+        (-r * t).exp() * sum * h
+    }
+
+    #[test]
+    fn exchange_parity() {
+        // Recibir 1 entregando 2 menos recibir 2 entregando 1 es un forward:
+        // S1 e^{-q1 T} - S2 e^{-q2 T}
+        let (t, r) = (0.1, 0.10);
+        let market = TwoAssetMarket::new(22.0, 20.0, 0.20, 0.25, -0.5).unwrap()
+            .with_dividend_yields(0.06, 0.04).unwrap();
+        let swapped = TwoAssetMarket::new(20.0, 22.0, 0.25, 0.20, -0.5).unwrap()
+            .with_dividend_yields(0.04, 0.06).unwrap();
+        let c12 = exchange_option_price(&market, t, r).unwrap();
+        let c21 = exchange_option_price(&swapped, t, r).unwrap();
+        let forward = 22.0 * (-0.06 * t).exp() - 20.0 * (-0.04 * t).exp();
+        assert!((c12 - c21 - forward).abs() < 1e-10);
+        assert!((c12 - 2.2827).abs() < 1e-4, "{c12}");
+    }
+
+    #[test]
+    fn exchange_with_riskless_second_asset_is_black_scholes() {
+        // σ2 = 0 y q2 = r: el activo 2 es un bono que paga S2 e^{rT} y Margrabe
+        // se reduce a una call de strike K = S2 e^{rT}
+        let (t, r) = (1.0, 0.05);
+        let market = TwoAssetMarket::new(100.0, 95.0, 0.3, 0.0, 0.0).unwrap()
+            .with_dividend_yields(0.02, 0.0).unwrap();
+        let price = exchange_option_price(&market, t, r).unwrap();
+        let call = call_price_dividend(100.0, 95.0 * (r * t).exp(), t, r, 0.02, 0.3).unwrap();
+        assert!((price - call).abs() < 1e-10);
+    }
+
+    #[test]
+    fn zero_strike_spread_is_margrabe() {
+        let market = TwoAssetMarket::new(110.0, 100.0, 0.3, 0.25, 0.6).unwrap()
+            .with_dividend_yields(0.01, 0.03).unwrap();
+        let margrabe = exchange_option_price(&market, 0.5, 0.04).unwrap();
+        let kirk = kirk_spread_price(&market, 0.0, 0.5, 0.04, OptionType::Call).unwrap();
+        let bs = bjerksund_stensland_spread_price(&market, 0.0, 0.5, 0.04, OptionType::Call).unwrap();
+        assert!((kirk - margrabe).abs() < 1e-10);
+        assert!((bs - margrabe).abs() < 1e-10);
+    }
+
+    #[test]
+    fn spread_approximations_match_quadrature() {
+        let (t, r) = (1.0, 0.05);
+        for rho in [-0.5, 0.3, 0.9] {
+            // Spread sobre futuros: q_i = r
+            let market = TwoAssetMarket::new(120.0, 100.0, 0.35, 0.25, rho).unwrap()
+                .with_dividend_yields(r, r).unwrap();
+            for k in [5.0, 20.0, 40.0] {
+                let exact = spread_by_quadrature(&market, k, t, r);
+                let kirk = kirk_spread_price(&market, k, t, r, OptionType::Call).unwrap();
+                let bs = bjerksund_stensland_spread_price(&market, k, t, r, OptionType::Call).unwrap();
+                // Kirk pierde precisión con K grande; Bjerksund-Stensland es un orden más preciso
+                assert!((kirk - exact).abs() < 0.1, "Kirk ρ={rho} K={k}: {kirk} vs {exact}");
+                assert!((bs - exact).abs() < 0.01, "BS ρ={rho} K={k}: {bs} vs {exact}");
+            }
+        }
+    }
+
+    #[test]
+    fn spread_put_call_parity() {
+        let (k, t, r) = (15.0, 0.75, 0.03);
+        let market = TwoAssetMarket::new(105.0, 90.0, 0.3, 0.2, 0.4).unwrap()
+            .with_dividend_yields(0.02, 0.01).unwrap();
+        let (f1, f2) = market.forwards(t, r);
+        let forward_value = (-r * t).exp() * (f1 - f2 - k);
+        for pricer in [kirk_spread_price, bjerksund_stensland_spread_price] {
+            let c = pricer(&market, k, t, r, OptionType::Call).unwrap();
+            let p = pricer(&market, k, t, r, OptionType::Put).unwrap();
+            assert!((c - p - forward_value).abs() < 1e-10);
+            assert!(p > 0.0);
+        }
+    }
+
+    #[test]
+    fn invalid_inputs() {
+        assert_eq!(TwoAssetMarket::new(100.0, 0.0, 0.2, 0.2, 0.5).unwrap_err(), FinanceError::InvalidMonetaryValue);
+        assert_eq!(TwoAssetMarket::new(100.0, 100.0, -0.2, 0.2, 0.5).unwrap_err(), FinanceError::InvalidInterestRate);
+        assert_eq!(TwoAssetMarket::new(100.0, 100.0, 0.2, 0.2, 1.5).unwrap_err(), FinanceError::InvalidInput);
+        let market = TwoAssetMarket::new(100.0, 50.0, 0.2, 0.2, 0.5).unwrap();
+        assert_eq!(kirk_spread_price(&market, -60.0, 1.0, 0.0, OptionType::Call).unwrap_err(), FinanceError::InvalidInput);
+        assert_eq!(exchange_option_price(&market, -1.0, 0.05).unwrap_err(), FinanceError::InvalidPeriods);
+    }
+}
@@ -0,0 +1,609 @@
+//! Superficie de volatilidad implícita.
+//!
+//! Cada vencimiento T_i (sección) guarda volatilidades sobre uno de tres
+//! ejes: strike K, moneyness K/F(T) o delta forward de la call N(d1). Todas
+//! las consultas se expresan en log-moneyness forward k = ln(K/F(T)), con
+//! F(T) = S₀ e^{(r-q)T}; en el eje delta el strike de cada nodo es
+//! k = σ²T/2 - σ√T N⁻¹(Δ).
+//!
+//! En strike se interpola la volatilidad de la sección (lineal o spline
+//! cúbico natural). Fuera de los nodos las alas son planas (volatilidad del
+//! nodo extremo) o siguen la SVI de Gatheral ajustada a la sección,
+//! w(k) = a + b(ρ(k - m) + √((k - m)² + σ²)),
+//! desplazada para empalmar con la varianza total del nodo extremo.
+//!
+//! En tiempo se interpola linealmente la varianza total w = σ²T a
+//! log-moneyness constante, lo que conserva la ausencia de arbitraje de
+//! calendario si las secciones la cumplen. Antes del primer vencimiento y
+//! después del último se mantiene la volatilidad de la sección extrema.
+
+use crate::common::{
+    FinanceError, FinanceResult,
+    DividendYield, ForwardPrice,
+    SpotPrice, StrikePrice, TimeToMaturity, InterestRate, Volatility,
+    OptionType, VolQuote
+};
+use crate::num::{brent, levenberg_marquardt, linear_interpolation, CubicSpline};
+use super::black_scholes::{normal_cdf, normal_inv_cdf, option_price_dividend};
+
+/// Mínimo de nodos para ajustar una SVI (cinco parámetros).
+const SVI_MIN_POINTS: usize = 5;
+/// Cota de b y de ρ durante el ajuste SVI.
+const SVI_MAX_B: f64 = 10.0;
+const SVI_RHO: f64 = 0.999;
+
+/// Coordenada de los nodos de cada sección
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StrikeAxis {
+    /// Precio de ejercicio K
+    Strike,
+    /// Moneyness forward K/F(T)
+    Moneyness,
+    /// Delta forward de la call N(d1) ∈ (0, 1)
+    Delta,
+}
+
+/// Interpolación de la volatilidad entre nodos de una sección
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SmileInterpolation {
+    /// Lineal a trozos
+    Linear,
+    /// Spline cúbico natural
+    CubicSpline,
+}
+
+/// Extrapolación de las alas de cada sección
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WingExtrapolation {
+    /// Volatilidad constante igual a la del nodo extremo
+    Flat,
+    /// Forma de la SVI ajustada a la sección
+    Svi,
+}
+
+/// Parametrización SVI "raw" de la varianza total de una sección
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SviParameters {
+    /// Nivel a
+    pub a: f64,
+    /// Pendiente de las alas b ≥ 0
+    pub b: f64,
+    /// Asimetría ρ ∈ (-1, 1)
+    pub rho: f64,
+    /// Desplazamiento m en log-moneyness
+    pub m: f64,
+    /// Curvatura σ > 0 en el mínimo
+    pub sigma: f64,
+}
+
+/// Superficie de volatilidad implícita
+#[derive(Debug, Clone, PartialEq)]
+pub struct VolSurface {
+    spot: SpotPrice,
+    rate: InterestRate,
+    dividend_yield: DividendYield,
+    axis: StrikeAxis,
+    interpolation: SmileInterpolation,
+    extrapolation: WingExtrapolation,
+    slices: Vec<Slice>,
+}
+
+/// Sección de un vencimiento
+#[derive(Debug, Clone, PartialEq)]
+struct Slice {
+    expiry: TimeToMaturity,
+    coordinates: Vec<f64>,
+    volatilities: Vec<Volatility>,
+    /// Log-moneyness forward de cada nodo
+    log_moneyness: Vec<f64>,
+    spline: CubicSpline,
+    svi: Option<SviParameters>,
+}
+
+impl SviParameters {
+    /// Crea la parametrización validando los parámetros.
+    ///
+    /// # Errores
+    /// * `InvalidInput` - Si b < 0, |ρ| ≥ 1, σ ≤ 0 o la varianza mínima
+    ///   a + bσ√(1 - ρ²) es negativa
+    pub fn new(a: f64, b: f64, rho: f64, m: f64, sigma: f64) -> FinanceResult<Self> {
+        if [a, b, rho, m, sigma].iter().any(|v| !v.is_finite())
+            || b < 0.0 || rho.abs() >= 1.0 || sigma <= 0.0
+            || a + b * sigma * (1.0 - rho * rho).sqrt() < 0.0
+        {
+            return Err(FinanceError::InvalidInput);
+        }
+        Ok(SviParameters { a, b, rho, m, sigma })
+    }
+
+    /// Varianza total w(k) en log-moneyness `k`.
+    pub fn total_variance(&self, k: f64) -> f64 {
+        let x = k - self.m;
+        self.a + self.b * (self.rho * x + (x * x + self.sigma * self.sigma).sqrt())
+    }
+
+    /// Ajusta la SVI por mínimos cuadrados a varianzas totales observadas.
+    ///
+    /// # Ejemplos
+    /// ```
+    /// use quant_finance::options::SviParameters;
+    ///
+    /// let truth = SviParameters::new(0.02, 0.1, -0.4, 0.05, 0.2).unwrap();
+    /// let k: Vec<f64> = (0..9).map(|i| -0.4 + 0.1 * i as f64).collect();
+    /// let w: Vec<f64> = k.iter().map(|&k| truth.total_variance(k)).collect();
+    /// let fit = SviParameters::fit(&k, &w).unwrap();
+    /// assert!((fit.total_variance(1.0) - truth.total_variance(1.0)).abs() < 1e-6);
+    /// ```
+    ///
+    /// # Errores
+    /// * `InvalidInput` - Si hay menos de cinco puntos, las longitudes no
+    ///   coinciden, algún dato no es finito o alguna varianza es negativa, o
+    ///   si el ajuste no da una SVI válida
+    pub fn fit(log_moneyness: &[f64], total_variance: &[f64]) -> FinanceResult<Self> {
+        if log_moneyness.len() < SVI_MIN_POINTS || log_moneyness.len() != total_variance.len()
+            || log_moneyness.iter().any(|k| !k.is_finite())
+            || total_variance.iter().any(|w| !w.is_finite() || *w < 0.0)
+        {
+            return Err(FinanceError::InvalidInput);
+        }
+        let (k_min, k_max) = log_moneyness.iter().fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), &k| (lo.min(k), hi.max(k)));
+        let span = (k_max - k_min).max(1e-3);
+        let (w_min, k_at_min) = total_variance.iter().zip(log_moneyness)
+            .fold((f64::INFINITY, 0.0), |best, (&w, &k)| if w < best.0 { (w, k) } else { best });
+        let w_max = total_variance.iter().fold(0.0_f64, |acc, &w| acc.max(w));
+
+        // Arranque: mínimo de la SVI en el nodo de menor varianza
+        let (b0, sigma0) = (0.1, 0.1 * span);
+        let x0 = [w_min - b0 * sigma0, b0, 0.0, k_at_min, sigma0];
+        let lower = [-w_max - 1.0, 0.0, -SVI_RHO, k_min - span, 1e-4 * span];
+        let upper = [w_max + 1.0, SVI_MAX_B, SVI_RHO, k_max + span, 2.0 * span];
+        let residuals = |p: &[f64]| -> FinanceResult<Vec<f64>> {
+            let svi = SviParameters { a: p[0], b: p[1], rho: p[2], m: p[3], sigma: p[4] };
+            Ok(log_moneyness.iter().zip(total_variance).map(|(&k, &w)| svi.total_variance(k) - w).collect())
+        };
+        let fit = levenberg_marquardt(residuals, &x0, &lower, &upper, 500, 1e-14)?;
+        SviParameters::new(fit.x[0], fit.x[1], fit.x[2], fit.x[3], fit.x[4])
+    }
+}
+
+impl VolSurface {
+    /// Superficie vacía sobre el eje `axis`, con interpolación lineal y alas
+    /// planas.
+    ///
+    /// # Ejemplos
+    /// ```
+    /// use quant_finance::options::{VolSurface, StrikeAxis, SmileInterpolation};
+    ///
+    /// let surface = VolSurface::new(100.0, 0.0, 0.0, StrikeAxis::Strike).unwrap()
+    ///     .with_interpolation(SmileInterpolation::CubicSpline)
+    ///     .with_slice(1.0, &[80.0, 100.0, 120.0], &[0.28, 0.2, 0.18]).unwrap()
+    ///     .with_slice(2.0, &[80.0, 100.0, 120.0], &[0.26, 0.21, 0.19]).unwrap();
+    /// assert!((surface.volatility(100.0, 1.0).unwrap() - 0.2).abs() < 1e-12);
+    /// // Ala plana más allá del último strike
+    /// assert!((surface.volatility(150.0, 1.0).unwrap() - 0.18).abs() < 1e-12);
+    /// ```
+    ///
+    /// # Errores
+    /// * `InvalidMonetaryValue` - Si el spot no es positivo
+    /// * `InvalidInterestRate` - Si r o q no son finitos
+    pub fn new(s0: SpotPrice, r: InterestRate, q: DividendYield, axis: StrikeAxis) -> FinanceResult<Self> {
+        if !s0.is_finite() || s0 <= 0.0 {
+            return Err(FinanceError::InvalidMonetaryValue);
+        }
+        if !r.is_finite() || !q.is_finite() {
+            return Err(FinanceError::InvalidInterestRate);
+        }
+        Ok(VolSurface {
+            spot: s0,
+            rate: r,
+            dividend_yield: q,
+            axis,
+            interpolation: SmileInterpolation::Linear,
+            extrapolation: WingExtrapolation::Flat,
+            slices: Vec::new(),
+        })
+    }
+
+    /// Superficie sobre el eje de strikes a partir de cotizaciones sueltas,
+    /// agrupadas por vencimiento.
+    ///
+    /// # Errores
+    /// * `InvalidInput` - Si no hay cotizaciones, algún vencimiento tiene
+    ///   menos de dos o se repite un strike dentro de un vencimiento
+    /// * Los de [`VolSurface::new`] y [`VolSurface::with_slice`]
+    pub fn from_quotes(quotes: &[VolQuote], s0: SpotPrice, r: InterestRate, q: DividendYield) -> FinanceResult<Self> {
+        if quotes.is_empty() {
+            return Err(FinanceError::InvalidInput);
+        }
+        let mut sorted = quotes.to_vec();
+        sorted.sort_by(|a, b| a.maturity.total_cmp(&b.maturity).then(a.strike.total_cmp(&b.strike)));
+        let mut surface = VolSurface::new(s0, r, q, StrikeAxis::Strike)?;
+        for group in sorted.chunk_by(|a, b| a.maturity == b.maturity) {
+            let strikes: Vec<f64> = group.iter().map(|quote| quote.strike).collect();
+            let volatilities: Vec<f64> = group.iter().map(|quote| quote.volatility).collect();
+            surface = surface.with_slice(group[0].maturity, &strikes, &volatilities)?;
+        }
+        Ok(surface)
+    }
+
+    /// Añade la sección de vencimiento `expiry` con volatilidades
+    /// `volatilities` en los nodos `coordinates` del eje de la superficie.
+    ///
+    /// # Errores
+    /// * `InvalidPeriods` - Si el vencimiento no es positivo
+    /// * `InvalidInterestRate` - Si alguna volatilidad no es positiva
+    /// * `InvalidMonetaryValue` - Si algún strike no es positivo (eje de strikes)
+    /// * `InvalidInput` - Si hay menos de dos nodos, las longitudes no
+    ///   coinciden, los nodos no son estrictamente crecientes, alguna
+    ///   moneyness no es positiva o alguna delta está fuera de (0, 1), los
+    ///   strikes implícitos en las deltas no son monótonos, el vencimiento ya
+    ///   existe o la SVI de las alas no puede ajustarse
+    pub fn with_slice(mut self, expiry: TimeToMaturity, coordinates: &[f64], volatilities: &[Volatility]) -> FinanceResult<Self> {
+        if !expiry.is_finite() || expiry <= 0.0 {
+            return Err(FinanceError::InvalidPeriods);
+        }
+        if volatilities.iter().any(|sigma| !sigma.is_finite() || *sigma <= 0.0) {
+            return Err(FinanceError::InvalidInterestRate);
+        }
+        if self.axis == StrikeAxis::Strike && coordinates.iter().any(|k| !k.is_finite() || *k <= 0.0) {
+            return Err(FinanceError::InvalidMonetaryValue);
+        }
+        let in_domain = |x: &f64| match self.axis {
+            StrikeAxis::Strike | StrikeAxis::Moneyness => *x > 0.0,
+            StrikeAxis::Delta => *x > 0.0 && *x < 1.0,
+        };
+        if !coordinates.iter().all(in_domain) || self.slices.iter().any(|slice| slice.expiry == expiry) {
+            return Err(FinanceError::InvalidInput);
+        }
+        let spline = CubicSpline::new(coordinates, volatilities)?;
+
+        let forward = self.forward(expiry);
+        let sqrt_t = expiry.sqrt();
+        let log_moneyness: Vec<f64> = coordinates.iter().zip(volatilities)
+            .map(|(&x, &sigma)| match self.axis {
+                StrikeAxis::Strike => (x / forward).ln(),
+                StrikeAxis::Moneyness => x.ln(),
+                StrikeAxis::Delta => 0.5 * sigma * sigma * expiry - sigma * sqrt_t * normal_inv_cdf(x),
+            })
+            .collect();
+        if self.axis == StrikeAxis::Delta && log_moneyness.windows(2).any(|w| w[1] >= w[0]) {
+            return Err(FinanceError::InvalidInput);
+        }
+
+        let mut slice = Slice {
+            expiry,
+            coordinates: coordinates.to_vec(),
+            volatilities: volatilities.to_vec(),
+            log_moneyness,
+            spline,
+            svi: None,
+        };
+        if self.extrapolation == WingExtrapolation::Svi {
+            slice.fit_svi()?;
+        }
+        let position = self.slices.partition_point(|other| other.expiry < expiry);
+        self.slices.insert(position, slice);
+        Ok(self)
+    }
+
+    /// Cambia la interpolación entre nodos de cada sección.
+    pub fn with_interpolation(mut self, interpolation: SmileInterpolation) -> Self {
+        self.interpolation = interpolation;
+        self
+    }
+
+    /// Cambia la extrapolación de las alas; con `Svi` ajusta una SVI a cada
+    /// sección existente y a las que se añadan después.
+    ///
+    /// # Errores
+    /// * `InvalidInput` - Si alguna sección tiene menos de cinco nodos o el
+    ///   ajuste SVI falla
+    pub fn with_extrapolation(mut self, extrapolation: WingExtrapolation) -> FinanceResult<Self> {
+        for slice in &mut self.slices {
+            match extrapolation {
+                WingExtrapolation::Svi => slice.fit_svi()?,
+                WingExtrapolation::Flat => slice.svi = None,
+            }
+        }
+        self.extrapolation = extrapolation;
+        Ok(self)
+    }
+
+    /// Forward F(T) = S₀ e^{(r-q)T}.
+    pub fn forward(&self, t: TimeToMaturity) -> ForwardPrice {
+        self.spot * ((self.rate - self.dividend_yield) * t).exp()
+    }
+
+    /// Volatilidad implícita para el strike `k` y el vencimiento `t`.
+    ///
+    /// # Errores
+    /// * `InvalidInput` - Si la superficie no tiene secciones
+    /// * `InvalidMonetaryValue` - Si el strike no es positivo
+    /// * `InvalidPeriods` - Si `t` es negativo
+    /// * `NoConvergence` - Si falla la búsqueda de la delta (eje delta)
+    pub fn volatility(&self, k: StrikePrice, t: TimeToMaturity) -> FinanceResult<Volatility> {
+        if !k.is_finite() || k <= 0.0 {
+            return Err(FinanceError::InvalidMonetaryValue);
+        }
+        if !t.is_finite() || t < 0.0 {
+            return Err(FinanceError::InvalidPeriods);
+        }
+        let (first, last) = match (self.slices.first(), self.slices.last()) {
+            (Some(first), Some(last)) => (first, last),
+            _ => return Err(FinanceError::InvalidInput),
+        };
+        let log_moneyness = (k / self.forward(t)).ln();
+        if t <= first.expiry {
+            return Ok((self.slice_variance(first, log_moneyness)? / first.expiry).sqrt());
+        }
+        if t >= last.expiry {
+            return Ok((self.slice_variance(last, log_moneyness)? / last.expiry).sqrt());
+        }
+        let i = self.slices.partition_point(|slice| slice.expiry <= t);
+        let (before, after) = (&self.slices[i - 1], &self.slices[i]);
+        let w_before = self.slice_variance(before, log_moneyness)?;
+        let w_after = self.slice_variance(after, log_moneyness)?;
+        let weight = (t - before.expiry) / (after.expiry - before.expiry);
+        Ok(((w_before + weight * (w_after - w_before)) / t).sqrt())
+    }
+
+    /// Varianza total σ²(K, T)·T.
+    ///
+    /// # Errores
+    /// Los mismos que [`VolSurface::volatility`].
+    pub fn total_variance(&self, k: StrikePrice, t: TimeToMaturity) -> FinanceResult<f64> {
+        let sigma = self.volatility(k, t)?;
+        Ok(sigma * sigma * t)
+    }
+
+    /// Precio de Black-Scholes con dividendo continuo usando la volatilidad
+    /// de la superficie para (K, T).
+    ///
+    /// # Errores
+    /// Los mismos que [`VolSurface::volatility`].
+    pub fn price(&self, k: StrikePrice, t: TimeToMaturity, kind: OptionType) -> FinanceResult<f64> {
+        let sigma = self.volatility(k, t)?;
+        option_price_dividend(self.spot, k, t, self.rate, self.dividend_yield, sigma, kind)
+    }
+
+    /// Varianza total de una sección en log-moneyness forward `k`.
+    fn slice_variance(&self, slice: &Slice, k: f64) -> FinanceResult<f64> {
+        let (low, high) = slice.wing_nodes();
+        let edge = if k <= slice.log_moneyness[low] { Some(low) } else if k >= slice.log_moneyness[high] { Some(high) } else { None };
+        if let Some(edge) = edge {
+            let w_edge = slice.volatilities[edge].powi(2) * slice.expiry;
+            return Ok(match (self.extrapolation, slice.svi) {
+                (WingExtrapolation::Svi, Some(svi)) => {
+                    (w_edge + svi.total_variance(k) - svi.total_variance(slice.log_moneyness[edge])).max(0.0)
+                }
+                _ => w_edge,
+            });
+        }
+
+        let coordinate = match self.axis {
+            StrikeAxis::Strike => self.forward(slice.expiry) * k.exp(),
+            StrikeAxis::Moneyness => k.exp(),
+            StrikeAxis::Delta => {
+                // Δ = N(d1(k, σ(Δ))) tiene raíz entre las deltas de los nodos
+                // que rodean a k, ya que d1 decrece con k
+                let sqrt_t = slice.expiry.sqrt();
+                let gap = |delta: f64| -> f64 {
+                    let sigma = self.smile(slice, delta).unwrap_or(f64::NAN);
+                    delta - normal_cdf((-k + 0.5 * sigma * sigma * slice.expiry) / (sigma * sqrt_t))
+                };
+                brent(gap, slice.coordinates[0], slice.coordinates[slice.coordinates.len() - 1], 1e-14, 200)
+                    .map_err(|_| FinanceError::NoConvergence)?
+            }
+        };
+        let sigma = self.smile(slice, coordinate)?.max(0.0);
+        Ok(sigma * sigma * slice.expiry)
+    }
+
+    /// Volatilidad interpolada en la coordenada `x` del eje.
+    fn smile(&self, slice: &Slice, x: f64) -> FinanceResult<Volatility> {
+        match self.interpolation {
+            SmileInterpolation::Linear => linear_interpolation(&slice.coordinates, &slice.volatilities, x),
+            SmileInterpolation::CubicSpline => Ok(slice.spline.evaluate(x)),
+        }
+    }
+}
+
+impl Slice {
+    /// Índices de los nodos de menor y mayor log-moneyness.
+    fn wing_nodes(&self) -> (usize, usize) {
+        let last = self.log_moneyness.len() - 1;
+        if self.log_moneyness[0] < self.log_moneyness[last] { (0, last) } else { (last, 0) }
+    }
+
+    fn fit_svi(&mut self) -> FinanceResult<()> {
+        let variances: Vec<f64> = self.volatilities.iter().map(|sigma| sigma * sigma * self.expiry).collect();
+        self.svi = Some(SviParameters::fit(&self.log_moneyness, &variances)?);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::options::d1_d2_dividend;
+
+    fn smile_surface(axis: StrikeAxis) -> VolSurface {
+        VolSurface::new(100.0, 0.03, 0.01, axis).unwrap()
+            .with_slice(0.5, &[80.0, 90.0, 100.0, 110.0, 120.0], &[0.30, 0.25, 0.21, 0.19, 0.185]).unwrap()
+            .with_slice(1.5, &[70.0, 85.0, 100.0, 115.0, 130.0], &[0.28, 0.24, 0.22, 0.205, 0.20]).unwrap()
+    }
+
+    #[test]
+    fn reproduces_pillars() {
+        for interpolation in [SmileInterpolation::Linear, SmileInterpolation::CubicSpline] {
+            let surface = smile_surface(StrikeAxis::Strike).with_interpolation(interpolation);
+            for (k, sigma) in [(80.0, 0.30), (100.0, 0.21), (120.0, 0.185)] {
+                assert!((surface.volatility(k, 0.5).unwrap() - sigma).abs() < 1e-12);
+            }
+            for (k, sigma) in [(70.0, 0.28), (115.0, 0.205)] {
+                assert!((surface.volatility(k, 1.5).unwrap() - sigma).abs() < 1e-12);
+            }
+        }
+        // Las secciones se ordenan por vencimiento
+        let reversed = VolSurface::new(100.0, 0.03, 0.01, StrikeAxis::Strike).unwrap()
+            .with_slice(1.5, &[70.0, 85.0, 100.0, 115.0, 130.0], &[0.28, 0.24, 0.22, 0.205, 0.20]).unwrap()
+            .with_slice(0.5, &[80.0, 90.0, 100.0, 110.0, 120.0], &[0.30, 0.25, 0.21, 0.19, 0.185]).unwrap();
+        assert_eq!(reversed, smile_surface(StrikeAxis::Strike));
+    }
+
+    #[test]
+    fn total_variance_is_linear_in_time() {
+        let surface = VolSurface::new(100.0, 0.05, 0.0, StrikeAxis::Moneyness).unwrap()
+            .with_slice(0.5, &[0.8, 1.2], &[0.2, 0.2]).unwrap()
+            .with_slice(2.0, &[0.8, 1.2], &[0.3, 0.3]).unwrap();
+        let expected = 0.02 + (0.18 - 0.02) * (1.0 - 0.5) / 1.5;
+        assert!((surface.total_variance(surface.forward(1.0), 1.0).unwrap() - expected).abs() < 1e-14);
+        // Volatilidad constante fuera del rango de vencimientos
+        assert!((surface.volatility(100.0, 0.1).unwrap() - 0.2).abs() < 1e-14);
+        assert!((surface.volatility(100.0, 0.0).unwrap() - 0.2).abs() < 1e-14);
+        assert!((surface.volatility(100.0, 5.0).unwrap() - 0.3).abs() < 1e-14);
+
+        // Interpolación a moneyness forward constante: el strike de cada
+        // sección se desplaza con el forward
+        let surface = VolSurface::new(100.0, 0.05, 0.0, StrikeAxis::Strike).unwrap()
+            .with_slice(1.0, &[90.0, 120.0], &[0.25, 0.15]).unwrap()
+            .with_slice(2.0, &[90.0, 120.0], &[0.25, 0.15]).unwrap();
+        let k = 110.0;
+        let w1 = surface.volatility(k * surface.forward(1.0) / surface.forward(1.5), 1.0).unwrap().powi(2);
+        let w2 = surface.volatility(k * surface.forward(2.0) / surface.forward(1.5), 2.0).unwrap().powi(2) * 2.0;
+        let expected = 0.5 * (w1 + w2);
+        assert!((surface.total_variance(k, 1.5).unwrap() - expected).abs() < 1e-14);
+    }
+
+    #[test]
+    fn cubic_spline_smile_is_smoother() {
+        // Sonrisa parabólica en moneyness
+        let truth = |x: f64| 0.2 + 0.5 * (x - 1.0).powi(2);
+        let nodes = [0.7, 0.85, 1.0, 1.15, 1.3];
+        let vols = nodes.map(truth);
+        let linear = VolSurface::new(100.0, 0.0, 0.0, StrikeAxis::Moneyness).unwrap()
+            .with_slice(1.0, &nodes, &vols).unwrap();
+        let cubic = linear.clone().with_interpolation(SmileInterpolation::CubicSpline);
+        for x in [0.78, 0.93, 1.07, 1.22] {
+            let linear_error = (linear.volatility(100.0 * x, 1.0).unwrap() - truth(x)).abs();
+            let cubic_error = (cubic.volatility(100.0 * x, 1.0).unwrap() - truth(x)).abs();
+            assert!(cubic_error < 0.5 * linear_error);
+        }
+    }
+
+    #[test]
+    fn wing_extrapolation() {
+        let svi = SviParameters::new(0.01, 0.08, -0.5, 0.02, 0.15).unwrap();
+        let t = 0.75;
+        let strikes = [80.0, 90.0, 95.0, 100.0, 105.0, 110.0, 125.0];
+        let surface = VolSurface::new(100.0, 0.0, 0.0, StrikeAxis::Strike).unwrap();
+        let vols = strikes.map(|k: f64| (svi.total_variance((k / 100.0).ln()) / t).sqrt());
+        let flat = surface.with_slice(t, &strikes, &vols).unwrap();
+        assert_eq!(flat.volatility(50.0, t).unwrap(), vols[0]);
+        assert_eq!(flat.volatility(200.0, t).unwrap(), vols[6]);
+
+        // Con datos SVI exactos el ala reproduce la SVI y empalma con el nodo
+        let wings = flat.with_extrapolation(WingExtrapolation::Svi).unwrap();
+        for k in [40.0, 60.0, 150.0, 250.0] {
+            let expected = (svi.total_variance((k / 100.0_f64).ln()) / t).sqrt();
+            assert!((wings.volatility(k, t).unwrap() - expected).abs() < 1e-6);
+        }
+        let edge = wings.volatility(125.0, t).unwrap();
+        assert!((wings.volatility(125.0 + 1e-6, t).unwrap() - edge).abs() < 1e-8);
+        assert!(wings.volatility(250.0, t).unwrap() > edge);
+
+        // La SVI necesita cinco nodos por sección
+        let short = VolSurface::new(100.0, 0.0, 0.0, StrikeAxis::Strike).unwrap()
+            .with_slice(t, &[90.0, 100.0, 110.0], &[0.25, 0.2, 0.18]).unwrap();
+        assert_eq!(short.with_extrapolation(WingExtrapolation::Svi).unwrap_err(), FinanceError::InvalidInput);
+    }
+
+    #[test]
+    fn svi_fit_recovers_parameters() {
+        let truth = SviParameters::new(0.03, 0.15, -0.3, -0.05, 0.25).unwrap();
+        let k: Vec<f64> = (0..11).map(|i| -0.6 + 0.12 * i as f64).collect();
+        let w: Vec<f64> = k.iter().map(|&k| truth.total_variance(k)).collect();
+        let fit = SviParameters::fit(&k, &w).unwrap();
+        for (fitted, exact) in [(fit.a, truth.a), (fit.b, truth.b), (fit.rho, truth.rho), (fit.m, truth.m), (fit.sigma, truth.sigma)] {
+            assert!((fitted - exact).abs() < 1e-5);
+        }
+        assert_eq!(SviParameters::fit(&k[..4], &w[..4]).unwrap_err(), FinanceError::InvalidInput);
+        assert_eq!(SviParameters::new(-0.1, 0.1, 0.0, 0.0, 0.1).unwrap_err(), FinanceError::InvalidInput);
+    }
+
+    #[test]
+    fn delta_axis_matches_strike_axis() {
+        let (s0, r, q, t) = (100.0, 0.03, 0.01, 0.5);
+        let strikes = [80.0, 90.0, 100.0, 110.0, 120.0];
+        let vols = [0.30, 0.25, 0.21, 0.19, 0.185];
+        let by_strike = VolSurface::new(s0, r, q, StrikeAxis::Strike).unwrap()
+            .with_slice(t, &strikes, &vols).unwrap();
+        // Delta forward de la call en cada nodo, en orden creciente
+        let mut deltas: Vec<f64> = strikes.iter().zip(&vols)
+            .map(|(&k, &sigma)| normal_cdf(d1_d2_dividend(s0, k, t, r, q, sigma).unwrap().0))
+            .collect();
+        let mut delta_vols = vols.to_vec();
+        deltas.reverse();
+        delta_vols.reverse();
+        let by_delta = VolSurface::new(s0, r, q, StrikeAxis::Delta).unwrap()
+            .with_slice(t, &deltas, &delta_vols).unwrap();
+        for (&k, &sigma) in strikes.iter().zip(&vols) {
+            // En los nodos el strike cae en el extremo del tramo y se
+            // recupera la volatilidad cotizada
+            assert!((by_delta.volatility(k, t).unwrap() - sigma).abs() < 1e-10);
+        }
+        for (&k, &expected) in [95.0, 105.0].iter().zip(&[by_strike.volatility(95.0, t).unwrap(), by_strike.volatility(105.0, t).unwrap()]) {
+            let sigma = by_delta.volatility(k, t).unwrap();
+            // Otra coordenada de interpolación, mismo orden de magnitud
+            assert!((sigma - expected).abs() < 5e-3);
+            // La delta usada es coherente con la volatilidad obtenida
+            let delta = normal_cdf(d1_d2_dividend(s0, k, t, r, q, sigma).unwrap().0);
+            let lookup = linear_interpolation(&deltas, &delta_vols, delta).unwrap();
+            assert!((lookup - sigma).abs() < 1e-10);
+        }
+        let cubic = by_delta.with_interpolation(SmileInterpolation::CubicSpline);
+        assert!((cubic.volatility(100.0, t).unwrap() - 0.21).abs() < 1e-5);
+    }
+
+    #[test]
+    fn prices_with_surface_volatility() {
+        let surface = smile_surface(StrikeAxis::Strike).with_interpolation(SmileInterpolation::CubicSpline);
+        for (k, t) in [(85.0, 0.3), (100.0, 1.0), (125.0, 1.2), (140.0, 3.0)] {
+            let sigma = surface.volatility(k, t).unwrap();
+            for kind in [OptionType::Call, OptionType::Put] {
+                let expected = option_price_dividend(100.0, k, t, 0.03, 0.01, sigma, kind).unwrap();
+                assert_eq!(surface.price(k, t, kind).unwrap(), expected);
+            }
+        }
+
+        let quotes = [
+            VolQuote { strike: 110.0, maturity: 1.0, volatility: 0.19 },
+            VolQuote { strike: 90.0, maturity: 1.0, volatility: 0.25 },
+            VolQuote { strike: 100.0, maturity: 0.5, volatility: 0.2 },
+            VolQuote { strike: 120.0, maturity: 0.5, volatility: 0.18 },
+        ];
+        let surface = VolSurface::from_quotes(&quotes, 100.0, 0.0, 0.0).unwrap();
+        assert!((surface.volatility(90.0, 1.0).unwrap() - 0.25).abs() < 1e-12);
+        assert!((surface.volatility(120.0, 0.5).unwrap() - 0.18).abs() < 1e-12);
+    }
+
+    #[test]
+    fn invalid_inputs() {
+        let empty = VolSurface::new(100.0, 0.0, 0.0, StrikeAxis::Strike).unwrap();
+        assert_eq!(empty.volatility(100.0, 1.0).unwrap_err(), FinanceError::InvalidInput);
+        assert_eq!(VolSurface::new(0.0, 0.0, 0.0, StrikeAxis::Strike).unwrap_err(), FinanceError::InvalidMonetaryValue);
+        assert_eq!(VolSurface::new(100.0, f64::NAN, 0.0, StrikeAxis::Strike).unwrap_err(), FinanceError::InvalidInterestRate);
+        assert_eq!(empty.clone().with_slice(0.0, &[90.0, 110.0], &[0.2, 0.2]).unwrap_err(), FinanceError::InvalidPeriods);
+        assert_eq!(empty.clone().with_slice(1.0, &[90.0, 110.0], &[0.2, -0.2]).unwrap_err(), FinanceError::InvalidInterestRate);
+        assert_eq!(empty.clone().with_slice(1.0, &[-90.0, 110.0], &[0.2, 0.2]).unwrap_err(), FinanceError::InvalidMonetaryValue);
+        assert_eq!(empty.clone().with_slice(1.0, &[110.0, 90.0], &[0.2, 0.2]).unwrap_err(), FinanceError::InvalidInput);
+        assert_eq!(empty.clone().with_slice(1.0, &[90.0], &[0.2]).unwrap_err(), FinanceError::InvalidInput);
+        let surface = empty.with_slice(1.0, &[90.0, 110.0], &[0.2, 0.2]).unwrap();
+        assert_eq!(surface.clone().with_slice(1.0, &[90.0, 110.0], &[0.2, 0.2]).unwrap_err(), FinanceError::InvalidInput);
+        assert_eq!(surface.volatility(0.0, 1.0).unwrap_err(), FinanceError::InvalidMonetaryValue);
+        assert_eq!(surface.volatility(100.0, -1.0).unwrap_err(), FinanceError::InvalidPeriods);
+        let delta = VolSurface::new(100.0, 0.0, 0.0, StrikeAxis::Delta).unwrap();
+        assert_eq!(delta.clone().with_slice(1.0, &[0.25, 1.0], &[0.2, 0.2]).unwrap_err(), FinanceError::InvalidInput);
+        assert_eq!(VolSurface::from_quotes(&[], 100.0, 0.0, 0.0).unwrap_err(), FinanceError::InvalidInput);
+    }
+}
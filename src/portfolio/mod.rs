@@ -1,4 +1,4 @@
-///! Teoría de portafolios y modelos de valoración de activos
+//! Teoría de portafolios y modelos de valoración de activos
 pub mod capm;
 
 pub use capm::{
@@ -58,7 +58,7 @@ pub fn future_value(
 /// use quant_finance::time_value::present_value;
 ///
 /// let pv = present_value(1628.89, 0.05, 10).unwrap();
-/// assert!((pv - 1000.0)abs() < 0.01);
+/// assert!((pv - 1000.0).abs() < 0.01);
 /// ```
 ///
 /// # Errores
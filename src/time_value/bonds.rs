@@ -36,7 +36,7 @@ impl Bond {
     /// ```
     /// use quant_finance::time_value::bonds::Bond;
     ///
-    /// let Bond = Bond::new_coupon(1000.0, 0.05, 10, 0.04).unwrap();
+    /// let bond = Bond::new_coupon_bond(1000.0, 0.05, 10, 0.04).unwrap();
     /// ```
     pub fn new_coupon_bond(
         face_value: MonetaryValue,
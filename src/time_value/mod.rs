@@ -6,7 +6,7 @@
 pub mod basic;
 pub mod simple;
 pub mod compound;
-mod bonds;
+pub mod bonds;
 
 // Re-exportar funciones principales
 pub use basic::{future_value, present_value};